@@ -8,12 +8,18 @@ use node_template_runtime as runtime;
 use runtime::{AccountId, Balance, BalancesCall, SystemCall};
 use sc_cli::Result;
 use sc_client_api::BlockBackend;
-use sp_core::{Encode, Pair};
+use sc_keystore::LocalKeystore;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::AuraApi;
+use sp_core::{crypto::key_types::AURA, sr25519, Encode, Pair};
 use sp_inherents::{InherentData, InherentDataProvider};
 use sp_keyring::Sr25519Keyring;
-use sp_runtime::{OpaqueExtrinsic, SaturatedConversion};
+use sp_keystore::SyncCryptoStore;
+use sp_runtime::{traits::Header as _, OpaqueExtrinsic, SaturatedConversion};
+use sp_ver::RandomSeedInherentDataProvider;
 
-use std::{sync::Arc, time::Duration};
+use std::{cell::RefCell, rc::Rc, sync::Arc, time::Duration};
 
 /// Generates extrinsics for the `benchmark overhead` command.
 ///
@@ -52,6 +58,45 @@ impl frame_benchmarking_cli::ExtrinsicBuilder for RemarkBuilder {
 	}
 }
 
+/// Generates extrinsics for the `benchmark overhead --ver` command.
+///
+/// Shares the client with the benchmark, which imports blocks in between building extrinsics.
+///
+/// Note: Should only be used for benchmarking.
+pub struct VerRemarkBuilder {
+	client: Rc<RefCell<FullClient>>,
+}
+
+impl VerRemarkBuilder {
+	/// Creates a new [`Self`] from the given client.
+	pub fn new(client: Rc<RefCell<FullClient>>) -> Self {
+		Self { client }
+	}
+}
+
+impl frame_benchmarking_cli::ExtrinsicBuilder for VerRemarkBuilder {
+	fn pallet(&self) -> &str {
+		"system"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"remark"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		let acc = Sr25519Keyring::Bob.pair();
+		let extrinsic: OpaqueExtrinsic = create_benchmark_extrinsic(
+			&self.client.borrow(),
+			acc,
+			SystemCall::remark { remark: vec![] }.into(),
+			nonce,
+		)
+		.into();
+
+		Ok(extrinsic)
+	}
+}
+
 /// Generates `Balances::TransferKeepAlive` extrinsics for the benchmarks.
 ///
 /// Note: Should only be used for benchmarking.
@@ -162,3 +207,55 @@ pub fn inherent_benchmark_data() -> Result<InherentData> {
 		.map_err(|e| format!("creating inherent data: {:?}", e))?;
 	Ok(inherent_data)
 }
+
+/// Generates inherent data of the two blocks built by the `benchmark overhead --ver` command.
+///
+/// The blocks are built on top of genesis in AURA slots 1 and 3, see
+/// `frame_benchmarking_cli::OverheadCmd::run_ver`. Their shuffling seeds are signed with the
+/// development key of the respective slot author.
+///
+/// Note: Should only be used for benchmarking.
+pub fn inherent_benchmark_data_ver(client: &FullClient) -> Result<(InherentData, InherentData)> {
+	let genesis_hash = client.info().genesis_hash;
+	let genesis = client
+		.header(genesis_hash)
+		.map_err(|e| format!("fetching genesis header: {:?}", e))?
+		.ok_or("Genesis header not found")?;
+	let authorities = client
+		.runtime_api()
+		.authorities(genesis_hash)
+		.map_err(|e| format!("fetching AURA authorities: {:?}", e))?;
+	if authorities.is_empty() {
+		return Err("No AURA authorities found".into())
+	}
+	let slot_duration = sc_consensus_aura::slot_duration(client)
+		.map_err(|e| format!("fetching AURA slot duration: {:?}", e))?;
+
+	let keystore = LocalKeystore::in_memory();
+	for account in Sr25519Keyring::iter() {
+		SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some(&account.to_seed()))
+			.map_err(|e| format!("inserting development key: {:?}", e))?;
+	}
+	let author = |slot: u64| -> sr25519::Public {
+		authorities[(slot % authorities.len() as u64) as usize].clone().into()
+	};
+	let first_seed = sp_ver::calculate_next_seed(&keystore, &author(1), genesis.seed())
+		.ok_or("AURA author of the first block is not a development account")?;
+	let second_seed = sp_ver::calculate_next_seed(&keystore, &author(3), &first_seed)
+		.ok_or("AURA author of the second block is not a development account")?;
+
+	let seeded_inherent_data = |slot: u64, seed| -> Result<InherentData> {
+		let mut inherent_data = InherentData::new();
+		let timestamp =
+			sp_timestamp::InherentDataProvider::new((slot * slot_duration.as_millis()).into());
+		futures::executor::block_on(async {
+			timestamp.provide_inherent_data(&mut inherent_data).await?;
+			RandomSeedInherentDataProvider(seed)
+				.provide_inherent_data(&mut inherent_data)
+				.await
+		})
+		.map_err(|e| format!("creating inherent data: {:?}", e))?;
+		Ok(inherent_data)
+	};
+	Ok((seeded_inherent_data(1, first_seed)?, seeded_inherent_data(3, second_seed)?))
+}
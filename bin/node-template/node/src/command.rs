@@ -1,5 +1,8 @@
 use crate::{
-	benchmarking::{inherent_benchmark_data, RemarkBuilder, TransferKeepAliveBuilder},
+	benchmarking::{
+		inherent_benchmark_data, inherent_benchmark_data_ver, RemarkBuilder,
+		TransferKeepAliveBuilder, VerRemarkBuilder,
+	},
 	chain_spec,
 	cli::{Cli, Subcommand},
 	service,
@@ -9,6 +12,7 @@ use node_template_runtime::{Block, EXISTENTIAL_DEPOSIT};
 use sc_cli::{ChainSpec, RuntimeVersion, SubstrateCli};
 use sc_service::PartialComponents;
 use sp_keyring::Sr25519Keyring;
+use std::{cell::RefCell, rc::Rc};
 
 #[cfg(feature = "try-runtime")]
 use try_runtime_cli::block_building_info::timestamp_with_aura_info;
@@ -144,17 +148,14 @@ pub fn run() -> sc_cli::Result<()> {
 
 						cmd.run(config, client, db, storage)
 					},
+					// VER runtime, so overhead is benchmarked with VER blocks even without `--ver`.
 					BenchmarkCmd::Overhead(cmd) => {
-						let PartialComponents { client, .. } = service::new_partial(&config)?;
-						let ext_builder = RemarkBuilder::new(client.clone());
-
-						cmd.run(
-							config,
-							client,
-							inherent_benchmark_data()?,
-							Vec::new(),
-							&ext_builder,
-						)
+						let (client, _task_manager) = service::new_benchmark_client(&config)?;
+						let inherent_data = inherent_benchmark_data_ver(&client)?;
+						let client = Rc::new(RefCell::new(client));
+						let ext_builder = VerRemarkBuilder::new(client.clone());
+
+						cmd.run_ver(config, client, inherent_data, &ext_builder)
 					},
 					BenchmarkCmd::Extrinsic(cmd) => {
 						let PartialComponents { client, .. } = service::new_partial(&config)?;
@@ -1,14 +1,25 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
+//!
+//! Blocks are built and imported using VER (delayed, shuffled execution of txs): the proposer
+//! from `sc-basic-authorship-ver` enqueues txs collected from the pool and executes txs enqueued
+//! by previous blocks, every block carries a shuffling seed signed by its AURA author.
 
 use node_template_runtime::{self, opaque::Block, RuntimeApi};
 use sc_client_api::BlockBackend;
-use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
+use sc_consensus_aura::{
+	BuildVerifierParams, ShufflingSeedVerifier, SlotProportion, StartAuraParams,
+};
 use sc_consensus_grandpa::SharedVoterState;
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_keystore::LocalKeystore;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncParams};
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::{sr25519::AuthorityPair as AuraPair, AuraApi, Slot};
+use sp_keystore::SyncCryptoStorePtr;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_ver::RandomSeedInherentDataProvider;
 use std::{sync::Arc, time::Duration};
 
 // Our native executor instance.
@@ -112,28 +123,37 @@ pub fn new_partial(
 
 	let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
 
-	let import_queue =
-		sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _>(ImportQueueParams {
-			block_import: grandpa_block_import.clone(),
-			justification_import: Some(Box::new(grandpa_block_import.clone())),
-			client: client.clone(),
-			create_inherent_data_providers: move |_, ()| async move {
-				let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
-
-				let slot =
-					sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
-						*timestamp,
-						slot_duration,
-					);
+	let verifier = sc_consensus_aura::build_verifier::<AuraPair, _, _, _>(BuildVerifierParams {
+		client: client.clone(),
+		create_inherent_data_providers: move |_, ()| async move {
+			let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+
+			let slot =
+				sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+					*timestamp,
+					slot_duration,
+				);
+
+			Ok((slot, timestamp))
+		},
+		check_for_equivocation: Default::default(),
+		telemetry: telemetry.as_ref().map(|x| x.handle()),
+		compatibility_mode: Default::default(),
+	});
+	// reject blocks with invalid shuffling seeds before they are executed
+	let verifier = ShufflingSeedVerifier::<_, AuraPair, _, _>::new(
+		client.clone(),
+		verifier,
+		Default::default(),
+	);
 
-				Ok((slot, timestamp))
-			},
-			spawner: &task_manager.spawn_essential_handle(),
-			registry: config.prometheus_registry(),
-			check_for_equivocation: Default::default(),
-			telemetry: telemetry.as_ref().map(|x| x.handle()),
-			compatibility_mode: Default::default(),
-		})?;
+	let import_queue = sc_consensus::BasicQueue::new(
+		verifier,
+		Box::new(grandpa_block_import.clone()),
+		Some(Box::new(grandpa_block_import.clone())),
+		&task_manager.spawn_essential_handle(),
+		config.prometheus_registry(),
+	);
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -147,6 +167,49 @@ pub fn new_partial(
 	})
 }
 
+/// Builds a client that is not shared with any other component.
+///
+/// Used by the `benchmark overhead --ver` command, which imports blocks and therefore needs to
+/// own the client. The returned task manager has to be kept alive while the client is used.
+pub fn new_benchmark_client(
+	config: &Configuration,
+) -> Result<(FullClient, TaskManager), ServiceError> {
+	let executor = NativeElseWasmExecutor::<ExecutorDispatch>::new(
+		config.wasm_method,
+		config.default_heap_pages,
+		config.max_runtime_instances,
+		config.runtime_cache_size,
+	);
+
+	let (client, _backend, _keystore_container, task_manager) =
+		sc_service::new_full_parts::<Block, RuntimeApi, _>(config, None, executor)?;
+	Ok((client, task_manager))
+}
+
+/// Creates the shuffling seed inherent of the block built on top of `parent` in `slot`.
+///
+/// The seed is signed with the AURA key of the slot author. If the key is not available in the
+/// keystore, the node is not going to author the block and the default seed is provided.
+fn shuffling_seed_inherent(
+	client: &FullClient,
+	keystore: &SyncCryptoStorePtr,
+	parent: <Block as BlockT>::Hash,
+	slot: Slot,
+) -> Result<RandomSeedInherentDataProvider, Box<dyn std::error::Error + Send + Sync>> {
+	let parent_header = client
+		.header(parent)?
+		.ok_or_else(|| format!("Parent header {:?} not found", parent))?;
+	let authorities = client.runtime_api().authorities(parent)?;
+	if authorities.is_empty() {
+		return Err("No AURA authorities found".into())
+	}
+
+	let author = authorities[(*slot % authorities.len() as u64) as usize].clone();
+	let seed = sp_ver::calculate_next_seed(&**keystore, &author.into(), parent_header.seed())
+		.unwrap_or_default();
+	Ok(RandomSeedInherentDataProvider(seed))
+}
+
 fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 	// FIXME: here would the concrete keystore be built,
 	//        must return a concrete type (NOT `LocalKeystore`) that
@@ -243,10 +306,11 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		sync_service: sync_service.clone(),
 		config,
 		telemetry: telemetry.as_mut(),
+		storage_queue_status: Some(ver_api::storage_queue_status_provider(client.clone())),
 	})?;
 
 	if role.is_authority() {
-		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+		let proposer_factory = sc_basic_authorship_ver::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
 			transaction_pool,
@@ -255,6 +319,8 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 		);
 
 		let slot_duration = sc_consensus_aura::slot_duration(&*client)?;
+		let seed_client = client.clone();
+		let seed_keystore = keystore_container.sync_keystore();
 
 		let aura = sc_consensus_aura::start_aura::<AuraPair, _, _, _, _, _, _, _, _, _, _>(
 			StartAuraParams {
@@ -263,16 +329,22 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 				select_chain,
 				block_import,
 				proposer_factory,
-				create_inherent_data_providers: move |_, ()| async move {
-					let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
-
-					let slot =
-						sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
-							*timestamp,
-							slot_duration,
-						);
-
-					Ok((slot, timestamp))
+				create_inherent_data_providers: move |parent, ()| {
+					let client = seed_client.clone();
+					let keystore = seed_keystore.clone();
+					async move {
+						let timestamp = sp_timestamp::InherentDataProvider::from_system_time();
+
+						let slot =
+							sp_consensus_aura::inherents::InherentDataProvider::from_timestamp_and_slot_duration(
+								*timestamp,
+								slot_duration,
+							);
+
+						let seed = shuffling_seed_inherent(&client, &keystore, parent, *slot)?;
+
+						Ok((slot, timestamp, seed))
+					}
 				},
 				force_authoring,
 				backoff_authoring_blocks,
@@ -46,6 +46,9 @@ impl frame_system::Config for Test {
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = ();
 }
 
 impl pallet_template::Config for Test {
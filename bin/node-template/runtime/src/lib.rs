@@ -6,14 +6,23 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::Encode;
+use frame_support::{
+	dispatch::{DispatchClass, GetDispatchInfo},
+	traits::FindAuthor,
+};
 use pallet_grandpa::AuthorityId as GrandpaId;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
+use sp_core::{
+	crypto::{ByteArray, KeyTypeId},
+	OpaqueMetadata,
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdLookup, BlakeTwo256, Block as BlockT, Header as HeaderT, IdentifyAccount,
+		NumberFor, One, StaticLookup, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
@@ -76,8 +85,9 @@ pub mod opaque {
 
 	pub use sp_runtime::OpaqueExtrinsic as UncheckedExtrinsic;
 
-	/// Opaque block header type.
-	pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
+	/// Opaque block header type, VER header carrying the shuffling seed and the number of
+	/// executed txs.
+	pub type Header = mangata_types::Header;
 	/// Opaque block type.
 	pub type Block = generic::Block<Header, UncheckedExtrinsic>;
 	/// Opaque block identifier type.
@@ -146,6 +156,13 @@ parameter_types! {
 		);
 	pub BlockLength: frame_system::limits::BlockLength = frame_system::limits::BlockLength
 		::max_with_normal_ratio(5 * 1024 * 1024, NORMAL_DISPATCH_RATIO);
+	/// Txs enqueued by a single block need to fit into the normal part of the following block.
+	pub StorageQueueLimits: frame_system::limits::StorageQueueLimits =
+		frame_system::limits::StorageQueueLimits {
+			depth: 2,
+			max_txs: 4096,
+			max_len: NORMAL_DISPATCH_RATIO * 5 * 1024 * 1024,
+		};
 	pub const SS58Prefix: u8 = 42;
 }
 
@@ -173,7 +190,7 @@ impl frame_system::Config for Runtime {
 	/// The hashing algorithm used.
 	type Hashing = BlakeTwo256;
 	/// The header type.
-	type Header = generic::Header<BlockNumber, BlakeTwo256>;
+	type Header = Header;
 	/// The ubiquitous event type.
 	type RuntimeEvent = RuntimeEvent;
 	/// The ubiquitous origin type.
@@ -201,6 +218,11 @@ impl frame_system::Config for Runtime {
 	/// The set code logic, just the default since we're not a parachain.
 	type OnSetCode = ();
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type ShuffleStrategy = ();
+	/// Limits of the storage queue, can be overridden by root.
+	type StorageQueueLimits = StorageQueueLimits;
+	/// Txs that cannot be executed (e.g. because their signer cannot pay fees) are not enqueued.
+	type EnqueuedTxsValidator = Executive;
 }
 
 impl pallet_aura::Config for Runtime {
@@ -291,7 +313,7 @@ construct_runtime!(
 /// The address format for describing accounts.
 pub type Address = sp_runtime::MultiAddress<AccountId, ()>;
 /// Block header type as expected by this runtime.
-pub type Header = generic::Header<BlockNumber, BlakeTwo256>;
+pub type Header = mangata_types::Header;
 /// Block type as expected by this runtime.
 pub type Block = generic::Block<Header, UncheckedExtrinsic>;
 /// The SignedExtension to the basic transaction logic.
@@ -342,7 +364,12 @@ impl_runtime_apis! {
 		}
 
 		fn execute_block(block: Block) {
-			Executive::execute_block(block);
+			// the shuffling seed of a VER block is verified against the AURA author of the block
+			let author = pallet_aura::AuraAuthorId::<Runtime>::find_author(
+				block.header().digest().logs().iter().filter_map(|d| d.as_pre_runtime()),
+			)
+			.expect("block needs to be authored by one of the AURA authorities");
+			Executive::execute_block_ver_impl(block, author.to_raw_vec());
 		}
 
 		fn initialize_block(header: &<Block as BlockT>::Header) {
@@ -350,6 +377,107 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(4)]
+	impl ver_api::VerApi<Block> for Runtime {
+		fn get_signer(tx: <Block as BlockT>::Extrinsic) -> Option<(AccountId, Index)> {
+			tx.signature.and_then(|(address, _, extra)| {
+				let nonce: frame_system::CheckNonce<Runtime> = extra.5;
+				<Runtime as frame_system::Config>::Lookup::lookup(address)
+					.ok()
+					.map(|who| (who, nonce.0))
+			})
+		}
+
+		fn is_storage_migration_scheduled() -> bool {
+			// txs enqueued along with the runtime upgrade would be executed by the new runtime
+			System::read_events_no_consensus().any(|record| {
+				matches!(record.event, RuntimeEvent::System(frame_system::Event::CodeUpdated))
+			})
+		}
+
+		fn store_seed(seed: sp_core::H256) {
+			Executive::store_seed(&seed);
+		}
+
+		fn pop_txs(count: u64) -> Vec<Vec<u8>> {
+			Executive::pop_txs(count as usize)
+		}
+
+		fn get_previous_block_txs() -> Vec<Vec<u8>> {
+			System::get_previous_blocks_txs()
+		}
+
+		fn can_enqueue_txs() -> bool {
+			System::can_enqueue_txs()
+		}
+
+		fn create_enqueue_txs_inherent(
+			txs: Vec<<Block as BlockT>::Extrinsic>,
+		) -> <Block as BlockT>::Extrinsic {
+			let txs = txs
+				.into_iter()
+				.map(|tx| {
+					let who = tx.signature.clone().and_then(|(address, _, _)| {
+						<Runtime as frame_system::Config>::Lookup::lookup(address).ok()
+					});
+					(who, tx.encode())
+				})
+				.collect();
+			UncheckedExtrinsic::new_unsigned(RuntimeCall::System(frame_system::Call::enqueue_txs {
+				txs,
+			}))
+		}
+
+		fn start_prevalidation() {
+			System::set_prevalidation()
+		}
+
+		fn get_storage_queue() -> Vec<ver_api::StorageQueueEntry> {
+			frame_system::StorageQueue::<Runtime>::get()
+				.into_iter()
+				.map(|(block_number, shuffle_index, txs)| ver_api::StorageQueueEntry {
+					block_number,
+					shuffle_index,
+					txs,
+				})
+				.collect()
+		}
+
+		fn get_block_seed() -> sp_core::H256 {
+			System::block_seed()
+		}
+
+		fn get_enqueue_limits() -> ver_api::EnqueueLimits {
+			// all txs enqueued by a block are executed in a single block
+			let weights = BlockWeights::get();
+			let normal = weights.get(DispatchClass::Normal);
+			let max_len = *BlockLength::get().max.get(DispatchClass::Normal);
+			ver_api::EnqueueLimits {
+				max_weight: normal.max_total.unwrap_or(weights.max_block),
+				max_len: max_len.min(System::storage_queue_limits().max_len),
+			}
+		}
+
+		fn get_dispatch_weight(tx: <Block as BlockT>::Extrinsic) -> Weight {
+			let info = tx.get_dispatch_info();
+			info.weight.saturating_add(BlockWeights::get().get(info.class).base_extrinsic)
+		}
+
+		fn get_enqueued_txs_proof_data(
+		) -> Option<ver_api::EnqueuedTxsProofData<<Block as BlockT>::Hash>> {
+			System::enqueued_txs_hashes().map(|hashes| ver_api::EnqueuedTxsProofData {
+				block_number: System::block_number(),
+				seed: System::block_seed(),
+				hashes,
+				queue_key: System::storage_queue_key(),
+			})
+		}
+
+		fn get_max_enqueued_txs() -> u32 {
+			System::storage_queue_limits().max_txs
+		}
+	}
+
 	impl sp_api::Metadata<Block> for Runtime {
 		fn metadata() -> OpaqueMetadata {
 			OpaqueMetadata::new(Runtime::metadata().into())
@@ -451,6 +579,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl ver_api::VerNonceApi<Block, AccountId> for Runtime {
+		fn enqueued_txs_count(account: AccountId) -> u64 {
+			System::enqueued_txs_count(&account) as u64
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,
@@ -537,6 +671,7 @@ impl_runtime_apis! {
 	}
 
 	#[cfg(feature = "try-runtime")]
+	#[api_version(2)]
 	impl frame_try_runtime::TryRuntime<Block> for Runtime {
 		fn on_runtime_upgrade(checks: frame_try_runtime::UpgradeCheckSelect) -> (Weight, Weight) {
 			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
@@ -556,6 +691,18 @@ impl_runtime_apis! {
 			// have a backtrace here.
 			Executive::try_execute_block(block, state_root_check, signature_check, select).expect("execute-block failed")
 		}
+
+		fn execute_block_ver(
+			block: Block,
+			public: Vec<u8>,
+			state_root_check: bool,
+			signature_check: bool,
+			select: frame_try_runtime::TryStateSelect
+		) -> Weight {
+			// NOTE: intentional unwrap: we don't want to propagate the error backwards, and want to
+			// have a backtrace here.
+			Executive::try_execute_block_ver(block, public, state_root_check, signature_check, select).expect("execute-block-ver failed")
+		}
 	}
 }
 
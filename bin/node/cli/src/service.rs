@@ -395,6 +395,7 @@ pub fn new_full_base(
 		tx_handler_controller,
 		sync_service: sync_service.clone(),
 		telemetry: telemetry.as_mut(),
+		storage_queue_status: None,
 	})?;
 
 	if let Some(hwbench) = hwbench {
@@ -238,6 +238,9 @@ impl frame_system::Config for Runtime {
 	type SS58Prefix = ConstU16<42>;
 	type OnSetCode = ();
 	type MaxConsumers = ConstU32<16>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = ();
 }
 
 impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
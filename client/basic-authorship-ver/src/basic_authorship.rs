@@ -30,14 +30,19 @@ use futures::{
 	select,
 };
 use log::{debug, error, info, trace, warn};
-use sc_block_builder::{validate_transaction, BlockBuilderApi, BlockBuilderProvider};
+use sc_block_builder::{
+	validate_transaction, BlockBuilderApi, BlockBuilderProvider, BlockLimitsPolicy, BuiltBlock,
+	EnqueuePolicy, EnqueuedTxs,
+};
 use sc_client_api::backend;
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_INFO};
 use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
 use sp_api::{ApiExt, ProvideRuntimeApi};
-use sp_blockchain::{ApplyExtrinsicFailed::Validity, Error::ApplyExtrinsicFailed, HeaderBackend};
+use sp_blockchain::{
+	ApplyExtrinsicFailed::Validity, Error::ApplyExtrinsicFailed, HeaderBackend, VerFailure,
+};
 use sp_consensus::{DisableProofRecording, EnableProofRecording, ProofRecording, Proposal};
-use sp_core::traits::SpawnNamed;
+use sp_core::{traits::SpawnNamed, ShufflingSeed};
 use sp_inherents::InherentData;
 use sp_runtime::{
 	generic::BlockId,
@@ -45,10 +50,10 @@ use sp_runtime::{
 	Digest, Percent, SaturatedConversion,
 };
 use std::{marker::PhantomData, pin::Pin, sync::Arc, time};
-use ver_api::VerApi;
+use ver_api::{StorageQueueEntry, VerApi};
 
 use prometheus_endpoint::Registry as PrometheusRegistry;
-use sc_proposer_metrics::{EndProposingReason, MetricsLink as PrometheusMetrics};
+use sc_proposer_metrics::{EndProposingReason, MetricsLink as PrometheusMetrics, VerPhase};
 use sp_inherents::InherentDataProvider;
 
 /// Default block size limit in bytes used by [`Proposer`].
@@ -62,6 +67,61 @@ pub const DEFAULT_BLOCK_SIZE_LIMIT: usize = 4 * 1024 * 1024 + 512;
 
 const DEFAULT_SOFT_DEADLINE_PERCENT: Percent = Percent::from_percent(50);
 
+/// Describes how the slot and the block size are divided between execution of txs enqueued by
+/// previous blocks (first phase) and collection of txs from the pool (second phase).
+///
+/// Can be overwritten by [`ProposerFactory::set_phase_split`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PhaseSplit {
+	/// The same split is used for every block.
+	Fixed {
+		/// share of the time left after applying inherents dedicated to execution phase
+		execution_time: Percent,
+		/// share of the block size dedicated to execution phase
+		execution_size: Percent,
+	},
+	/// Execution phase gets the share of the block size that is occupied by txs ready for
+	/// execution, clamped to `min..=max`. The same share of time is dedicated to execution phase,
+	/// the rest of the slot and the block is used for collection phase.
+	Adaptive {
+		/// minimal share of the time and the block size dedicated to execution phase
+		min: Percent,
+		/// maximal share of the time and the block size dedicated to execution phase
+		max: Percent,
+	},
+}
+
+impl Default for PhaseSplit {
+	fn default() -> Self {
+		PhaseSplit::Fixed {
+			execution_time: Percent::from_percent(55),
+			execution_size: Percent::from_percent(50),
+		}
+	}
+}
+
+impl PhaseSplit {
+	fn is_adaptive(&self) -> bool {
+		matches!(self, PhaseSplit::Adaptive { .. })
+	}
+
+	/// Returns shares of the time and the block size dedicated to execution phase, `queue_size`
+	/// is the encoded size of txs ready for execution.
+	fn execution_shares(&self, queue_size: usize, block_size_limit: usize) -> (Percent, Percent) {
+		match *self {
+			PhaseSplit::Fixed { execution_time, execution_size } =>
+				(execution_time, execution_size),
+			PhaseSplit::Adaptive { min, max } => {
+				let share =
+					Percent::from_rational(queue_size as u64, block_size_limit.max(1) as u64)
+						.max(min)
+						.min(max.max(min));
+				(share, share)
+			},
+		}
+	}
+}
+
 /// [`Proposer`] factory.
 pub struct ProposerFactory<A, B, C, PR> {
 	spawn_handle: Box<dyn SpawnNamed>,
@@ -87,6 +147,10 @@ pub struct ProposerFactory<A, B, C, PR> {
 	telemetry: Option<TelemetryHandle>,
 	/// When estimating the block size, should the proof be included?
 	include_proof_in_block_size_estimation: bool,
+	/// Split of the slot and the block size between execution and collection phases.
+	phase_split: PhaseSplit,
+	/// Decides which of the txs collected from the pool are stored in the storage queue.
+	enqueue_policy: Arc<dyn EnqueuePolicy>,
 	/// phantom member to pin the `Backend`/`ProofRecording` type.
 	_phantom: PhantomData<(B, PR)>,
 }
@@ -112,6 +176,8 @@ impl<A, B, C> ProposerFactory<A, B, C, DisableProofRecording> {
 			telemetry,
 			client,
 			include_proof_in_block_size_estimation: false,
+			phase_split: PhaseSplit::default(),
+			enqueue_policy: Arc::new(BlockLimitsPolicy),
 			_phantom: PhantomData,
 		}
 	}
@@ -140,6 +206,8 @@ impl<A, B, C> ProposerFactory<A, B, C, EnableProofRecording> {
 			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
 			telemetry,
 			include_proof_in_block_size_estimation: true,
+			phase_split: PhaseSplit::default(),
+			enqueue_policy: Arc::new(BlockLimitsPolicy),
 			_phantom: PhantomData,
 		}
 	}
@@ -177,6 +245,27 @@ impl<A, B, C, PR> ProposerFactory<A, B, C, PR> {
 	pub fn set_soft_deadline(&mut self, percent: Percent) {
 		self.soft_deadline_percent = percent;
 	}
+
+	/// Set the split of the slot and the block size between execution of txs enqueued by
+	/// previous blocks and collection of txs from the pool.
+	///
+	/// By default 55% of the time left after applying inherents and half of the block size are
+	/// dedicated to execution. Dedicating too little to execution may result in the storage
+	/// queue growing faster than it is executed, dedicating too much results in blocks with
+	/// few newly collected txs. [`PhaseSplit::Adaptive`] sizes both phases based on the current
+	/// queue depth.
+	pub fn set_phase_split(&mut self, split: PhaseSplit) {
+		self.phase_split = split;
+	}
+
+	/// Set the policy deciding which of the txs collected from the transaction pool are stored
+	/// in the storage queue by the enqueue inherent.
+	///
+	/// [`BlockLimitsPolicy`] is used by default, it enqueues txs as long as they fit into the
+	/// limits provided by the runtime.
+	pub fn set_enqueue_policy(&mut self, policy: Arc<dyn EnqueuePolicy>) {
+		self.enqueue_policy = policy;
+	}
 }
 
 impl<B, Block, C, A, PR> ProposerFactory<A, B, C, PR>
@@ -207,6 +296,7 @@ where
 		let proposer = Proposer::<_, _, _, _, PR> {
 			spawn_handle: self.spawn_handle.clone(),
 			client: self.client.clone(),
+			parent_hash,
 			parent_id: id,
 			parent_number: *parent_header.number(),
 			transaction_pool: self.transaction_pool.clone(),
@@ -217,6 +307,8 @@ where
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			phase_split: self.phase_split,
+			enqueue_policy: self.enqueue_policy.clone(),
 		};
 
 		proposer
@@ -252,6 +344,7 @@ where
 pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR> {
 	spawn_handle: Box<dyn SpawnNamed>,
 	client: Arc<C>,
+	parent_hash: Block::Hash,
 	parent_id: BlockId<Block>,
 	parent_number: <<Block as BlockT>::Header as HeaderT>::Number,
 	transaction_pool: Arc<A>,
@@ -261,6 +354,8 @@ pub struct Proposer<B, Block: BlockT, C, A: TransactionPool, PR> {
 	include_proof_in_block_size_estimation: bool,
 	soft_deadline_percent: Percent,
 	telemetry: Option<TelemetryHandle>,
+	phase_split: PhaseSplit,
+	enqueue_policy: Arc<dyn EnqueuePolicy>,
 	_phantom: PhantomData<(B, PR)>,
 }
 
@@ -421,7 +516,9 @@ where
 		let inherent_data = inherent_data.clone();
 
 		let mut block_builder =
-			self.client.new_block_at(&self.parent_id, inherent_digests, PR::ENABLED)?;
+			self.client
+				.new_block_at(&self.parent_id, inherent_digests.clone(), PR::ENABLED)?;
+		block_builder.set_enqueue_policy(self.enqueue_policy.clone());
 
 		let create_inherents_start = time::Instant::now();
 		let (seed, inherents) = block_builder.create_inherents(inherent_data.clone())?;
@@ -436,10 +533,11 @@ where
 		});
 
 		debug!(target:"block_builder", "found {} inherents", inherents.len());
+		let mut applied_inherents = Vec::new();
 		for inherent in inherents {
 			debug!(target:"block_builder", "processing inherent");
 			// TODO now it actually commits changes
-			match block_builder.push(inherent) {
+			match block_builder.push(inherent.clone()) {
 				Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() => {
 					warn!("⚠️  Dropping non-mandatory inherent from overweight block.")
 				},
@@ -453,21 +551,46 @@ where
 					warn!("❗️ Inherent extrinsic returned unexpected error: {}. Dropping.", e);
 				},
 				Ok(_) => {
+					applied_inherents.push(inherent);
 					trace!(target:"block_builder", "inherent pushed into the block");
 				},
 			}
 		}
 
+		self.report_storage_queue_depth();
+
+		let block_size_limit = block_size_limit.unwrap_or(self.default_block_size_limit);
+		let queue_size = if self.phase_split.is_adaptive() {
+			match self.storage_queue_size() {
+				Ok(queue_size) => queue_size,
+				Err(e) => {
+					self.report_ver_failure(VerFailure::PreviousBlockTxs);
+					warn!("❗️ Cannot fetch storage queue size: {}. Assuming empty queue.", e);
+					0
+				},
+			}
+		} else {
+			0
+		};
+		let (execution_time, execution_size) =
+			self.phase_split.execution_shares(queue_size, block_size_limit);
+		debug!(target: "block_builder",
+			"queue size: {}, execution phase gets {:?} of time and {:?} of block size",
+			queue_size,
+			execution_time,
+			execution_size,
+		);
+
 		// proceed with transactions
 		// We calculate soft deadline used only in case we start skipping transactions.
 		let now = (self.now)();
 		let left = deadline.saturating_duration_since(now);
 		let left_micros: u64 = left.as_micros().saturated_into();
+		let execution_micros = execution_time.mul_floor(left_micros);
 		let first_slot_limit =
-			futures_timer::Delay::new(time::Duration::from_micros(left_micros * 55 / 100));
+			futures_timer::Delay::new(time::Duration::from_micros(execution_micros));
 
-		// let queue_processing_deadline = now + time::Duration::from_micros(left_micros / 2);
-		let queue_processing_deadline = now + time::Duration::from_micros(left_micros * 55 / 100);
+		let queue_processing_deadline = now + time::Duration::from_micros(execution_micros);
 
 		let block_timer = time::Instant::now();
 		let mut skipped = 0;
@@ -480,25 +603,39 @@ where
 		let get_current_time = &self.now;
 		let is_expired = || get_current_time() > queue_processing_deadline;
 
-		let block_size_limit = block_size_limit.unwrap_or(self.default_block_size_limit);
-		block_builder.apply_previous_block_extrinsics(
+		let execution_size_limit = execution_size.mul_floor(block_size_limit);
+		let execution_start = time::Instant::now();
+		let previous_block_txs_applied = block_builder.apply_previous_block_extrinsics(
 			seed.clone(),
 			&mut block_size,
-			block_size_limit / 2, // txs from queue should not occupy more than half of the block
+			execution_size_limit, // txs from queue should not exceed execution phase share
 			is_expired,
 		);
+		let execution_end = time::Instant::now();
+
+		self.metrics.report(|metrics| {
+			metrics.report_ver_phase_time(
+				VerPhase::Execution,
+				execution_end.saturating_duration_since(execution_start),
+			);
+			if let Ok(txs) = &previous_block_txs_applied {
+				metrics.report_ver_queued_txs(txs.executed, txs.left);
+			}
+		});
 
 		// there might be some txs comming in that time - so its better to sleep than
 		// shortening remaining time
-		debug!(target: "block_builder", "sleeping by the end of the slot");
-		first_slot_limit.await;
+		if previous_block_txs_applied.is_ok() {
+			debug!(target: "block_builder", "sleeping by the end of the slot");
+			first_slot_limit.await;
+		}
 
-		// artificially simulate that block is half filled
+		// artificially simulate that execution phase share of the block is filled
 		// also include header & proof cost for the second part to make sure
 		// that all txs included in that phase will have enought room to be executed in following
 		// block
 		debug!(target: "block_builder", "esitmated block size{}", block_builder.estimate_block_size_without_extrinsics(self.include_proof_in_block_size_estimation));
-		block_size = block_size_limit / 2 +
+		block_size = execution_size_limit +
 			block_builder.estimate_block_size_without_extrinsics(
 				self.include_proof_in_block_size_estimation,
 			);
@@ -531,8 +668,9 @@ where
 		// after previous block is applied it is possible to prevalidate incomming transaction
 		// but eventually changess needs to be rolled back, as those can be executed
 		// only in the following(future) block
-		let (block, storage_changes, proof) = block_builder
-			.build_with_seed(seed, |at, api| {
+		let collection_start = time::Instant::now();
+		let built_block = previous_block_txs_applied.and_then(|_| {
+			block_builder.build_with_seed(seed.clone(), |at, api| {
 				let mut valid_txs = Vec::new();
 
 				end_reason = loop {
@@ -580,10 +718,16 @@ where
 					}
 
 					trace!(target:"block_builder", "[{:?}] Pushing to the block.", pending_tx_hash);
-					let who = api
-						.get_signer(*at, pending_tx_data.clone())
-						.unwrap()
-						.map(|signer_info| signer_info.0.clone());
+					let who = match api.get_signer(*at, pending_tx_data.clone()) {
+						Ok(signer_info) => signer_info.map(|signer_info| signer_info.0),
+						Err(e) => {
+							pending_iterator.report_invalid(&pending_tx);
+							debug!(target: "block_builder",
+								"[{:?}] Cannot fetch transaction signer: {}", pending_tx_hash, e
+							);
+							continue;
+						},
+					};
 					match validate_transaction::<Block, C>(*at, &api, pending_tx_data.clone()) {
 						Ok(()) => {
 							transaction_pushed = true;
@@ -627,8 +771,21 @@ where
 					}
 				};
 				valid_txs
-			})?
-			.into_inner();
+			})
+		});
+
+		let (block, storage_changes, proof) = match built_block {
+			Ok(built_block) => {
+				let collection_end = time::Instant::now();
+				self.report_enqueued_txs(
+					&built_block.enqueued_txs,
+					collection_end.saturating_duration_since(collection_start),
+				);
+				built_block
+			},
+			Err(e) => self.build_empty_block(e, inherent_digests, seed, applied_inherents)?,
+		}
+		.into_inner();
 
 		if matches!(end_reason, EndProposingReason::HitBlockSizeLimit) && !transaction_pushed {
 			warn!(
@@ -685,6 +842,86 @@ where
 		});
 		Ok(Proposal { block, proof, storage_changes })
 	}
+
+	/// Builds a block without any txs executed from or stored in the storage queue when building
+	/// a regular block failed because of VER related runtime error. Other errors are propagated.
+	fn build_empty_block(
+		&self,
+		error: sp_blockchain::Error,
+		inherent_digests: Digest,
+		seed: ShufflingSeed,
+		inherents: Vec<Block::Extrinsic>,
+	) -> Result<BuiltBlock<Block, backend::StateBackendFor<B, Block>>, sp_blockchain::Error> {
+		let failure = error.ver_failure().ok_or(error)?;
+		self.report_ver_failure(failure);
+		warn!("❗️ Building block failed at {}. Falling back to empty block.", failure);
+
+		let mut block_builder =
+			self.client.new_block_at(&self.parent_id, inherent_digests, PR::ENABLED)?;
+		for inherent in inherents {
+			block_builder.push(inherent)?;
+		}
+		block_builder.build_empty_with_seed(seed).map_err(|e| {
+			if let Some(failure) = e.ver_failure() {
+				self.report_ver_failure(failure);
+			}
+			error!("❌️ Building empty block failed: {}", e);
+			e
+		})
+	}
+
+	fn report_ver_failure(&self, failure: VerFailure) {
+		self.metrics.report(|metrics| metrics.report_ver_failure(failure.as_str()));
+	}
+
+	fn report_enqueued_txs(&self, txs: &EnqueuedTxs, collection_time: time::Duration) {
+		self.metrics.report(|metrics| {
+			metrics.report_ver_phase_time(VerPhase::Collection, collection_time);
+			metrics
+				.report_ver_enqueued_txs(txs.enqueued, txs.collected.saturating_sub(txs.enqueued));
+			if txs.queue_full {
+				metrics.ver_storage_queue_full.inc();
+			}
+		});
+	}
+
+	/// Encoded size of txs waiting in the storage queue at the parent block, including txs
+	/// enqueued by the parent block that are going to be shuffled and executed by this block.
+	fn storage_queue_size(&self) -> Result<usize, sp_api::ApiError> {
+		let api = self.client.runtime_api();
+		match api.api_version::<dyn VerApi<Block>>(self.parent_hash) {
+			Ok(Some(version)) if version >= 2 =>
+				api.get_storage_queue(self.parent_hash).map(|queue| pending_txs_size(&queue)),
+			// older runtimes only expose txs that have been shuffled already
+			_ => api
+				.get_previous_block_txs(self.parent_hash)
+				.map(|txs| txs.iter().map(|tx| tx.len()).sum()),
+		}
+	}
+
+	/// Reports depth of the storage queue at the parent block, requires `VerApi` version 2.
+	fn report_storage_queue_depth(&self) {
+		self.metrics.report(|metrics| {
+			let api = self.client.runtime_api();
+			let queue = match api.api_version::<dyn VerApi<Block>>(self.parent_hash) {
+				Ok(Some(version)) if version >= 2 => api.get_storage_queue(self.parent_hash),
+				_ => return,
+			};
+			match queue {
+				Ok(queue) => metrics.ver_storage_queue_depth.set(queue.len() as u64),
+				Err(e) => debug!(target: "block_builder", "Cannot fetch storage queue: {}", e),
+			}
+		});
+	}
+}
+
+/// Encoded size of txs from `queue` that have not been executed yet.
+fn pending_txs_size(queue: &[StorageQueueEntry]) -> usize {
+	queue
+		.iter()
+		.flat_map(|entry| entry.txs.iter().skip(entry.shuffle_index.unwrap_or_default() as usize))
+		.map(|(_, tx)| tx.len())
+		.sum()
 }
 
 #[cfg(test)]
@@ -696,7 +933,7 @@ mod tests {
 	use sc_transaction_pool::BasicPool;
 	use sc_transaction_pool_api::{ChainEvent, MaintainedTransactionPool, TransactionSource};
 	use sp_blockchain::HeaderBackend;
-	use sp_consensus::{Environment, Proposer};
+	use sp_consensus::{BlockOrigin, Environment, Proposer};
 	use sp_core::Pair;
 	use sp_runtime::traits::NumberFor;
 	use substrate_test_runtime_client::{
@@ -912,6 +1149,84 @@ mod tests {
 		// );
 	}
 
+	#[tokio::test]
+	async fn should_report_ver_metrics() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+
+		block_on(txpool.submit_at(&BlockId::number(0), SOURCE, vec![extrinsic(0)])).unwrap();
+
+		block_on(
+			txpool.maintain(chain_event(
+				client
+					.expect_header(client.info().genesis_hash)
+					.expect("there should be header"),
+			)),
+		);
+
+		let registry = PrometheusRegistry::new();
+		let mut proposer_factory = ProposerFactory::new(
+			spawner.clone(),
+			client.clone(),
+			txpool.clone(),
+			Some(&registry),
+			None,
+		);
+
+		let proposer = proposer_factory.init_with_now(
+			&client.expect_header(client.info().genesis_hash).unwrap(),
+			Box::new(move || time::Instant::now()),
+		);
+
+		let mut inherent_data = InherentData::new();
+		sp_ver::RandomSeedInherentDataProvider(Default::default())
+			.provide_inherent_data(&mut inherent_data)
+			.await
+			.unwrap();
+
+		let deadline = time::Duration::from_secs(9);
+		block_on(proposer.propose(inherent_data, Default::default(), deadline, None)).unwrap();
+
+		let metrics = registry.gather();
+		let metric = |name: &str, label: Option<&str>| {
+			metrics
+				.iter()
+				.find(|family| family.get_name() == name)
+				.and_then(|family| {
+					family.get_metric().iter().find(|metric| {
+						label.map_or(true, |label| {
+							metric.get_label().iter().any(|pair| pair.get_value() == label)
+						})
+					})
+				})
+				.cloned()
+				.unwrap_or_else(|| panic!("metric {} {:?} should be reported", name, label))
+		};
+		let gauge =
+			|name: &str, label: Option<&str>| metric(name, label).get_gauge().get_value() as u64;
+
+		assert_eq!(gauge("substrate_proposer_ver_queued_txs", Some("executed")), 0);
+		assert_eq!(gauge("substrate_proposer_ver_queued_txs", Some("left")), 0);
+		assert_eq!(gauge("substrate_proposer_ver_enqueued_txs", Some("enqueued")), 1);
+		assert_eq!(gauge("substrate_proposer_ver_enqueued_txs", Some("dropped")), 0);
+		assert_eq!(gauge("substrate_proposer_ver_storage_queue_depth", None), 0);
+		for phase in ["execution", "collection"] {
+			assert_eq!(
+				metric("substrate_proposer_ver_phase_time", Some(phase))
+					.get_histogram()
+					.get_sample_count(),
+				1
+			);
+		}
+	}
+
 	#[tokio::test]
 	async fn should_cease_building_block_when_block_limit_is_reached() {
 		let _ = env_logger::try_init();
@@ -935,7 +1250,7 @@ mod tests {
 
 		let init_size = genesis_header.encoded_size()
 			+ Vec::<Extrinsic>::new().encoded_size() // list of extrinsics
-			+ Extrinsic::EnqueueTxs(extrinsics_num).encoded_size();
+			+ Extrinsic::EnqueueTxs(Default::default()).encoded_size();
 
 		let block_limit = init_size +
 			extrinsics
@@ -977,7 +1292,7 @@ mod tests {
 		assert_eq!(block.extrinsics().len(), 1);
 		assert!(matches!(
 				block.extrinsics().get(0).expect("enqueue tx extrinsic"),
-				Extrinsic::EnqueueTxs(count) if *count == (extrinsics_num - 1)as u64));
+				Extrinsic::EnqueueTxs(txs) if txs.len() == (extrinsics_num - 1) as usize));
 
 		let proposer = block_on(proposer_factory.init(&genesis_header)).unwrap();
 
@@ -990,7 +1305,7 @@ mod tests {
 		assert_eq!(block.extrinsics().len(), 1);
 		assert!(matches!(
 				block.extrinsics().get(0).expect("enqueue tx extrinsic"),
-				Extrinsic::EnqueueTxs(count) if *count == extrinsics_num as u64));
+				Extrinsic::EnqueueTxs(txs) if txs.len() == extrinsics_num as usize));
 
 		let mut proposer_factory = ProposerFactory::with_proof_recording(
 			spawner.clone(),
@@ -1020,7 +1335,7 @@ mod tests {
 		// assert!(
 		// 	matches!(
 		// 		block.extrinsics().get(0).expect("enqueue tx extrinsic"),
-		// 		Extrinsic::EnqueueTxs(count) if *count == (extrinsics_num - 2) as u64)
+		// 		Extrinsic::EnqueueTxs(txs) if txs.len() == (extrinsics_num - 2) as usize)
 		// 	);
 	}
 
@@ -1097,7 +1412,7 @@ mod tests {
 		assert_eq!(block.extrinsics().len(), 1);
 		assert!(matches!(
 				block.extrinsics().get(0).expect("enqueue tx extrinsic"),
-				Extrinsic::EnqueueTxs(count) if *count == (MAX_SKIPPED_TRANSACTIONS + 1) as u64));
+				Extrinsic::EnqueueTxs(txs) if txs.len() == MAX_SKIPPED_TRANSACTIONS + 1));
 	}
 
 	#[tokio::test]
@@ -1181,4 +1496,107 @@ mod tests {
 			"Not enough calls to current time, which indicates the test might have ended because of deadline, not soft deadline"
 		);
 	}
+
+	#[tokio::test]
+	async fn adaptive_phase_split_counts_txs_enqueued_by_parent_block() {
+		let client = Arc::new(substrate_test_runtime_client::new());
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let txpool = BasicPool::new_full(
+			Default::default(),
+			true.into(),
+			None,
+			spawner.clone(),
+			client.clone(),
+		);
+		let genesis_header = client
+			.expect_header(client.info().genesis_hash)
+			.expect("there should be header");
+
+		block_on(txpool.submit_at(&BlockId::number(0), SOURCE, vec![extrinsic(0), extrinsic(1)]))
+			.unwrap();
+		block_on(txpool.maintain(chain_event(genesis_header.clone())));
+
+		let min = Percent::from_percent(10);
+		let split = PhaseSplit::Adaptive { min, max: Percent::from_percent(70) };
+		let mut proposer_factory =
+			ProposerFactory::new(spawner.clone(), client.clone(), txpool.clone(), None, None);
+		proposer_factory.set_phase_split(split);
+
+		// nothing is enqueued at genesis
+		let proposer = block_on(proposer_factory.init(&genesis_header)).unwrap();
+		assert_eq!(proposer.storage_queue_size().unwrap(), 0);
+
+		let mut inherent_data = InherentData::new();
+		sp_ver::RandomSeedInherentDataProvider(Default::default())
+			.provide_inherent_data(&mut inherent_data)
+			.await
+			.unwrap();
+		let deadline = time::Duration::from_secs(9);
+		let block = block_on(proposer.propose(inherent_data, Default::default(), deadline, None))
+			.map(|r| r.block)
+			.unwrap();
+		let enqueued = match block.extrinsics() {
+			[Extrinsic::EnqueueTxs(txs)] => txs.clone(),
+			extrinsics => panic!("unexpected extrinsics {:?}", extrinsics),
+		};
+		assert_eq!(enqueued.len(), 2);
+		block_on(client.import(BlockOrigin::Own, block.clone())).unwrap();
+
+		// Txs enqueued by the parent block have not been shuffled yet, they are executed by the
+		// next block so they count towards the execution phase.
+		let proposer = block_on(proposer_factory.init(block.header())).unwrap();
+		let queue_size = proposer.storage_queue_size().unwrap();
+		assert_eq!(queue_size, enqueued.iter().map(|tx| tx.len()).sum::<usize>());
+		assert_eq!(split.execution_shares(0, 2 * queue_size), (min, min));
+		assert_eq!(
+			split.execution_shares(queue_size, 2 * queue_size),
+			(Percent::from_percent(50), Percent::from_percent(50))
+		);
+	}
+
+	#[test]
+	fn pending_txs_size_skips_executed_txs() {
+		let entry = |shuffle_index, txs: &[usize]| StorageQueueEntry {
+			block_number: 1,
+			shuffle_index,
+			txs: txs.iter().map(|len| (None, vec![0; *len])).collect(),
+		};
+
+		assert_eq!(pending_txs_size(&[]), 0);
+		assert_eq!(pending_txs_size(&[entry(Some(1), &[1, 2, 4]), entry(None, &[8, 16])]), 30);
+		assert_eq!(pending_txs_size(&[entry(Some(3), &[1, 2, 4])]), 0);
+	}
+
+	#[test]
+	fn fixed_phase_split_ignores_queue_size() {
+		let split = PhaseSplit::default();
+
+		assert_eq!(
+			split.execution_shares(0, 1000),
+			(Percent::from_percent(55), Percent::from_percent(50))
+		);
+		assert_eq!(
+			split.execution_shares(1000, 1000),
+			(Percent::from_percent(55), Percent::from_percent(50))
+		);
+	}
+
+	#[test]
+	fn adaptive_phase_split_follows_queue_size() {
+		let split =
+			PhaseSplit::Adaptive { min: Percent::from_percent(10), max: Percent::from_percent(70) };
+
+		assert_eq!(
+			split.execution_shares(0, 1000),
+			(Percent::from_percent(10), Percent::from_percent(10))
+		);
+		assert_eq!(
+			split.execution_shares(300, 1000),
+			(Percent::from_percent(30), Percent::from_percent(30))
+		);
+		assert_eq!(
+			split.execution_shares(2000, 1000),
+			(Percent::from_percent(70), Percent::from_percent(70))
+		);
+	}
 }
@@ -105,7 +105,8 @@ use aquamarine::aquamarine;
 /// For that reason we divide every slot into 2 part, and we apply `X/2` for each of them.
 /// As a result we know that execution of previous block extrinsics will use at most 50% of the
 /// resources (time, size, weight) and remainig time can be used for validation and inclusion of txs
-/// submitted by users.
+/// submitted by users. Exact split can be configured with
+/// [`basic_authorship::ProposerFactory::set_phase_split`], see [`basic_authorship::PhaseSplit`].
 ///
 ///
 /// so comparing to origin impl which can be presented as
@@ -190,4 +191,6 @@ use aquamarine::aquamarine;
 /// for block executive details see [frame_executive](https://storage.googleapis.com/mangata-docs-node/frame_executive/struct.Executive.html)
 pub mod basic_authorship;
 
-pub use crate::basic_authorship::{Proposer, ProposerFactory, DEFAULT_BLOCK_SIZE_LIMIT};
+pub use crate::basic_authorship::{
+	PhaseSplit, Proposer, ProposerFactory, DEFAULT_BLOCK_SIZE_LIMIT,
+};
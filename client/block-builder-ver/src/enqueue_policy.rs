@@ -0,0 +1,134 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Policies deciding which of the txs collected from the transaction pool are stored in the
+//! storage queue by the `enqueue_txs` inherent.
+
+use sp_runtime::{AccountId32, Percent};
+use sp_weights::Weight;
+pub use ver_api::EnqueueLimits;
+
+/// Tx that has been collected from the transaction pool and validated successfully.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnqueueCandidate {
+	/// tx signer
+	pub who: Option<AccountId32>,
+	/// tx dispatch weight
+	pub weight: Weight,
+	/// tx encoded length
+	pub encoded_len: usize,
+}
+
+/// Decides which txs are stored in the storage queue by the `enqueue_txs` inherent.
+pub trait EnqueuePolicy: Send + Sync {
+	/// Returns indexes of `candidates` that should be enqueued, in ascending order.
+	fn select(&self, candidates: &[EnqueueCandidate], limits: &EnqueueLimits) -> Vec<usize>;
+}
+
+/// Enqueues txs as long as their cumulative dispatch weight and encoded length fit into the
+/// [`EnqueueLimits`] provided by the runtime. Stops at the first tx that does not fit, so txs
+/// from the same account are never reordered.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BlockLimitsPolicy;
+
+impl EnqueuePolicy for BlockLimitsPolicy {
+	fn select(&self, candidates: &[EnqueueCandidate], limits: &EnqueueLimits) -> Vec<usize> {
+		let mut weight = Weight::zero();
+		let mut len = 0usize;
+		candidates
+			.iter()
+			.take_while(|candidate| {
+				weight = weight.saturating_add(candidate.weight);
+				len = len.saturating_add(candidate.encoded_len);
+				weight.all_lte(limits.max_weight) && len <= limits.max_len as usize
+			})
+			.enumerate()
+			.map(|(index, _)| index)
+			.collect()
+	}
+}
+
+/// Drops a fixed share of collected txs whenever more than `threshold` of them were collected,
+/// regardless of their weight and size.
+#[derive(Clone, Copy, Debug)]
+pub struct TruncatePolicy {
+	/// number of txs above which truncation kicks in
+	pub threshold: usize,
+	/// share of txs that is kept when truncating
+	pub keep: Percent,
+}
+
+impl Default for TruncatePolicy {
+	fn default() -> Self {
+		Self { threshold: 100, keep: Percent::from_percent(90) }
+	}
+}
+
+impl EnqueuePolicy for TruncatePolicy {
+	fn select(&self, candidates: &[EnqueueCandidate], _limits: &EnqueueLimits) -> Vec<usize> {
+		let count = if candidates.len() > self.threshold {
+			self.keep.mul_floor(candidates.len())
+		} else {
+			candidates.len()
+		};
+		(0..count).collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn candidate(weight: u64, encoded_len: usize) -> EnqueueCandidate {
+		EnqueueCandidate { who: None, weight: Weight::from_parts(weight, 0), encoded_len }
+	}
+
+	#[test]
+	fn block_limits_policy_stops_at_first_tx_exceeding_limits() {
+		let limits = EnqueueLimits { max_weight: Weight::from_parts(10, 0), max_len: 100 };
+		let candidates =
+			vec![candidate(4, 10), candidate(4, 10), candidate(4, 10), candidate(1, 1)];
+
+		assert_eq!(BlockLimitsPolicy.select(&candidates, &limits), vec![0, 1]);
+
+		let limits = EnqueueLimits { max_weight: Weight::from_parts(100, 0), max_len: 25 };
+		assert_eq!(BlockLimitsPolicy.select(&candidates, &limits), vec![0, 1]);
+	}
+
+	#[test]
+	fn block_limits_policy_accepts_all_txs_that_fit() {
+		let limits = EnqueueLimits { max_weight: Weight::from_parts(100, 0), max_len: 100 };
+		let candidates = vec![candidate(1, 1); 10];
+
+		assert_eq!(BlockLimitsPolicy.select(&candidates, &limits), (0..10).collect::<Vec<_>>());
+	}
+
+	#[test]
+	fn truncate_policy_drops_txs_above_threshold() {
+		let limits = EnqueueLimits { max_weight: Weight::zero(), max_len: 0 };
+
+		assert_eq!(
+			TruncatePolicy::default().select(&vec![candidate(1, 1); 100], &limits).len(),
+			100
+		);
+		assert_eq!(
+			TruncatePolicy::default().select(&vec![candidate(1, 1); 200], &limits).len(),
+			180
+		);
+	}
+}
@@ -26,12 +26,15 @@
 
 #![warn(missing_docs)]
 
+use std::sync::Arc;
+
 use codec::{Decode, Encode};
 
 use sp_api::{
-	ApiExt, ApiRef, Core, ProvideRuntimeApi, StorageChanges, StorageProof, TransactionOutcome,
+	ApiError, ApiExt, ApiRef, Core, ProvideRuntimeApi, StorageChanges, StorageProof,
+	TransactionOutcome,
 };
-use sp_blockchain::{ApplyExtrinsicFailed, Error};
+use sp_blockchain::{ApplyExtrinsicFailed, Error, VerFailure};
 use sp_core::ExecutionContext;
 use sp_runtime::{
 	generic::BlockId,
@@ -46,6 +49,13 @@ use ver_api::VerApi;
 use sc_client_api::backend;
 use sp_core::ShufflingSeed;
 use sp_ver::extract_inherent_data;
+use sp_weights::Weight;
+
+mod enqueue_policy;
+
+pub use enqueue_policy::{
+	BlockLimitsPolicy, EnqueueCandidate, EnqueueLimits, EnqueuePolicy, TruncatePolicy,
+};
 
 /// Used as parameter to [`BlockBuilderProvider`] to express if proof recording should be enabled.
 ///
@@ -109,6 +119,31 @@ where
 	}
 }
 
+/// Fetch version of [`VerApi`] implemented by the runtime at given block.
+///
+/// Methods added to [`VerApi`] after its initial version can only be called if this version is
+/// high enough.
+pub fn ver_api_version<'a, Block, Api>(
+	api: &<Api as ProvideRuntimeApi<Block>>::Api,
+	block_id: Block::Hash,
+) -> Result<u32, sp_api::ApiError>
+where
+	Block: BlockT,
+	Api: ProvideRuntimeApi<Block> + 'a,
+	Api::Api: VerApi<Block>,
+{
+	api.api_version::<dyn VerApi<Block>>(block_id)?
+		.ok_or_else(|| Error::VersionInvalid("VerApi".to_string()).into())
+}
+
+/// Wraps an error of VER related runtime call into [`Error::Ver`].
+fn ver_error<E>(failure: VerFailure) -> impl FnOnce(E) -> Error
+where
+	E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+{
+	move |e| Error::Ver(failure, e.into())
+}
+
 /// A block that was build by [`BlockBuilder`] plus some additional data.
 ///
 /// This additional data includes the `storage_changes`, these changes can be applied to the
@@ -122,6 +157,30 @@ pub struct BuiltBlock<Block: BlockT, StateBackend: backend::StateBackend<HashFor
 	pub storage_changes: StorageChanges<StateBackend, Block>,
 	/// An optional proof that was recorded while building the block.
 	pub proof: Option<StorageProof>,
+	/// Txs collected for the enqueue inherent of the block.
+	pub enqueued_txs: EnqueuedTxs,
+}
+
+/// Txs collected for the enqueue inherent of a built block.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EnqueuedTxs {
+	/// Number of valid txs collected from the pool.
+	pub collected: usize,
+	/// Number of collected txs stored in the storage queue, the rest was dropped by the
+	/// [`EnqueuePolicy`].
+	pub enqueued: usize,
+	/// No txs were collected as the storage queue was full.
+	pub queue_full: bool,
+}
+
+/// Txs enqueued by previous blocks, processed by
+/// [`BlockBuilder::apply_previous_block_extrinsics`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PreviousBlockTxs {
+	/// Number of txs executed in the block.
+	pub executed: usize,
+	/// Number of txs left in the storage queue for the following blocks.
+	pub left: usize,
 }
 
 impl<Block: BlockT, StateBackend: backend::StateBackend<HashFor<Block>>>
@@ -170,6 +229,10 @@ pub struct BlockBuilder<'a, Block: BlockT, A: ProvideRuntimeApi<Block>, B> {
 	backend: &'a B,
 	/// The estimated size of the block header.
 	estimated_header_size: usize,
+	/// Decides which of the collected txs are stored in the storage queue.
+	enqueue_policy: Arc<dyn EnqueuePolicy>,
+	/// Version of [`VerApi`] implemented by the runtime.
+	ver_api_version: u32,
 }
 
 impl<'a, Block, A, B> BlockBuilder<'a, Block, A, B>
@@ -211,6 +274,7 @@ where
 		}
 
 		let block_id = BlockId::Hash(parent_hash);
+		let ver_api_version = ver_api_version::<Block, A>(&api, parent_hash)?;
 
 		api.initialize_block_with_context(
 			parent_hash,
@@ -226,9 +290,20 @@ where
 			block_id,
 			backend,
 			estimated_header_size,
+			enqueue_policy: Arc::new(BlockLimitsPolicy),
+			ver_api_version,
 		})
 	}
 
+	/// Set the policy deciding which of the txs collected in [`BlockBuilder::build_with_seed`]
+	/// are stored in the storage queue.
+	///
+	/// [`BlockLimitsPolicy`] is used by default. Runtimes implementing [`VerApi`] older than
+	/// version 2 do not provide enqueue limits, [`TruncatePolicy`] is always used for them.
+	pub fn set_enqueue_policy(&mut self, policy: Arc<dyn EnqueuePolicy>) {
+		self.enqueue_policy = policy;
+	}
+
 	/// temporaily apply extrinsics and record them on the list
 	pub fn build_with_seed<
 		F: FnOnce(
@@ -236,24 +311,45 @@ where
 			&'_ A::Api,
 		) -> Vec<(Option<sp_runtime::AccountId32>, Block::Extrinsic)>,
 	>(
-		mut self,
+		self,
 		seed: ShufflingSeed,
 		call: F,
 	) -> Result<BuiltBlock<Block, backend::StateBackendFor<B, Block>>, Error> {
 		let parent_hash = self.parent_hash;
 
-		let previous_block_txs = self.api.get_previous_block_txs(parent_hash).unwrap();
+		let previous_block_txs = self
+			.api
+			.get_previous_block_txs(parent_hash)
+			.map_err(ver_error(VerFailure::PreviousBlockTxs))?;
 
-		let mut valid_txs = if self.extrinsics.len() == 0 && previous_block_txs.len() > 0 {
+		let mut queue_full = false;
+		let valid_txs = if self.extrinsics.len() == 0 && previous_block_txs.len() > 0 {
 			log::info!(target:"block_builder", "Not enough room for (any) StoragQeueue enqueue inherent, producing empty block");
 			vec![]
-		} else if self.api.can_enqueue_txs(parent_hash).unwrap() {
+		} else if self
+			.api
+			.can_enqueue_txs(parent_hash)
+			.map_err(ver_error(VerFailure::CanEnqueueTxs))?
+		{
 			self.api.execute_in_transaction(|api| {
-				let next_header = api
-					.finalize_block_with_context(parent_hash, ExecutionContext::BlockConstruction)
-					.unwrap();
-
-				api.start_prevalidation(parent_hash).unwrap();
+				let prevalidation = || -> Result<_, ApiError> {
+					let next_header = api.finalize_block_with_context(
+						parent_hash,
+						ExecutionContext::BlockConstruction,
+					)?;
+					api.start_prevalidation(parent_hash)?;
+					let is_storage_migration_scheduled =
+						api.is_storage_migration_scheduled(parent_hash)?;
+					Ok((next_header, is_storage_migration_scheduled))
+				};
+				let (next_header, is_storage_migration_scheduled) = match prevalidation() {
+					Ok(result) => result,
+					Err(e) =>
+						return TransactionOutcome::Rollback(Err(Error::Ver(
+							VerFailure::Prevalidation,
+							e.into(),
+						))),
+				};
 
 				// create dummy header just to condider N+1 block extrinsics like new session
 				let header = <<Block as BlockT>::Header as HeaderT>::new(
@@ -264,38 +360,66 @@ where
 					Default::default(),
 				);
 
-				if api.is_storage_migration_scheduled(parent_hash).unwrap() {
+				if is_storage_migration_scheduled {
 					log::debug!(target:"block_builder", "storage migration scheduled - ignoring any txs");
-					TransactionOutcome::Rollback(vec![])
+					TransactionOutcome::Rollback(Ok(vec![]))
+				} else if let Err(e) = api.initialize_block_with_context(
+					parent_hash,
+					ExecutionContext::BlockConstruction,
+					&header,
+				) {
+					TransactionOutcome::Rollback(Err(Error::Ver(
+						VerFailure::Prevalidation,
+						e.into(),
+					)))
 				} else {
-					api.initialize_block_with_context(
-						parent_hash,
-						ExecutionContext::BlockConstruction,
-						&header,
-					)
-					.unwrap();
 					let txs = call(&self.parent_hash, &api);
-					TransactionOutcome::Rollback(txs)
+					TransactionOutcome::Rollback(Ok(txs))
 				}
-			})
+			})?
 		} else {
 			log::info!(target:"block_builder", "storage queue is full, no room for new txs");
+			queue_full = true;
 			vec![]
 		};
 
-		// that should be improved at some point
-		if valid_txs.len() > 100 {
-			valid_txs.truncate(valid_txs.len() * 90 / 100);
-		}
+		let collected = valid_txs.len();
+		let valid_txs = self.select_txs_to_enqueue(valid_txs)?;
+		let enqueued_txs = EnqueuedTxs { collected, enqueued: valid_txs.len(), queue_full };
+		let mut built_block =
+			self.finish_with_seed(seed, valid_txs.into_iter().map(|(_, tx)| tx).collect())?;
+		built_block.enqueued_txs = enqueued_txs;
+		Ok(built_block)
+	}
 
-		let valid_txs_count = valid_txs.len();
+	/// Builds a block that neither executes txs from the storage queue nor enqueues new ones.
+	///
+	/// Such block is always valid, so it can be used as a fallback when building a regular block
+	/// fails. Must not be combined with [`BlockBuilder::apply_previous_block_extrinsics`].
+	pub fn build_empty_with_seed(
+		mut self,
+		seed: ShufflingSeed,
+	) -> Result<BuiltBlock<Block, backend::StateBackendFor<B, Block>>, Error> {
+		self.api
+			.store_seed(self.parent_hash, seed.seed)
+			.map_err(ver_error(VerFailure::StoreSeed))?;
+		self.api.pop_txs(self.parent_hash, 0).map_err(ver_error(VerFailure::PopTxs))?;
+		self.extrinsics.clear();
+		self.finish_with_seed(seed, vec![])
+	}
+
+	/// Applies the enqueue inherent with given txs and finalizes the block.
+	fn finish_with_seed(
+		mut self,
+		seed: ShufflingSeed,
+		txs_to_enqueue: Vec<Block::Extrinsic>,
+	) -> Result<BuiltBlock<Block, backend::StateBackendFor<B, Block>>, Error> {
+		let parent_hash = self.parent_hash;
+		let valid_txs_count = txs_to_enqueue.len();
 		let store_txs_inherent = self
 			.api
-			.create_enqueue_txs_inherent(
-				parent_hash,
-				valid_txs.into_iter().map(|(_, tx)| tx).collect(),
-			)
-			.unwrap();
+			.create_enqueue_txs_inherent(parent_hash, txs_to_enqueue)
+			.map_err(ver_error(VerFailure::CreateEnqueueTxsInherent))?;
 
 		apply_transaction_wrapper::<Block, A>(
 			&self.api,
@@ -303,9 +427,9 @@ where
 			store_txs_inherent.clone(),
 			ExecutionContext::BlockConstruction,
 		)
-		.unwrap()
-		.unwrap()
-		.unwrap();
+		.map_err(ver_error(VerFailure::ApplyEnqueueTxsInherent))?
+		.map_err(ver_error(VerFailure::ApplyEnqueueTxsInherent))?
+		.map_err(|e| Error::Ver(VerFailure::ApplyEnqueueTxsInherent, format!("{:?}", e).into()))?;
 
 		// TODO get rid of collect
 		let mut next_header = self
@@ -345,9 +469,73 @@ where
 			block: <Block as BlockT>::new(next_header, all_extrinsics),
 			storage_changes,
 			proof,
+			enqueued_txs: Default::default(),
 		})
 	}
 
+	/// Filters collected txs using configured [`EnqueuePolicy`].
+	fn select_txs_to_enqueue(
+		&self,
+		txs: Vec<(Option<sp_runtime::AccountId32>, Block::Extrinsic)>,
+	) -> Result<Vec<(Option<sp_runtime::AccountId32>, Block::Extrinsic)>, Error> {
+		if txs.is_empty() {
+			return Ok(txs)
+		}
+
+		let mut selected = if self.ver_api_version < 2 {
+			let candidates = txs
+				.iter()
+				.map(|(who, tx)| EnqueueCandidate {
+					who: who.clone(),
+					weight: Default::default(),
+					encoded_len: tx.encoded_size(),
+				})
+				.collect::<Vec<_>>();
+			let limits = EnqueueLimits { max_weight: Weight::MAX, max_len: u32::MAX };
+			TruncatePolicy::default().select(&candidates, &limits)
+		} else {
+			let limits = self
+				.api
+				.get_enqueue_limits(self.parent_hash)
+				.map_err(ver_error(VerFailure::EnqueueLimits))?;
+			let candidates = txs
+				.iter()
+				.map(|(who, tx)| {
+					Ok(EnqueueCandidate {
+						who: who.clone(),
+						weight: self
+							.api
+							.get_dispatch_weight(self.parent_hash, tx.clone())
+							.map_err(ver_error(VerFailure::EnqueueLimits))?,
+						encoded_len: tx.encoded_size(),
+					})
+				})
+				.collect::<Result<Vec<_>, Error>>()?;
+			self.enqueue_policy.select(&candidates, &limits)
+		};
+		if self.ver_api_version >= 4 {
+			let max_txs = self
+				.api
+				.get_max_enqueued_txs(self.parent_hash)
+				.map_err(ver_error(VerFailure::EnqueueLimits))?;
+			selected.truncate(max_txs as usize);
+		}
+		if selected.len() < txs.len() {
+			log::info!(
+				target: "block_builder",
+				"enqueue policy left {}/{} txs in the pool",
+				txs.len() - selected.len(),
+				txs.len()
+			);
+		}
+
+		let mut txs = txs.into_iter().map(Some).collect::<Vec<_>>();
+		Ok(selected
+			.into_iter()
+			.filter_map(|index| txs.get_mut(index).and_then(Option::take))
+			.collect())
+	}
+
 	/// Push onto the block's list of extrinsics.
 	///
 	/// validate extrinsics but without commiting the change
@@ -376,21 +564,28 @@ where
 
 	/// fetch previous block and apply it
 	///
-	/// consequence of delayed block execution
+	/// consequence of delayed block execution, returns number of executed txs and txs left in the
+	/// storage queue
 	pub fn apply_previous_block_extrinsics<F>(
 		&mut self,
 		seed: ShufflingSeed,
 		block_size: &mut usize,
 		max_block_size: usize,
 		is_timer_expired: F,
-	) where
+	) -> Result<PreviousBlockTxs, Error>
+	where
 		F: Fn() -> bool,
 	{
 		let parent_hash = self.parent_hash;
-		self.api.store_seed(self.parent_hash, seed.seed).unwrap();
+		self.api
+			.store_seed(self.parent_hash, seed.seed)
+			.map_err(ver_error(VerFailure::StoreSeed))?;
 		let extrinsics = &mut self.extrinsics;
 
-		let previous_block_txs = self.api.get_previous_block_txs(self.parent_hash).unwrap();
+		let previous_block_txs = self
+			.api
+			.get_previous_block_txs(self.parent_hash)
+			.map_err(ver_error(VerFailure::PreviousBlockTxs))?;
 		let previous_block_txs_count = previous_block_txs.len();
 		log::debug!(target: "block_builder", "previous block enqueued {} txs", previous_block_txs_count);
 
@@ -437,8 +632,14 @@ where
 			}
 		}
 
-		self.api.pop_txs(self.parent_hash, extrinsics.len() as u64).unwrap();
+		self.api
+			.pop_txs(self.parent_hash, extrinsics.len() as u64)
+			.map_err(ver_error(VerFailure::PopTxs))?;
 		log::info!(target: "block_builder", "executed {}/{} previous block transactions", extrinsics.len(), previous_block_txs_count);
+		Ok(PreviousBlockTxs {
+			executed: extrinsics.len(),
+			left: previous_block_txs_count.saturating_sub(extrinsics.len()),
+		})
 	}
 
 	/// Create the inherents for the block.
@@ -479,8 +680,11 @@ where
 			self.inherents.encoded_size() +
 			self.api
 				.create_enqueue_txs_inherent(self.parent_hash, Default::default())
-				.unwrap()
-				.encoded_size();
+				.map(|xt| xt.encoded_size())
+				.unwrap_or_else(|e| {
+					log::warn!(target: "block_builder", "cannot estimate enqueue inherent size: {}", e);
+					0
+				});
 
 		if include_proof {
 			size + self.api.proof_recorder().map(|pr| pr.estimate_encoded_size()).unwrap_or(0)
@@ -557,4 +761,19 @@ mod tests {
 			.unwrap_err()
 			.contains("Database missing expected key"),);
 	}
+
+	#[test]
+	fn ver_api_version_is_fetched_from_runtime() {
+		let client = substrate_test_runtime_client::TestClientBuilder::new().build();
+		let api = client.runtime_api();
+
+		assert_eq!(
+			ver_api_version::<_, substrate_test_runtime_client::TestClient>(
+				&api,
+				client.info().best_hash
+			)
+			.unwrap(),
+			4
+		);
+	}
 }
@@ -58,12 +58,14 @@ use sp_runtime::{
 };
 
 mod import_queue;
+mod shuffling_seed;
 
 pub use import_queue::{
 	build_verifier, import_queue, AuraVerifier, BuildVerifierParams, CheckForEquivocation,
 	ImportQueueParams,
 };
 pub use sc_consensus_slots::SlotProportion;
+pub use shuffling_seed::{check_shuffling_seed, ShufflingSeedVerifier};
 pub use sp_consensus::SyncOracle;
 pub use sp_consensus_aura::{
 	digests::CompatibleDigestItem,
@@ -575,6 +577,9 @@ pub enum Error<B: BlockT> {
 	/// Inherents Error
 	#[error("Inherent error: {0}")]
 	Inherent(sp_inherents::Error),
+	/// Bad shuffling seed
+	#[error("Bad shuffling seed on {0:?}: {1}")]
+	BadShufflingSeed(B::Hash, sp_ver::SeedVerificationError),
 }
 
 impl<B: BlockT> From<Error<B>> for String {
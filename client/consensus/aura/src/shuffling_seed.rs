@@ -0,0 +1,196 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Client side verification of VER shuffling seeds.
+//!
+//! Every VER block header carries a shuffling seed that is a VRF output of the parent block seed
+//! signed by the block author. The runtime verifies it during block execution, checks from this
+//! module allow to reject blocks with invalid seeds before they are executed, using headers only.
+
+use crate::{authorities, find_pre_digest, slot_author, AuthorityId, CompatibilityMode, Error};
+use codec::{Codec, Decode, Encode};
+use log::trace;
+use sc_consensus::{import_queue::Verifier, BlockImportParams};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_consensus_aura::AuraApi;
+use sp_core::crypto::Pair;
+use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
+use std::{fmt::Debug, marker::PhantomData, sync::Arc};
+
+/// Checks that the shuffling seed of `header` has been generated from the seed of
+/// `parent_header` by the author of the slot `header` was produced in.
+pub fn check_shuffling_seed<B, P>(
+	header: &B::Header,
+	parent_header: &B::Header,
+	authorities: &[AuthorityId<P>],
+) -> Result<(), Error<B>>
+where
+	B: BlockT,
+	P: Pair,
+	P::Signature: Codec,
+{
+	let slot = find_pre_digest::<B, P::Signature>(header)?;
+	let author = slot_author::<P>(slot, authorities).ok_or(Error::SlotAuthorNotFound)?;
+
+	sp_ver::verify_shuffling_seed(author.as_ref(), &parent_header.seed().seed, header.seed())
+		.map_err(|e| Error::BadShufflingSeed(header.hash(), e))
+}
+
+/// Import queue verifier that checks shuffling seeds of incoming blocks and passes the blocks
+/// with valid seeds to the inner verifier (e.g. [`crate::AuraVerifier`]).
+///
+/// Blocks with invalid seeds are rejected before they are executed, so the peer that provided
+/// them gets reported by the import queue.
+pub struct ShufflingSeedVerifier<C, P, V, N> {
+	client: Arc<C>,
+	inner: V,
+	compatibility_mode: CompatibilityMode<N>,
+	_phantom: PhantomData<P>,
+}
+
+impl<C, P, V, N> ShufflingSeedVerifier<C, P, V, N> {
+	/// Create a new verifier wrapping `inner`.
+	pub fn new(client: Arc<C>, inner: V, compatibility_mode: CompatibilityMode<N>) -> Self {
+		Self { client, inner, compatibility_mode, _phantom: PhantomData }
+	}
+}
+
+#[async_trait::async_trait]
+impl<B, C, P, V> Verifier<B> for ShufflingSeedVerifier<C, P, V, NumberFor<B>>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync,
+	C::Api: AuraApi<B, AuthorityId<P>>,
+	P: Pair + Send + Sync + 'static,
+	P::Public: Codec + Debug,
+	P::Signature: Encode + Decode,
+	V: Verifier<B>,
+{
+	async fn verify(
+		&mut self,
+		block: BlockImportParams<B, ()>,
+	) -> Result<BlockImportParams<B, ()>, String> {
+		// Same as the inner verifier, skip checks when execution checks are not expected.
+		if block.with_state() || block.state_action.skip_execution_checks() {
+			return self.inner.verify(block).await
+		}
+
+		let parent_hash = *block.header.parent_hash();
+		let parent_header = self
+			.client
+			.header(parent_hash)
+			.map_err(|e| format!("Could not fetch parent header {:?}: {}", parent_hash, e))?
+			.ok_or_else(|| format!("Parent header {:?} not found", parent_hash))?;
+		let authorities = authorities(
+			self.client.as_ref(),
+			parent_hash,
+			*block.header.number(),
+			&self.compatibility_mode,
+		)
+		.map_err(|e| format!("Could not fetch authorities at {:?}: {}", parent_hash, e))?;
+
+		check_shuffling_seed::<B, P>(&block.header, &parent_header, &authorities)
+			.map_err(|e| e.to_string())?;
+		trace!(target: crate::LOG_TARGET, "Shuffling seed of {:?} is valid", block.header.hash());
+
+		self.inner.verify(block).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::CompatibleDigestItem;
+	use sc_keystore::LocalKeystore;
+	use sp_application_crypto::key_types::AURA;
+	use sp_consensus_aura::sr25519::{AuthorityPair, AuthoritySignature};
+	use sp_core::{sr25519, ShufflingSeed};
+	use sp_keyring::sr25519::Keyring;
+	use sp_keystore::SyncCryptoStore;
+	use sp_runtime::{Digest, DigestItem};
+	use substrate_test_runtime_client::runtime::{Block, Header, H256};
+
+	fn header(number: u64, slot: u64, seed: ShufflingSeed) -> Header {
+		let mut header = Header::new(
+			number,
+			Default::default(),
+			Default::default(),
+			H256::from_low_u64_be(number.saturating_sub(1)),
+			Digest {
+				logs: vec![
+					<DigestItem as CompatibleDigestItem<AuthoritySignature>>::aura_pre_digest(
+						slot.into(),
+					),
+				],
+			},
+		);
+		header.set_seed(seed);
+		header
+	}
+
+	#[test]
+	fn rejects_shuffling_seed_signed_by_wrong_author() {
+		let keystore = LocalKeystore::in_memory();
+		let alice: sr25519::Public =
+			SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some(&Keyring::Alice.to_seed()))
+				.unwrap();
+		let bob: sr25519::Public =
+			SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some(&Keyring::Bob.to_seed()))
+				.unwrap();
+		let authorities: Vec<AuthorityId<AuthorityPair>> = vec![alice.into(), bob.into()];
+
+		let parent =
+			header(1, 0, ShufflingSeed { seed: H256::repeat_byte(1), proof: Default::default() });
+		let alice_seed = sp_ver::calculate_next_seed(&keystore, &alice, parent.seed())
+			.expect("alice key is in keystore");
+		let bob_seed = sp_ver::calculate_next_seed(&keystore, &bob, parent.seed())
+			.expect("bob key is in keystore");
+
+		// slot 2 belongs to alice
+		assert!(check_shuffling_seed::<Block, AuthorityPair>(
+			&header(2, 2, alice_seed.clone()),
+			&parent,
+			&authorities
+		)
+		.is_ok());
+		assert!(matches!(
+			check_shuffling_seed::<Block, AuthorityPair>(
+				&header(2, 2, bob_seed.clone()),
+				&parent,
+				&authorities
+			),
+			Err(Error::BadShufflingSeed(_, sp_ver::SeedVerificationError::BadProof))
+		));
+		// slot 3 belongs to bob
+		assert!(matches!(
+			check_shuffling_seed::<Block, AuthorityPair>(
+				&header(2, 3, alice_seed),
+				&parent,
+				&authorities
+			),
+			Err(Error::BadShufflingSeed(_, sp_ver::SeedVerificationError::BadProof))
+		));
+		assert!(check_shuffling_seed::<Block, AuthorityPair>(
+			&header(2, 3, bob_seed),
+			&parent,
+			&authorities
+		)
+		.is_ok());
+	}
+}
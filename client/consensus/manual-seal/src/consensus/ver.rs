@@ -0,0 +1,99 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Shuffling seed inherent, allows for manual seal to create blocks for VER runtimes.
+//!
+//! VER runtimes expect every block to carry a shuffling seed that is a VRF output of the parent
+//! block seed. Use [`ShufflingSeedProvider`] from `create_inherent_data_providers` along with
+//! `sc_basic_authorship_ver::ProposerFactory` as the manual seal environment:
+//!
+//! ```ignore
+//! let seed_provider = Arc::new(ShufflingSeedProvider::dev(client.clone()));
+//! let create_inherent_data_providers = move |parent, ()| {
+//! 	let seed = seed_provider.create_inherent_data_provider(parent).map_err(Into::into);
+//! 	async move { seed }
+//! };
+//! ```
+
+use crate::Error;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::key_types::AURA, sr25519};
+use sp_keystore::{testing::KeyStore, SyncCryptoStore, SyncCryptoStorePtr};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+use sp_ver::RandomSeedInherentDataProvider;
+use std::{marker::PhantomData, sync::Arc};
+
+/// Well known key used by [`ShufflingSeedProvider::dev`].
+pub const DEV_SEED_KEY: &str = "//Alice";
+
+/// Calculates shuffling seeds of blocks created by manual seal.
+///
+/// The seed of a new block is derived from the seed of its parent using the sr25519 key stored
+/// in the keystore (under the `aura` key type). Runtimes that verify seeds against the block
+/// author need that key to be the author of manual seal blocks.
+pub struct ShufflingSeedProvider<B, C> {
+	client: Arc<C>,
+	keystore: SyncCryptoStorePtr,
+	public: sr25519::Public,
+	_phantom: PhantomData<B>,
+}
+
+impl<B, C> ShufflingSeedProvider<B, C>
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+{
+	/// Create a new seed provider signing seeds with `public` key from `keystore`.
+	pub fn new(client: Arc<C>, keystore: SyncCryptoStorePtr, public: sr25519::Public) -> Self {
+		Self { client, keystore, public, _phantom: PhantomData }
+	}
+
+	/// Create a new seed provider signing seeds with the [`DEV_SEED_KEY`] stored in an in-memory
+	/// keystore.
+	pub fn dev(client: Arc<C>) -> Self {
+		let keystore = KeyStore::new();
+		let public = SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some(DEV_SEED_KEY))
+			.expect("in-memory keystore can always generate keys; qed");
+		Self::new(client, Arc::new(keystore), public)
+	}
+
+	/// Public key used to sign seeds.
+	pub fn public(&self) -> &sr25519::Public {
+		&self.public
+	}
+
+	/// Creates the seed inherent for a block built on top of `parent`.
+	pub fn create_inherent_data_provider(
+		&self,
+		parent: B::Hash,
+	) -> Result<RandomSeedInherentDataProvider, Error> {
+		let header = self
+			.client
+			.header(parent)?
+			.ok_or_else(|| Error::BlockNotFound(format!("{}", parent)))?;
+
+		let seed = sp_ver::calculate_next_seed::<dyn SyncCryptoStore>(
+			&*self.keystore,
+			&self.public,
+			header.seed(),
+		)
+		.ok_or_else(|| Error::StringError("Cannot sign shuffling seed".into()))?;
+
+		Ok(RandomSeedInherentDataProvider(seed))
+	}
+}
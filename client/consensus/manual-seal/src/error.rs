@@ -64,6 +64,12 @@ pub enum Error {
 	/// Supplied parent_hash doesn't exist in chain
 	#[error("Supplied parent_hash: {0} doesn't exist in chain")]
 	BlockNotFound(String),
+	/// More blocks than [`crate::rpc::MAX_CREATE_BLOCKS`] requested at once
+	#[error(
+		"Cannot create {0} blocks at once, at most {max} are allowed",
+		max = crate::rpc::MAX_CREATE_BLOCKS
+	)]
+	TooManyBlocks(u32),
 	/// Some string error
 	#[error("{0}")]
 	StringError(String),
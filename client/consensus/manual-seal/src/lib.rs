@@ -313,7 +313,8 @@ mod tests {
 	use sp_inherents::InherentData;
 	use sp_runtime::generic::{BlockId, Digest, DigestItem};
 	use substrate_test_runtime_client::{
-		AccountKeyring::*, DefaultTestClientBuilderExt, TestClientBuilder, TestClientBuilderExt,
+		runtime::Extrinsic, AccountKeyring::*, DefaultTestClientBuilderExt, TestClientBuilder,
+		TestClientBuilderExt,
 	};
 	use substrate_test_runtime_transaction_pool::{uxt, TestApi};
 
@@ -670,4 +671,96 @@ mod tests {
 		let header = client.header(created_block.hash).unwrap().unwrap();
 		assert_eq!(header.number, 1);
 	}
+
+	#[tokio::test]
+	async fn manual_seal_ver_blocks() {
+		use crate::{
+			consensus::ver::ShufflingSeedProvider,
+			rpc::{ManualSeal, ManualSealApiServer},
+		};
+		use codec::Encode;
+		use sc_basic_authorship_ver::PhaseSplit;
+		use sc_client_api::BlockBackend;
+		use sp_runtime::{traits::Header as HeaderT, Percent};
+
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let pool_api = Arc::new(FullChainApi::new(
+			client.clone(),
+			None,
+			&sp_core::testing::TaskExecutor::new(),
+		));
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			pool_api,
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let mut env = sc_basic_authorship_ver::ProposerFactory::new(
+			spawner.clone(),
+			client.clone(),
+			pool.clone(),
+			None,
+			None,
+		);
+		// do not wait for the execution phase deadline
+		env.set_phase_split(PhaseSplit::Fixed {
+			execution_time: Percent::zero(),
+			execution_size: Percent::from_percent(50),
+		});
+		let seed_provider = Arc::new(ShufflingSeedProvider::dev(client.clone()));
+		let public = *seed_provider.public();
+
+		let (sink, commands_stream) = futures::channel::mpsc::channel(1024);
+		let future = run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: move |parent, ()| {
+				let seed = seed_provider.create_inherent_data_provider(parent).map_err(Into::into);
+				async move { seed }
+			},
+		});
+		std::thread::spawn(|| {
+			let rt = tokio::runtime::Runtime::new().unwrap();
+			// spawn the background authorship task
+			rt.block_on(future);
+		});
+		assert!(pool.submit_one(&BlockId::Number(0), SOURCE, uxt(Alice, 0)).await.is_ok());
+
+		// tx is enqueued in the first block and executed in the second one
+		let created = ManualSeal::new(sink).create_blocks(2, false).await.unwrap();
+		assert_eq!(created.len(), 2);
+
+		let mut parent = client.header(genesis_hash).unwrap().unwrap();
+		for (number, block) in created.iter().enumerate() {
+			let header = client.header(block.hash).unwrap().unwrap();
+			assert_eq!(header.number, number as u64 + 1);
+			assert_eq!(*header.parent_hash(), parent.hash());
+			assert_eq!(
+				sp_ver::verify_shuffling_seed(public.as_ref(), &parent.seed().seed, header.seed()),
+				Ok(())
+			);
+			parent = header;
+		}
+		assert_eq!(
+			client.block_body(created[0].hash).unwrap().unwrap(),
+			vec![Extrinsic::EnqueueTxs(vec![uxt(Alice, 0).encode()])]
+		);
+		let body = client.block_body(created[1].hash).unwrap().unwrap();
+		assert_eq!(body.first(), Some(&uxt(Alice, 0)));
+		assert_eq!(client.header(created[1].hash).unwrap().unwrap().count, 1);
+	}
 }
@@ -31,6 +31,9 @@ use sc_consensus::ImportedAux;
 use serde::{Deserialize, Serialize};
 use sp_runtime::EncodedJustification;
 
+/// Maximum number of blocks that can be requested by a single `engine_createBlocks` call.
+pub const MAX_CREATE_BLOCKS: u32 = 256;
+
 /// Sender passed to the authorship task to report errors or successes.
 pub type Sender<T> = Option<oneshot::Sender<std::result::Result<T, Error>>>;
 
@@ -76,6 +79,14 @@ pub trait ManualSealApi<Hash> {
 		parent_hash: Option<Hash>,
 	) -> RpcResult<CreatedBlock<Hash>>;
 
+	/// Instructs the manual-seal authorship task to create `count` blocks one after another, on
+	/// top of the best block. Blocks are created even if the transaction pool is empty, so txs
+	/// enqueued by VER runtimes get executed. At most [`MAX_CREATE_BLOCKS`] blocks can be
+	/// requested at once.
+	#[method(name = "engine_createBlocks")]
+	async fn create_blocks(&self, count: u32, finalize: bool)
+		-> RpcResult<Vec<CreatedBlock<Hash>>>;
+
 	/// Instructs the manual-seal authorship task to finalize a block
 	#[method(name = "engine_finalizeBlock")]
 	async fn finalize_block(
@@ -133,6 +144,21 @@ impl<Hash: Send + 'static> ManualSealApiServer<Hash> for ManualSeal<Hash> {
 		}
 	}
 
+	async fn create_blocks(
+		&self,
+		count: u32,
+		finalize: bool,
+	) -> RpcResult<Vec<CreatedBlock<Hash>>> {
+		if count > MAX_CREATE_BLOCKS {
+			return Err(Error::TooManyBlocks(count).into())
+		}
+		let mut created = Vec::new();
+		for _ in 0..count {
+			created.push(self.create_block(true, finalize, None).await?);
+		}
+		Ok(created)
+	}
+
 	async fn finalize_block(
 		&self,
 		hash: Hash,
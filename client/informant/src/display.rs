@@ -25,8 +25,12 @@ use sc_network_common::sync::{
 	warp::{WarpSyncPhase, WarpSyncProgress},
 	SyncState, SyncStatus,
 };
-use sp_runtime::traits::{Block as BlockT, CheckedDiv, NumberFor, Saturating, Zero};
+use sp_runtime::{
+	traits::{Block as BlockT, CheckedDiv, NumberFor, Saturating, Zero},
+	SaturatedConversion,
+};
 use std::{fmt, time::Instant};
+use ver_api::StorageQueueStatus;
 
 /// State of the informant display system.
 ///
@@ -34,7 +38,10 @@ use std::{fmt, time::Instant};
 /// like:
 ///
 /// > Syncing  5.4 bps, target=#531028 (4 peers), best: #90683 (0x4ca8…51b8),
-/// > finalized #360 (0x6f24…a38b), ⬇ 5.5kiB/s ⬆ 0.9kiB/s
+/// > finalized #360 (0x6f24…a38b), queue: 52 txs in 2 blocks (oldest 2 blocks ago),
+/// > ⬇ 5.5kiB/s ⬆ 0.9kiB/s
+///
+/// The storage queue part is only displayed if the runtime exposes the storage queue.
 ///
 /// # Usage
 ///
@@ -72,6 +79,7 @@ impl<B: BlockT> InformantDisplay<B> {
 		info: &ClientInfo<B>,
 		net_status: NetworkStatus,
 		sync_status: SyncStatus<B>,
+		queue_status: Option<StorageQueueStatus>,
 	) {
 		let best_number = info.chain.best_number;
 		let best_hash = info.chain.best_hash;
@@ -133,10 +141,21 @@ impl<B: BlockT> InformantDisplay<B> {
 					("⚙️ ", format!("Preparing{}", speed), format!(", target=#{target}")),
 			};
 
+		let queue = queue_status
+			.map(|status| {
+				let queue = StorageQueueFormat(status, best_number.saturated_into::<u32>());
+				if self.format.enable_color {
+					format!(", queue: {}", Colour::White.bold().paint(queue.to_string()))
+				} else {
+					format!(", queue: {}", queue)
+				}
+			})
+			.unwrap_or_default();
+
 		if self.format.enable_color {
 			info!(
 				target: "substrate",
-				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}), {} {}",
+				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}){}, {} {}",
 				level,
 				Colour::White.bold().paint(&status),
 				target,
@@ -145,13 +164,14 @@ impl<B: BlockT> InformantDisplay<B> {
 				best_hash,
 				Colour::White.bold().paint(format!("{}", finalized_number)),
 				info.chain.finalized_hash,
+				queue,
 				Colour::Green.paint(format!("⬇ {}", TransferRateFormat(avg_bytes_per_sec_inbound))),
 				Colour::Red.paint(format!("⬆ {}", TransferRateFormat(avg_bytes_per_sec_outbound))),
 			)
 		} else {
 			info!(
 				target: "substrate",
-				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}), ⬇ {} ⬆ {}",
+				"{} {}{} ({} peers), best: #{} ({}), finalized #{} ({}){}, ⬇ {} ⬆ {}",
 				level,
 				status,
 				target,
@@ -160,6 +180,7 @@ impl<B: BlockT> InformantDisplay<B> {
 				best_hash,
 				finalized_number,
 				info.chain.finalized_hash,
+				queue,
 				TransferRateFormat(avg_bytes_per_sec_inbound),
 				TransferRateFormat(avg_bytes_per_sec_outbound),
 			)
@@ -235,3 +256,20 @@ impl fmt::Display for TransferRateFormat {
 		write!(f, "{:.1}MiB/s", self.0 as f64 / (1024.0 * 1024.0))
 	}
 }
+
+/// Contains the status of the storage queue along with the best block number. Implements
+/// `fmt::Display` and shows the number of pending txs and how far behind their execution is.
+struct StorageQueueFormat(StorageQueueStatus, u32);
+impl fmt::Display for StorageQueueFormat {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let StorageQueueFormat(status, best_block) = self;
+		match status.oldest_block_age(*best_block) {
+			Some(age) => write!(
+				f,
+				"{} txs in {} blocks (oldest {} blocks ago)",
+				status.pending_txs, status.depth, age
+			),
+			None => write!(f, "empty"),
+		}
+	}
+}
@@ -28,6 +28,7 @@ use sc_network_common::sync::SyncStatusProvider;
 use sp_blockchain::HeaderMetadata;
 use sp_runtime::traits::{Block as BlockT, Header};
 use std::{collections::VecDeque, fmt::Display, sync::Arc, time::Duration};
+use ver_api::StorageQueueStatusProvider;
 
 mod display;
 
@@ -52,8 +53,16 @@ impl Default for OutputFormat {
 }
 
 /// Builds the informant and returns a `Future` that drives the informant.
-pub async fn build<B: BlockT, C, N, S>(client: Arc<C>, network: N, syncing: S, format: OutputFormat)
-where
+///
+/// Along with the sync status, the informant displays the status of the VER storage queue at the
+/// best block, if `queue_status` is provided and returns it.
+pub async fn build<B: BlockT, C, N, S>(
+	client: Arc<C>,
+	network: N,
+	syncing: S,
+	queue_status: Option<StorageQueueStatusProvider<B::Hash>>,
+	format: OutputFormat,
+) where
 	N: NetworkStatusProvider,
 	S: SyncStatusProvider<B>,
 	C: UsageProvider<B> + HeaderMetadata<B> + BlockchainEvents<B>,
@@ -83,7 +92,9 @@ where
 					"Usage statistics not displayed as backend does not provide it",
 				)
 			}
-			display.display(&info, net_status, sync_status);
+			let queue_status =
+				queue_status.as_ref().and_then(|provider| provider(info.chain.best_hash));
+			display.display(&info, net_status, sync_status, queue_status);
 			future::ready(())
 		});
 
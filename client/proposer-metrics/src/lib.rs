@@ -19,9 +19,10 @@
 //! Prometheus basic proposer metrics.
 
 use prometheus_endpoint::{
-	prometheus::CounterVec, register, Gauge, Histogram, HistogramOpts, Opts, PrometheusError,
-	Registry, U64,
+	prometheus::CounterVec, register, Counter, Gauge, GaugeVec, Histogram, HistogramOpts,
+	HistogramVec, Opts, PrometheusError, Registry, U64,
 };
+use std::time::Duration;
 
 /// Optional shareable link to basic authorship metrics.
 #[derive(Clone, Default)]
@@ -51,6 +52,14 @@ pub enum EndProposingReason {
 	HitBlockWeightLimit,
 }
 
+/// Phase of VER block construction.
+pub enum VerPhase {
+	/// Execution of txs enqueued by previous blocks.
+	Execution,
+	/// Collection of txs from the pool into the enqueue inherent.
+	Collection,
+}
+
 /// Authorship metrics.
 #[derive(Clone)]
 pub struct Metrics {
@@ -59,6 +68,12 @@ pub struct Metrics {
 	pub end_proposing_reason: CounterVec,
 	pub create_inherents_time: Histogram,
 	pub create_block_proposal_time: Histogram,
+	pub ver_failures: CounterVec,
+	pub ver_phase_time: HistogramVec,
+	pub ver_queued_txs: GaugeVec<U64>,
+	pub ver_enqueued_txs: GaugeVec<U64>,
+	pub ver_storage_queue_depth: Gauge<U64>,
+	pub ver_storage_queue_full: Counter<U64>,
 }
 
 impl Metrics {
@@ -102,6 +117,62 @@ impl Metrics {
 				)?,
 				registry,
 			)?,
+			ver_failures: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_proposer_ver_failures",
+						"Number of VER block building failures by the failed step.",
+					),
+					&["kind"],
+				)?,
+				registry,
+			)?,
+			ver_phase_time: register(
+				HistogramVec::new(
+					HistogramOpts::new(
+						"substrate_proposer_ver_phase_time",
+						"Histogram of time taken by the phases of VER block construction",
+					),
+					&["phase"],
+				)?,
+				registry,
+			)?,
+			ver_queued_txs: register(
+				GaugeVec::new(
+					Opts::new(
+						"substrate_proposer_ver_queued_txs",
+						"Number of txs from the storage queue executed in block or left for the \
+						following blocks",
+					),
+					&["status"],
+				)?,
+				registry,
+			)?,
+			ver_enqueued_txs: register(
+				GaugeVec::new(
+					Opts::new(
+						"substrate_proposer_ver_enqueued_txs",
+						"Number of txs collected into the enqueue inherent or dropped by the \
+						enqueue policy",
+					),
+					&["status"],
+				)?,
+				registry,
+			)?,
+			ver_storage_queue_depth: register(
+				Gauge::new(
+					"substrate_proposer_ver_storage_queue_depth",
+					"Number of blocks with txs held in the storage queue",
+				)?,
+				registry,
+			)?,
+			ver_storage_queue_full: register(
+				Counter::new(
+					"substrate_proposer_ver_storage_queue_full",
+					"Number of blocks that could not enqueue any txs as the storage queue was full",
+				)?,
+				registry,
+			)?,
 		})
 	}
 
@@ -116,4 +187,32 @@ impl Metrics {
 
 		self.end_proposing_reason.with_label_values(&[reason]).inc();
 	}
+
+	/// Report VER block building failure of given kind.
+	pub fn report_ver_failure(&self, kind: &str) {
+		self.ver_failures.with_label_values(&[kind]).inc();
+	}
+
+	/// Report time taken by the given phase of VER block construction.
+	pub fn report_ver_phase_time(&self, phase: VerPhase, time: Duration) {
+		let phase = match phase {
+			VerPhase::Execution => "execution",
+			VerPhase::Collection => "collection",
+		};
+
+		self.ver_phase_time.with_label_values(&[phase]).observe(time.as_secs_f64());
+	}
+
+	/// Report number of txs from the storage queue `executed` in block and `left` for the
+	/// following blocks.
+	pub fn report_ver_queued_txs(&self, executed: usize, left: usize) {
+		self.ver_queued_txs.with_label_values(&["executed"]).set(executed as u64);
+		self.ver_queued_txs.with_label_values(&["left"]).set(left as u64);
+	}
+
+	/// Report number of txs `enqueued` by block and `dropped` by the enqueue policy.
+	pub fn report_ver_enqueued_txs(&self, enqueued: usize, dropped: usize) {
+		self.ver_enqueued_txs.with_label_values(&["enqueued"]).set(enqueued as u64);
+		self.ver_enqueued_txs.with_label_values(&["dropped"]).set(dropped as u64);
+	}
 }
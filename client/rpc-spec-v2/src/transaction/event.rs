@@ -79,19 +79,23 @@ pub struct TransactionDropped {
 /// 		- `Broadcast`
 ///
 /// 3. Leaving the pool:
+/// 		- `BestChainBlockEnqueued`
 /// 		- `BestChainBlockIncluded`
 /// 		- `Invalid`
 ///
-/// 4. Block finalized:
+/// 4. Leaving the storage queue without being executed:
+/// 		- `DroppedFromQueue`
+///
+/// 5. Block finalized:
 /// 		- `Finalized`
 ///
-/// 5. At any time:
+/// 6. At any time:
 /// 		- `Dropped`
 /// 		- `Error`
 ///
 /// The subscription's stream is considered finished whenever the following events are
-/// received: `Finalized`, `Error`, `Invalid` or `Dropped`. However, the user is allowed
-/// to unsubscribe at any moment.
+/// received: `Finalized`, `Error`, `Invalid`, `Dropped` or `DroppedFromQueue`. However, the user
+/// is allowed to unsubscribe at any moment.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 // We need to manually specify the trait bounds for the `Hash` trait to ensure `into` and
 // `from` still work.
@@ -112,6 +116,14 @@ pub enum TransactionEvent<Hash> {
 	/// This may contain `None` if the block is no longer a best
 	/// block of the chain.
 	BestChainBlockIncluded(Option<TransactionBlock<Hash>>),
+	/// The transaction was stored in the storage queue by a best block of the chain, it is
+	/// going to be executed (`BestChainBlockIncluded`) in one of the following blocks.
+	///
+	/// # Note
+	///
+	/// This may contain `None` if the block is no longer a best
+	/// block of the chain.
+	BestChainBlockEnqueued(Option<TransactionBlock<Hash>>),
 	/// The transaction was included in a finalized block.
 	Finalized(TransactionBlock<Hash>),
 	/// The transaction could not be processed due to an error.
@@ -120,6 +132,9 @@ pub enum TransactionEvent<Hash> {
 	Invalid(TransactionError),
 	/// The client was not capable of keeping track of this transaction.
 	Dropped(TransactionDropped),
+	/// The block that stored the transaction in the storage queue is no longer part of the
+	/// chain. The transaction is not going to be executed and has to be submitted again.
+	DroppedFromQueue,
 }
 
 /// Intermediate representation (IR) for the transaction events
@@ -140,6 +155,8 @@ pub enum TransactionEvent<Hash> {
 enum TransactionEventBlockIR<Hash> {
 	/// The transaction was included in the best block of the chain.
 	BestChainBlockIncluded(Option<TransactionBlock<Hash>>),
+	/// The transaction was stored in the storage queue by the best block of the chain.
+	BestChainBlockEnqueued(Option<TransactionBlock<Hash>>),
 	/// The transaction was included in a finalized block of the chain.
 	Finalized(TransactionBlock<Hash>),
 }
@@ -165,6 +182,7 @@ enum TransactionEventNonBlockIR {
 	Error(TransactionError),
 	Invalid(TransactionError),
 	Dropped(TransactionDropped),
+	DroppedFromQueue,
 }
 
 /// Intermediate representation (IR) used for serialization/deserialization of the
@@ -192,6 +210,8 @@ impl<Hash> From<TransactionEvent<Hash>> for TransactionEventIR<Hash> {
 				TransactionEventIR::NonBlock(TransactionEventNonBlockIR::Broadcasted(event)),
 			TransactionEvent::BestChainBlockIncluded(event) =>
 				TransactionEventIR::Block(TransactionEventBlockIR::BestChainBlockIncluded(event)),
+			TransactionEvent::BestChainBlockEnqueued(event) =>
+				TransactionEventIR::Block(TransactionEventBlockIR::BestChainBlockEnqueued(event)),
 			TransactionEvent::Finalized(event) =>
 				TransactionEventIR::Block(TransactionEventBlockIR::Finalized(event)),
 			TransactionEvent::Error(event) =>
@@ -200,6 +220,8 @@ impl<Hash> From<TransactionEvent<Hash>> for TransactionEventIR<Hash> {
 				TransactionEventIR::NonBlock(TransactionEventNonBlockIR::Invalid(event)),
 			TransactionEvent::Dropped(event) =>
 				TransactionEventIR::NonBlock(TransactionEventNonBlockIR::Dropped(event)),
+			TransactionEvent::DroppedFromQueue =>
+				TransactionEventIR::NonBlock(TransactionEventNonBlockIR::DroppedFromQueue),
 		}
 	}
 }
@@ -214,11 +236,14 @@ impl<Hash> From<TransactionEventIR<Hash>> for TransactionEvent<Hash> {
 				TransactionEventNonBlockIR::Error(event) => TransactionEvent::Error(event),
 				TransactionEventNonBlockIR::Invalid(event) => TransactionEvent::Invalid(event),
 				TransactionEventNonBlockIR::Dropped(event) => TransactionEvent::Dropped(event),
+				TransactionEventNonBlockIR::DroppedFromQueue => TransactionEvent::DroppedFromQueue,
 			},
 			TransactionEventIR::Block(block) => match block {
 				TransactionEventBlockIR::Finalized(event) => TransactionEvent::Finalized(event),
 				TransactionEventBlockIR::BestChainBlockIncluded(event) =>
 					TransactionEvent::BestChainBlockIncluded(event),
+				TransactionEventBlockIR::BestChainBlockEnqueued(event) =>
+					TransactionEvent::BestChainBlockEnqueued(event),
 			},
 		}
 	}
@@ -295,6 +320,43 @@ mod tests {
 		assert_eq!(event_dec, event);
 	}
 
+	#[test]
+	fn best_chain_enqueued_event() {
+		let event: TransactionEvent<()> = TransactionEvent::BestChainBlockEnqueued(None);
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"event":"bestChainBlockEnqueued","block":null}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionEvent<()> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+
+		let event: TransactionEvent<H256> =
+			TransactionEvent::BestChainBlockEnqueued(Some(TransactionBlock {
+				hash: H256::from_low_u64_be(1),
+				index: 2,
+			}));
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"event":"bestChainBlockEnqueued","block":{"hash":"0x0000000000000000000000000000000000000000000000000000000000000001","index":"2"}}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionEvent<H256> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
+	#[test]
+	fn dropped_from_queue_event() {
+		let event: TransactionEvent<()> = TransactionEvent::DroppedFromQueue;
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"event":"droppedFromQueue"}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionEvent<()> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, event);
+	}
+
 	#[test]
 	fn finalized_event() {
 		let event: TransactionEvent<H256> = TransactionEvent::Finalized(TransactionBlock {
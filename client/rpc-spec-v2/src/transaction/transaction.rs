@@ -187,6 +187,17 @@ impl TransactionState {
 					index,
 				}))),
 			TransactionStatus::Retracted(_) => Some(TransactionEvent::BestChainBlockIncluded(None)),
+			TransactionStatus::Enqueued((hash, index)) =>
+				Some(TransactionEvent::BestChainBlockEnqueued(Some(TransactionBlock {
+					hash,
+					index,
+				}))),
+			TransactionStatus::Executed((hash, index)) =>
+				Some(TransactionEvent::BestChainBlockIncluded(Some(TransactionBlock {
+					hash,
+					index,
+				}))),
+			TransactionStatus::DroppedFromQueue(_) => Some(TransactionEvent::DroppedFromQueue),
 			TransactionStatus::FinalityTimeout(_) =>
 				Some(TransactionEvent::Dropped(TransactionDropped {
 					broadcasted: self.broadcasted,
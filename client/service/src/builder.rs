@@ -391,6 +391,9 @@ pub struct SpawnTasksParams<'a, TBl: BlockT, TCl, TExPool, TRpc, Backend> {
 	pub sync_service: Arc<SyncingService<TBl>>,
 	/// Telemetry instance for this node.
 	pub telemetry: Option<&'a mut Telemetry>,
+	/// Status of the VER storage queue reported by the informant and telemetry, `None` if the
+	/// runtime does not implement [`ver_api::VerApi`].
+	pub storage_queue_status: Option<ver_api::StorageQueueStatusProvider<<TBl as BlockT>::Hash>>,
 }
 
 /// Build a shared offchain workers instance.
@@ -468,6 +471,7 @@ where
 		tx_handler_controller,
 		sync_service,
 		telemetry,
+		storage_queue_status,
 	} = params;
 
 	let chain_info = client.usage_info().chain;
@@ -534,6 +538,7 @@ where
 			transaction_pool.clone(),
 			network.clone(),
 			sync_service.clone(),
+			storage_queue_status.clone(),
 		),
 	);
 
@@ -565,6 +570,7 @@ where
 			client.clone(),
 			network,
 			sync_service.clone(),
+			storage_queue_status,
 			config.informant_output_format,
 		),
 	);
@@ -33,6 +33,7 @@ use std::{
 	sync::Arc,
 	time::{Duration, Instant},
 };
+use ver_api::{StorageQueueStatus, StorageQueueStatusProvider};
 
 struct PrometheusMetrics {
 	// generic info
@@ -181,6 +182,7 @@ impl MetricsService {
 		transactions: Arc<TExPool>,
 		network: TNet,
 		syncing: TSync,
+		queue_status: Option<StorageQueueStatusProvider<TBl::Hash>>,
 	) where
 		TBl: Block,
 		TCl: ProvideRuntimeApi<TBl> + UsageProvider<TBl>,
@@ -201,8 +203,13 @@ impl MetricsService {
 			// Try to get the latest syncing information.
 			let sync_status = syncing.status().await.ok();
 
+			// Try to get the latest status of the VER storage queue.
+			let info = client.usage_info();
+			let queue_status =
+				queue_status.as_ref().and_then(|provider| provider(info.chain.best_hash));
+
 			// Update / Send the metrics.
-			self.update(&client.usage_info(), &transactions.status(), net_status, sync_status);
+			self.update(&info, &transactions.status(), net_status, sync_status, queue_status);
 
 			// Schedule next tick.
 			timer.reset(timer_interval);
@@ -215,6 +222,7 @@ impl MetricsService {
 		txpool_status: &PoolStatus,
 		net_status: Option<NetworkStatus>,
 		sync_status: Option<SyncStatus<T>>,
+		queue_status: Option<StorageQueueStatus>,
 	) {
 		let now = Instant::now();
 		let elapsed = (now - self.last_update).as_secs();
@@ -281,6 +289,20 @@ impl MetricsService {
 			);
 		}
 
+		// Update/send VER storage queue information, if any.
+		if let Some(queue_status) = queue_status {
+			telemetry!(
+				self.telemetry;
+				SUBSTRATE_INFO;
+				"system.interval";
+				"ver_queue_depth" => queue_status.depth,
+				"ver_pending_txs" => queue_status.pending_txs,
+				"ver_oldest_enqueued_age" => queue_status
+					.oldest_block_age(best_number.saturated_into::<u32>())
+					.unwrap_or(0),
+			);
+		}
+
 		if let Some(sync_status) = sync_status {
 			if let Some(metrics) = self.metrics.as_ref() {
 				let best_seen_block: Option<u64> =
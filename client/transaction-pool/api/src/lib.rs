@@ -69,12 +69,16 @@ impl PoolStatus {
 /// 		- `Broadcast`
 /// 3. Leaving the pool:
 /// 		- `InBlock`
+/// 		- `Enqueued`
 /// 		- `Invalid`
 /// 		- `Usurped`
 /// 		- `Dropped`
-/// 	4. Re-entering the pool:
+/// 	4. Waiting for execution in the storage queue:
+/// 		- `Executed`
+/// 		- `DroppedFromQueue`
+/// 	5. Re-entering the pool:
 /// 		- `Retracted`
-/// 	5. Block finalized:
+/// 	6. Block finalized:
 /// 		- `Finalized`
 /// 		- `FinalityTimeout`
 ///
@@ -95,13 +99,17 @@ impl PoolStatus {
 /// such case).
 /// 4. `Retracted` transactions might be included in some next block.
 ///
-/// The stream is considered finished only when either `Finalized` or `FinalityTimeout`
-/// event is triggered. You are however free to unsubscribe from notifications at any point.
+/// The stream is considered finished only when either `Finalized`, `FinalityTimeout` or
+/// `DroppedFromQueue` event is triggered. You are however free to unsubscribe from notifications
+/// at any point.
 /// The first one will be emitted when the block, in which transaction was included gets
 /// finalized. The `FinalityTimeout` event will be emitted when the block did not reach finality
 /// within 512 blocks. This either indicates that finality is not available for your chain,
 /// or that finality gadget is lagging behind. If you choose to wait for finality longer, you can
 /// re-subscribe for a particular transaction hash manually again.
+/// The `DroppedFromQueue` event will be emitted when the block that stored the transaction in the
+/// storage queue has been retracted. Such transactions are not returned to the pool, they have to
+/// be submitted again.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TransactionStatus<Hash, BlockHash> {
@@ -115,6 +123,17 @@ pub enum TransactionStatus<Hash, BlockHash> {
 	/// at the given position.
 	#[serde(with = "v1_compatible")]
 	InBlock((BlockHash, TxIndex)),
+	/// Transaction has been stored in the storage queue by the block with given hash
+	/// at the given position. It will be executed in one of the following blocks.
+	#[serde(with = "v1_compatible")]
+	Enqueued((BlockHash, TxIndex)),
+	/// Transaction that has been enqueued before was executed in block with given hash
+	/// at the given position.
+	#[serde(with = "v1_compatible")]
+	Executed((BlockHash, TxIndex)),
+	/// The block that enqueued this transaction has been retracted, transaction is no longer
+	/// part of the storage queue and has to be submitted again.
+	DroppedFromQueue(BlockHash),
 	/// The block this transaction was included in has been retracted.
 	Retracted(BlockHash),
 	/// Maximum number of finality watchers has been reached,
@@ -418,5 +437,23 @@ mod tests {
 
 		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
 		assert_eq!(event_dec, TransactionStatus::Finalized((1, 0)));
+
+		let event: TransactionStatus<u8, u8> = TransactionStatus::Enqueued((1, 2));
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"enqueued":1}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, TransactionStatus::Enqueued((1, 0)));
+
+		let event: TransactionStatus<u8, u8> = TransactionStatus::Executed((1, 2));
+		let ser = serde_json::to_string(&event).unwrap();
+
+		let exp = r#"{"executed":1}"#;
+		assert_eq!(ser, exp);
+
+		let event_dec: TransactionStatus<u8, u8> = serde_json::from_str(exp).unwrap();
+		assert_eq!(event_dec, TransactionStatus::Executed((1, 0)));
 	}
 }
@@ -30,6 +30,10 @@ use super::{watcher, BlockHash, ChainApi, ExtrinsicHash};
 pub struct Listener<H: hash::Hash + Eq, C: ChainApi> {
 	watchers: HashMap<H, watcher::Sender<H, ExtrinsicHash<C>>>,
 	finality_watchers: LinkedHashMap<ExtrinsicHash<C>, Vec<H>>,
+	/// Txs stored in the storage queue, grouped by the block that enqueued them.
+	queue_watchers: LinkedHashMap<BlockHash<C>, Vec<H>>,
+	/// Txs that have been enqueued but not executed yet.
+	enqueued: HashMap<H, BlockHash<C>>,
 }
 
 /// Maximum number of blocks awaiting finality at any time.
@@ -37,7 +41,12 @@ const MAX_FINALITY_WATCHERS: usize = 512;
 
 impl<H: hash::Hash + Eq + Debug, C: ChainApi> Default for Listener<H, C> {
 	fn default() -> Self {
-		Self { watchers: Default::default(), finality_watchers: Default::default() }
+		Self {
+			watchers: Default::default(),
+			finality_watchers: Default::default(),
+			queue_watchers: Default::default(),
+			enqueued: Default::default(),
+		}
 	}
 }
 
@@ -111,7 +120,18 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 		// Current transaction is the last one included.
 		let tx_index = txs.len() - 1;
 
-		self.fire(tx, |watcher| watcher.in_block(block_hash, tx_index));
+		if let Some(enqueued_at) = self.enqueued.remove(tx) {
+			// Executed txs do not need to be dropped when the enqueuing block is retracted.
+			if let Some(txs) = self.queue_watchers.get_mut(&enqueued_at) {
+				txs.retain(|hash| hash != tx);
+				if txs.is_empty() {
+					self.queue_watchers.remove(&enqueued_at);
+				}
+			}
+			self.fire(tx, |watcher| watcher.executed(block_hash, tx_index));
+		} else {
+			self.fire(tx, |watcher| watcher.in_block(block_hash, tx_index));
+		}
 
 		while self.finality_watchers.len() > MAX_FINALITY_WATCHERS {
 			if let Some((hash, txs)) = self.finality_watchers.pop_front() {
@@ -122,6 +142,28 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 		}
 	}
 
+	/// Transaction was pruned from the pool and stored in the storage queue, it is going to be
+	/// executed in one of the following blocks.
+	pub fn enqueued(&mut self, block_hash: BlockHash<C>, tx: &H) {
+		debug!(target: LOG_TARGET, "[{:?}] Enqueued at {:?}", tx, block_hash);
+		let txs = self.queue_watchers.entry(block_hash).or_insert(vec![]);
+		txs.push(tx.clone());
+		let tx_index = txs.len() - 1;
+		self.enqueued.insert(tx.clone(), block_hash);
+
+		self.fire(tx, |watcher| watcher.enqueued(block_hash, tx_index));
+
+		while self.queue_watchers.len() > MAX_FINALITY_WATCHERS {
+			if let Some((hash, txs)) = self.queue_watchers.pop_front() {
+				for tx in txs {
+					if self.enqueued.remove(&tx).is_some() {
+						self.fire(&tx, |watcher| watcher.finality_timeout(hash));
+					}
+				}
+			}
+		}
+	}
+
 	/// The block this transaction was included in has been retracted.
 	pub fn retracted(&mut self, block_hash: BlockHash<C>) {
 		if let Some(hashes) = self.finality_watchers.remove(&block_hash) {
@@ -129,6 +171,15 @@ impl<H: hash::Hash + traits::Member + Serialize, C: ChainApi> Listener<H, C> {
 				self.fire(&hash, |watcher| watcher.retracted(block_hash))
 			}
 		}
+
+		if let Some(hashes) = self.queue_watchers.remove(&block_hash) {
+			for hash in hashes {
+				if self.enqueued.get(&hash) == Some(&block_hash) {
+					self.enqueued.remove(&hash);
+					self.fire(&hash, |watcher| watcher.dropped_from_queue(block_hash))
+				}
+			}
+		}
 	}
 
 	/// Notify all watchers that transactions have been finalized
@@ -238,6 +238,26 @@ impl<B: ChainApi> Pool<B> {
 		self.validated_pool.fire_pruned(at, pruned_transactions)
 	}
 
+	/// Prunes known ready transactions that were stored in the storage queue.
+	///
+	/// Works exactly as `prune_known`, but listeners are notified that transactions were
+	/// enqueued (not executed yet) rather than included in the block.
+	pub fn prune_enqueued(
+		&self,
+		at: &BlockId<B::Block>,
+		hashes: &[ExtrinsicHash<B>],
+	) -> Result<(), B::Error> {
+		// Get details of all extrinsics that are already in the pool
+		let in_pool_tags =
+			self.validated_pool.extrinsics_tags(hashes).into_iter().flatten().flatten();
+
+		// Prune all transactions that provide given tags
+		let prune_status = self.validated_pool.prune_tags(in_pool_tags)?;
+		let pruned_transactions =
+			hashes.iter().cloned().chain(prune_status.pruned.iter().map(|tx| tx.hash));
+		self.validated_pool.fire_enqueued(at, pruned_transactions)
+	}
+
 	/// Prunes ready transactions.
 	///
 	/// Used to clear the pool from transactions that were part of recently imported block.
@@ -358,6 +378,25 @@ impl<B: ChainApi> Pool<B> {
 		)
 	}
 
+	/// Revalidates all transactions waiting in the future queue.
+	///
+	/// Nonces of txs stored in the storage queue are taken into account by the runtime when
+	/// validating txs, even though enqueued txs are not executed yet. Txs waiting for the enqueued
+	/// ones may become ready even if tags they require have never been provided by txs in the pool
+	/// (e.g. enqueued txs were submitted to other nodes), so after block that enqueued txs is
+	/// imported future txs need to be re-tagged.
+	pub async fn revalidate_future(&self, at: &BlockId<B::Block>) -> Result<(), B::Error> {
+		let futures = self.validated_pool.futures_with_source();
+		if futures.is_empty() {
+			return Ok(())
+		}
+
+		log::debug!(target: LOG_TARGET, "Revalidating {} future txs at {:?}", futures.len(), at);
+		let revalidated = self.verify(at, futures, CheckBannedBeforeVerify::No).await?;
+		self.validated_pool.resubmit(revalidated);
+		Ok(())
+	}
+
 	/// Returns transaction hash
 	pub fn hash_of(&self, xt: &ExtrinsicFor<B>) -> ExtrinsicHash<B> {
 		self.validated_pool.api().hash_and_length(xt).0
@@ -660,6 +699,33 @@ mod tests {
 		assert!(pool.validated_pool.is_banned(&hash1));
 	}
 
+	#[test]
+	fn should_promote_future_transactions_after_revalidation() {
+		// given
+		let pool = pool();
+		let hash = block_on(pool.submit_one(
+			&BlockId::Number(0),
+			SOURCE,
+			uxt(Transfer {
+				from: AccountId::from_h256(H256::from_low_u64_be(1)),
+				to: AccountId::from_h256(H256::from_low_u64_be(2)),
+				amount: 5,
+				nonce: 1,
+			}),
+		))
+		.unwrap();
+		assert_eq!(pool.validated_pool().status().future, 1);
+
+		// when
+		// tx with nonce 0 has been enqueued elsewhere, the runtime does not require it anymore
+		pool.validated_pool.api().clear_requirements.lock().insert(hash);
+		block_on(pool.revalidate_future(&BlockId::Number(0))).unwrap();
+
+		// then
+		assert_eq!(pool.validated_pool().status().future, 0);
+		assert_eq!(pool.validated_pool().ready().map(|v| v.hash).collect::<Vec<_>>(), vec![hash]);
+	}
+
 	#[test]
 	fn should_limit_futures() {
 		// given
@@ -820,6 +886,76 @@ mod tests {
 			);
 		}
 
+		#[test]
+		fn should_trigger_enqueued_and_executed() {
+			// given
+			let pool = pool();
+			let watcher = block_on(pool.submit_and_watch(
+				&BlockId::Number(0),
+				SOURCE,
+				uxt(Transfer {
+					from: AccountId::from_h256(H256::from_low_u64_be(1)),
+					to: AccountId::from_h256(H256::from_low_u64_be(2)),
+					amount: 5,
+					nonce: 0,
+				}),
+			))
+			.unwrap();
+			let hash = *watcher.hash();
+
+			// when
+			pool.prune_enqueued(&BlockId::Number(2), &[hash]).unwrap();
+			assert_eq!(pool.validated_pool().status().ready, 0);
+			pool.prune_known(&BlockId::Number(3), &[hash]).unwrap();
+
+			// then
+			let mut stream = futures::executor::block_on_stream(watcher.into_stream());
+			assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+			assert_eq!(
+				stream.next(),
+				Some(TransactionStatus::Enqueued((H256::from_low_u64_be(2).into(), 0))),
+			);
+			assert_eq!(
+				stream.next(),
+				Some(TransactionStatus::Executed((H256::from_low_u64_be(3).into(), 0))),
+			);
+		}
+
+		#[test]
+		fn should_trigger_dropped_from_queue_when_enqueuing_block_is_retracted() {
+			// given
+			let pool = pool();
+			let watcher = block_on(pool.submit_and_watch(
+				&BlockId::Number(0),
+				SOURCE,
+				uxt(Transfer {
+					from: AccountId::from_h256(H256::from_low_u64_be(1)),
+					to: AccountId::from_h256(H256::from_low_u64_be(2)),
+					amount: 5,
+					nonce: 0,
+				}),
+			))
+			.unwrap();
+			let hash = *watcher.hash();
+
+			// when
+			pool.prune_enqueued(&BlockId::Number(2), &[hash]).unwrap();
+			pool.validated_pool().on_block_retracted(H256::from_low_u64_be(2).into());
+
+			// then
+			let mut stream = futures::executor::block_on_stream(watcher.into_stream());
+			assert_eq!(stream.next(), Some(TransactionStatus::Ready));
+			assert_eq!(
+				stream.next(),
+				Some(TransactionStatus::Enqueued((H256::from_low_u64_be(2).into(), 0))),
+			);
+			assert_eq!(
+				stream.next(),
+				Some(TransactionStatus::DroppedFromQueue(H256::from_low_u64_be(2).into())),
+			);
+			assert_eq!(stream.next(), None);
+		}
+
 		#[test]
 		fn should_trigger_future_and_ready_after_promoted() {
 			// given
@@ -524,6 +524,27 @@ impl<B: ChainApi> ValidatedPool<B> {
 		Ok(())
 	}
 
+	/// Fire notifications for transactions stored in the storage queue.
+	pub fn fire_enqueued(
+		&self,
+		at: &BlockId<B::Block>,
+		hashes: impl Iterator<Item = ExtrinsicHash<B>>,
+	) -> Result<(), B::Error> {
+		let header_hash = self
+			.api
+			.block_id_to_hash(at)?
+			.ok_or_else(|| error::Error::InvalidBlockId(format!("{:?}", at)))?;
+		let mut listener = self.listener.write();
+		let mut set = HashSet::with_capacity(hashes.size_hint().0);
+		for h in hashes {
+			if !set.contains(&h) {
+				listener.enqueued(header_hash, &h);
+				set.insert(h);
+			}
+		}
+		Ok(())
+	}
+
 	/// Removes stale transactions from the pool.
 	///
 	/// Stale transactions are transaction beyond their longevity period.
@@ -625,6 +646,11 @@ impl<B: ChainApi> ValidatedPool<B> {
 		self.pool.read().futures().map(|tx| (tx.hash, tx.data.clone())).collect()
 	}
 
+	/// Returns a Vec of sources and extrinsics in the future pool.
+	pub fn futures_with_source(&self) -> Vec<(TransactionSource, ExtrinsicFor<B>)> {
+		self.pool.read().futures().map(|tx| (tx.source, tx.data.clone())).collect()
+	}
+
 	/// Returns pool status.
 	pub fn status(&self) -> PoolStatus {
 		self.pool.read().status()
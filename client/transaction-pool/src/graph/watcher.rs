@@ -88,6 +88,23 @@ impl<H: Clone, BH: Clone> Sender<H, BH> {
 		self.send(TransactionStatus::InBlock((hash, index)));
 	}
 
+	/// Extrinsic has been stored in the storage queue by block with given hash.
+	pub fn enqueued(&mut self, hash: BH, index: usize) {
+		self.send(TransactionStatus::Enqueued((hash, index)));
+	}
+
+	/// Enqueued extrinsic has been executed in block with given hash.
+	pub fn executed(&mut self, hash: BH, index: usize) {
+		self.send(TransactionStatus::Executed((hash, index)));
+	}
+
+	/// The block that enqueued this extrinsic has been retracted.
+	pub fn dropped_from_queue(&mut self, hash: BH) {
+		self.send(TransactionStatus::DroppedFromQueue(hash));
+		// the extrinsic is not returned to the pool, so there are no more notifications
+		self.is_finalized = true;
+	}
+
 	/// Extrinsic has been finalized by a finality gadget.
 	pub fn finalized(&mut self, hash: BH, index: usize) {
 		self.send(TransactionStatus::Finalized((hash, index)));
@@ -571,6 +571,7 @@ async fn prune_known_txs_for_block<Block: BlockT, Api: graph::ChainApi<Block = B
 		},
 	};
 
+	let mut enqueued_any = false;
 	if let Some(digest) = header
 		.digest()
 		.logs()
@@ -579,12 +580,13 @@ async fn prune_known_txs_for_block<Block: BlockT, Api: graph::ChainApi<Block = B
 	{
 		if let sp_runtime::DigestItem::Other(bytes) = digest {
 			let enqueued_hashes = Vec::<Block::Hash>::decode(&mut bytes.as_ref()).unwrap();
+			enqueued_any = !enqueued_hashes.is_empty();
 
 			enqueued_hashes.iter().for_each(
 				|hash| log::debug!(target: "txpool", "found enqueued tx in the log {}", hash),
 			);
 
-			if let Err(e) = pool.prune_known(&BlockId::Hash(block_hash), &enqueued_hashes) {
+			if let Err(e) = pool.prune_enqueued(&BlockId::Hash(block_hash), &enqueued_hashes) {
 				log::error!("Cannot prune known in the pool: {}", e);
 			}
 		}
@@ -597,6 +599,14 @@ async fn prune_known_txs_for_block<Block: BlockT, Api: graph::ChainApi<Block = B
 		log::error!("Cannot prune known in the pool: {}", e);
 	}
 
+	// nonces of enqueued txs are already accounted for by the runtime, txs waiting for them may
+	// be ready now
+	if enqueued_any {
+		if let Err(e) = pool.revalidate_future(&BlockId::Hash(block_hash)).await {
+			log::error!("Cannot revalidate future txs in the pool: {}", e);
+		}
+	}
+
 	hashes
 }
 
@@ -75,6 +75,9 @@ impl frame_system::Config for Test {
 	type SS58Prefix = ();
 	type OnSetCode = ();
 	type MaxConsumers = ConstU32<2>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = ();
 }
 
 impl pallet_balances::Config for Test {
@@ -50,6 +50,9 @@ impl frame_system::Config for Test {
 	type SS58Prefix = ();
 	type OnSetCode = ();
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = ();
 }
 
 impl pallet_balances::Config for Test {
@@ -147,6 +147,9 @@ mod tests {
 		type SS58Prefix = ();
 		type OnSetCode = ();
 		type MaxConsumers = ConstU32<16>;
+		type ShuffleStrategy = ();
+		type StorageQueueLimits = ();
+		type EnqueuedTxsValidator = ();
 	}
 
 	impl pallet::Config for Test {
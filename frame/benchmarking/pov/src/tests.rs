@@ -203,6 +203,9 @@ mod mock {
 		type SS58Prefix = ();
 		type OnSetCode = ();
 		type MaxConsumers = frame_support::traits::ConstU32<16>;
+		type ShuffleStrategy = ();
+		type StorageQueueLimits = ();
+		type EnqueuedTxsValidator = ();
 	}
 
 	impl crate::Config for Test {
@@ -155,6 +155,9 @@ pub mod mock {
 		type SS58Prefix = ();
 		type OnSetCode = ();
 		type MaxConsumers = frame_support::traits::ConstU32<16>;
+		type ShuffleStrategy = ();
+		type StorageQueueLimits = ();
+		type EnqueuedTxsValidator = ();
 	}
 
 	impl super::Config for Test {}
@@ -231,6 +231,9 @@ mod tests {
 		type SystemWeightInfo = ();
 		type OnSetCode = ();
 		type MaxConsumers = frame_support::traits::ConstU32<16>;
+		type ShuffleStrategy = ();
+		type StorageQueueLimits = ();
+		type EnqueuedTxsValidator = ();
 	}
 
 	struct PhragmenParams;
@@ -130,7 +130,6 @@ use frame_support::{
 	},
 	weights::Weight,
 };
-use schnorrkel::vrf::{VRFOutput, VRFProof};
 use sp_runtime::{
 	generic::Digest,
 	traits::{
@@ -267,6 +266,47 @@ where
 	}
 }
 
+impl<
+		System: frame_system::Config + EnsureInherentsAreFirst<Block>,
+		Block: traits::Block<Header = System::Header, Hash = System::Hash>,
+		Context: Default,
+		UnsignedValidator,
+		AllPalletsWithSystem: OnRuntimeUpgrade
+			+ OnInitialize<System::BlockNumber>
+			+ OnIdle<System::BlockNumber>
+			+ OnFinalize<System::BlockNumber>
+			+ OffchainWorker<System::BlockNumber>,
+		COnRuntimeUpgrade: OnRuntimeUpgrade,
+	> frame_system::EnqueuedTxsValidator<System>
+	for Executive<System, Block, Context, UnsignedValidator, AllPalletsWithSystem, COnRuntimeUpgrade>
+where
+	<System as frame_system::Config>::BlockNumber: AtLeast32BitUnsigned,
+	Block::Extrinsic: IdentifyAccountWithLookup<Context, AccountId = System::AccountId>
+		+ Checkable<Context>
+		+ Codec
+		+ GetDispatchInfo,
+	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
+	CallOf<Block::Extrinsic, Context>:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
+	UnsignedValidator: ValidateUnsigned<Call = CallOf<Block::Extrinsic, Context>>,
+{
+	/// Runs the same checks as the transaction pool does (signature, fees, nonce, ...) against
+	/// the state of the block that enqueues `tx`.
+	fn validate_enqueued_tx(tx: &[u8]) -> Result<(), TransactionValidityError> {
+		let uxt = Block::Extrinsic::decode(&mut &*tx).map_err(|_| InvalidTransaction::Call)?;
+		let xt = uxt.check(&Default::default())?;
+		let dispatch_info = xt.get_dispatch_info();
+
+		if dispatch_info.class == DispatchClass::Mandatory {
+			return Err(InvalidTransaction::MandatoryValidation.into())
+		}
+
+		xt.validate::<UnsignedValidator>(TransactionSource::InBlock, &dispatch_info, tx.len())
+			.map(|_| ())
+	}
+}
+
 #[cfg(feature = "try-runtime")]
 impl<
 		System: frame_system::Config + EnsureInherentsAreFirst<Block>,
@@ -320,29 +360,8 @@ where
 
 		let (header, extrinsics) = block.deconstruct();
 
-		let try_apply_extrinsic = |uxt: Block::Extrinsic| -> ApplyExtrinsicResult {
-			sp_io::init_tracing();
-			let encoded = uxt.encode();
-			let encoded_len = encoded.len();
-
-			// skip signature verification.
-			let xt = if signature_check {
-				uxt.check(&Default::default())
-			} else {
-				uxt.unchecked_into_checked_i_know_what_i_am_doing(&Default::default())
-			}?;
-			<frame_system::Pallet<System>>::note_extrinsic(encoded);
-
-			let dispatch_info = xt.get_dispatch_info();
-			let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
-
-			<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
-
-			Ok(r.map(|_| ()).map_err(|e| e.error))
-		};
-
 		for e in extrinsics {
-			if let Err(err) = try_apply_extrinsic(e.clone()) {
+			if let Err(err) = Self::try_apply_extrinsic(e.clone(), signature_check) {
 				frame_support::log::error!(
 					target: "runtime::executive", "executing transaction {:?} failed due to {:?}. Aborting the rest of the block execution.",
 					e,
@@ -352,6 +371,162 @@ where
 			}
 		}
 
+		Self::try_finalize_block(header, state_root_check, select)
+	}
+
+	/// Execute given VER block, but don't as strict is the normal block execution.
+	///
+	/// Follows [`Self::execute_block_ver_impl`]: the shuffling seed is verified against `public`
+	/// (the block author key), txs popped from the storage queue need to match signed txs from
+	/// the block body and are executed right before the inherent that enqueues new txs. Some checks
+	/// can be disabled via:
+	///
+	/// - `state_root_check`
+	/// - `signature_check`
+	///
+	/// Should only be used for testing ONLY.
+	pub fn try_execute_block_ver(
+		block: Block,
+		public: Vec<u8>,
+		state_root_check: bool,
+		signature_check: bool,
+		select: frame_try_runtime::TryStateSelect,
+	) -> Result<Weight, &'static str> {
+		frame_support::log::info!(
+			target: "frame::executive",
+			"try-runtime: executing VER block #{:?} / state root check: {:?} / signature check: {:?} / try-state-select: {:?}",
+			block.header().number(),
+			state_root_check,
+			signature_check,
+			select,
+		);
+
+		Self::initialize_block(block.header());
+		Self::ver_checks(&block, public);
+		Self::store_seed(&block.header().seed().seed);
+		Self::initial_checks(&block);
+
+		let popped_txs_count: usize = (*block.header().count()).saturated_into();
+		let popped_elems = Self::pop_txs(popped_txs_count);
+		if popped_elems.len() != popped_txs_count {
+			return Err("not enough elements to pop found")
+		}
+		let popped_txs = popped_elems
+			.into_iter()
+			.filter_map(|tx_data| Block::Extrinsic::decode(&mut tx_data.as_slice()).ok())
+			.collect::<Vec<_>>();
+
+		let (header, extrinsics) = block.deconstruct();
+		let (mut inherents, signed): (Vec<_>, Vec<_>) =
+			extrinsics.into_iter().partition(|e| !e.is_signed().unwrap_or(false));
+		if popped_txs != signed {
+			return Err("txs from the block body do not match txs popped from the storage queue")
+		}
+
+		// the last inherent enqueues new txs, popped txs are executed right before it
+		let enqueue_inherent = inherents.split_off(inherents.len().saturating_sub(1));
+		let enqueued_blocks_count_before = <frame_system::Pallet<System>>::enqueued_blocks_count();
+		for e in inherents.into_iter().chain(popped_txs).chain(enqueue_inherent) {
+			let is_signed = e.is_signed().unwrap_or(false);
+			match Self::try_apply_extrinsic(e.clone(), signature_check) {
+				// delayed execution of signed txs may fail, that does not invalidate the block
+				Err(TransactionValidityError::Invalid(err))
+					if is_signed && !err.exhausted_resources() =>
+				{
+					frame_support::log::debug!(
+						target: "runtime::executive",
+						"executing enqueued transaction {:?} failed due to {:?}",
+						e,
+						err,
+					);
+				},
+				Err(err) => {
+					frame_support::log::error!(
+						target: "runtime::executive",
+						"executing transaction {:?} failed due to {:?}",
+						e,
+						err,
+					);
+					return Err(err.into())
+				},
+				Ok(_) => {},
+			}
+		}
+
+		let enqueued_blocks_count_after = <frame_system::Pallet<System>>::enqueued_blocks_count();
+		if enqueued_blocks_count_before != 0 &&
+			popped_txs_count == 0 &&
+			enqueued_blocks_count_before != enqueued_blocks_count_after
+		{
+			return Err("Collator didnt execute enqueued txs")
+		}
+
+		if let Some((nr, _, txs)) = frame_system::StorageQueue::<System>::get().last() {
+			// check txs added in the current block, as it is done by `execute_block_ver_impl`
+			if *nr == frame_system::Pallet::<System>::block_number() {
+				if txs.iter().collect::<BTreeSet<_>>().len() != txs.len() {
+					return Err("only unique txs can be passed into queue")
+				}
+
+				let queue_len =
+					frame_system::StorageQueue::<System>::decode_len().unwrap_or_default();
+				if queue_len as u32 > <frame_system::Pallet<System>>::storage_queue_limits().depth {
+					return Err("storage queue is full")
+				}
+				<frame_system::Pallet<System>>::ensure_enqueue_limits(txs)
+					.map_err(|_| "enqueued txs exceed storage queue limits")?;
+
+				let max = System::BlockWeights::get();
+				let mut all: frame_system::ConsumedWeight = Default::default();
+				for (_, tx_data) in txs.iter() {
+					let tx = Block::Extrinsic::decode(&mut tx_data.as_slice())
+						.map_err(|_| "cannot deserialize tx that has been just enqueued")?;
+					let info = tx.get_dispatch_info();
+					if signature_check {
+						tx.check(&Default::default())
+							.map_err(|_| "incoming tx needs to be properly signed")?;
+					}
+					all = frame_system::calculate_consumed_weight::<
+						CallOf<Block::Extrinsic, Context>,
+					>(max.clone(), all, &info)
+					.map_err(|_| "Transaction would exhaust the block limits")?;
+				}
+			}
+		}
+
+		Self::try_finalize_block(header, state_root_check, select)
+	}
+
+	/// Apply extrinsic the same way [`Self::apply_extrinsic`] does, optionally skipping the
+	/// signature verification.
+	fn try_apply_extrinsic(uxt: Block::Extrinsic, signature_check: bool) -> ApplyExtrinsicResult {
+		sp_io::init_tracing();
+		let encoded = uxt.encode();
+		let encoded_len = encoded.len();
+
+		// skip signature verification.
+		let xt = if signature_check {
+			uxt.check(&Default::default())
+		} else {
+			uxt.unchecked_into_checked_i_know_what_i_am_doing(&Default::default())
+		}?;
+		<frame_system::Pallet<System>>::note_extrinsic(encoded);
+
+		let dispatch_info = xt.get_dispatch_info();
+		let r = Applyable::apply::<UnsignedValidator>(xt, &dispatch_info, encoded_len)?;
+
+		<frame_system::Pallet<System>>::note_applied_extrinsic(&r, dispatch_info);
+
+		Ok(r.map(|_| ()).map_err(|e| e.error))
+	}
+
+	/// Finish the block executed by [`Self::try_execute_block`] or
+	/// [`Self::try_execute_block_ver`], run the try-state checks and verify the header.
+	fn try_finalize_block(
+		header: Block::Header,
+		state_root_check: bool,
+		select: frame_try_runtime::TryStateSelect,
+	) -> Result<Weight, &'static str> {
 		// post-extrinsics book-keeping
 		<frame_system::Pallet<System>>::note_finished_extrinsics();
 		Self::idle_and_finalize_hook(*header.number());
@@ -504,6 +679,36 @@ where
 		frame_system::Pallet::<System>::note_finished_initialize();
 	}
 
+	/// Stores the shuffling seed of the current block and shuffles txs enqueued by the previous
+	/// block, see [`frame_system::Pallet::set_block_seed`].
+	///
+	/// Registers the weight of the shuffling in `BlockWeight`. Needs to be used for both block
+	/// production and block execution, so that both account the same weight.
+	pub fn store_seed(seed: &sp_core::H256) {
+		let shuffled = <frame_system::Pallet<System>>::set_block_seed(seed);
+		<frame_system::Pallet<System>>::register_extra_weight_unchecked(
+			<System::SystemWeightInfo as frame_system::WeightInfo>::set_block_seed(
+				shuffled.saturated_into(),
+			),
+			DispatchClass::Mandatory,
+		);
+	}
+
+	/// Pops `count` txs from the storage queue, see [`frame_system::Pallet::pop_txs`].
+	///
+	/// Registers the weight of the storage queue update in `BlockWeight`. Needs to be used for
+	/// both block production and block execution, so that both account the same weight.
+	pub fn pop_txs(count: usize) -> Vec<Vec<u8>> {
+		let popped = <frame_system::Pallet<System>>::pop_txs(count);
+		<frame_system::Pallet<System>>::register_extra_weight_unchecked(
+			<System::SystemWeightInfo as frame_system::WeightInfo>::pop_txs(
+				popped.len().saturated_into(),
+			),
+			DispatchClass::Mandatory,
+		);
+		popped
+	}
+
 	/// Returns if the runtime was upgraded since the last time this function was called.
 	fn runtime_upgraded() -> bool {
 		let last = frame_system::LastRuntimeUpgrade::<System>::get();
@@ -520,24 +725,14 @@ where
 	}
 
 	fn ver_checks(block: &Block, public_key: Vec<u8>) {
-		// Check that `parent_hash` is correct.
 		sp_tracing::enter_span!(sp_tracing::Level::TRACE, "ver checks");
-		let header = block.header();
-		// Check that shuffling seedght is generated properly
-		let new_seed = VRFOutput::from_bytes(&header.seed().seed.as_bytes())
-			.expect("cannot parse shuffling seed");
-
-		let proof = VRFProof::from_bytes(&header.seed().proof.as_bytes())
-			.expect("cannot parse shuffling seed proof");
+		// Check that shuffling seed is generated properly
 		let prev_seed = <frame_system::Pallet<System>>::block_seed();
-
-		let mut transcript = merlin::Transcript::new(b"shuffling_seed");
-		transcript.append_message(b"prev_seed", prev_seed.as_bytes());
-
-		let pub_key = schnorrkel::PublicKey::from_bytes(&public_key).expect("cannot build public");
-		pub_key
-			.vrf_verify(transcript, &new_seed, &proof)
-			.expect("shuffling seed verification failed");
+		if let Err(e) =
+			sp_ver::verify_shuffling_seed(&public_key, &prev_seed, block.header().seed())
+		{
+			panic!("shuffling seed verification failed: {:?}", e);
+		}
 	}
 
 	fn initial_checks(block: &Block) {
@@ -602,13 +797,13 @@ where
 
 			// any initial checks
 			Self::ver_checks(&block, public);
-			<frame_system::Pallet<System>>::set_block_seed(&block.header().seed().seed);
+			Self::store_seed(&block.header().seed().seed);
 			Self::initial_checks(&block);
 
 			let signature_batching = sp_runtime::SignatureBatching::start();
 
 			let poped_txs_count = *block.header().count();
-			let popped_elems = <frame_system::Pallet<System>>::pop_txs(poped_txs_count.saturated_into());
+			let popped_elems = Self::pop_txs(poped_txs_count.saturated_into());
 
 			assert_eq!(popped_elems.len(), poped_txs_count.saturated_into::<usize>(), "not enought elements to pop found");
 
@@ -650,6 +845,10 @@ where
 					let unique_tx_count = txs.iter().collect::<BTreeSet<_>>().len();
 					assert!(unique_tx_count == txs.len(), "only unique txs can be passed into queue");
 
+					let queue_len = frame_system::StorageQueue::<System>::decode_len().unwrap_or_default();
+					assert!(queue_len as u32 <= <frame_system::Pallet<System>>::storage_queue_limits().depth, "storage queue is full");
+					<frame_system::Pallet<System>>::ensure_enqueue_limits(txs).expect("enqueued txs exceed storage queue limits");
+
 					for t in txs.iter()
 						.map(|(_who, tx_data)| Block::Extrinsic::decode(& mut tx_data.as_slice()).expect("cannot deserialize tx that has been just enqueued"))
 						.collect::<Vec<_>>()
@@ -1077,10 +1276,55 @@ mod tests {
 		type AccountData = pallet_balances::AccountData<Balance>;
 		type OnNewAccount = ();
 		type OnKilledAccount = ();
-		type SystemWeightInfo = ();
+		type SystemWeightInfo = MockSystemWeightInfo;
 		type SS58Prefix = ();
 		type OnSetCode = ();
 		type MaxConsumers = ConstU32<16>;
+		type ShuffleStrategy = ();
+		type StorageQueueLimits = ();
+		type EnqueuedTxsValidator = ();
+	}
+
+	parameter_types! {
+		/// Weight of a single tx enqueued, shuffled or popped from the storage queue. Zero by
+		/// default, so that the state roots of the VER blocks used in tests stay the same.
+		pub static StorageQueueTxWeight: Weight = Weight::zero();
+	}
+
+	/// `frame_system` weights, storage queue bookkeeping is charged with
+	/// [`StorageQueueTxWeight`] per tx.
+	pub struct MockSystemWeightInfo;
+	impl frame_system::WeightInfo for MockSystemWeightInfo {
+		fn remark(b: u32) -> Weight {
+			<() as frame_system::WeightInfo>::remark(b)
+		}
+		fn remark_with_event(b: u32) -> Weight {
+			<() as frame_system::WeightInfo>::remark_with_event(b)
+		}
+		fn set_heap_pages() -> Weight {
+			<() as frame_system::WeightInfo>::set_heap_pages()
+		}
+		fn set_storage(i: u32) -> Weight {
+			<() as frame_system::WeightInfo>::set_storage(i)
+		}
+		fn kill_storage(i: u32) -> Weight {
+			<() as frame_system::WeightInfo>::kill_storage(i)
+		}
+		fn kill_prefix(p: u32) -> Weight {
+			<() as frame_system::WeightInfo>::kill_prefix(p)
+		}
+		fn set_storage_queue_limits() -> Weight {
+			<() as frame_system::WeightInfo>::set_storage_queue_limits()
+		}
+		fn enqueue_txs(t: u32, _b: u32) -> Weight {
+			StorageQueueTxWeight::get().saturating_mul(t.into())
+		}
+		fn set_block_seed(t: u32) -> Weight {
+			StorageQueueTxWeight::get().saturating_mul(t.into())
+		}
+		fn pop_txs(t: u32) -> Weight {
+			StorageQueueTxWeight::get().saturating_mul(t.into())
+		}
 	}
 
 	type Balance = u64;
@@ -1467,6 +1711,54 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn enqueued_txs_are_prevalidated() {
+		let validate = |tx: &[u8]| {
+			<Executive as frame_system::EnqueuedTxsValidator<Runtime>>::validate_enqueued_tx(tx)
+		};
+		let valid = TestXt::new(call_transfer(2, 69), sign_extra(1, 1, 0));
+		let stale = TestXt::new(call_transfer(2, 69), sign_extra(1, 0, 0));
+		let unpayable = TestXt::new(call_transfer(1, 69), sign_extra(2, 0, 0));
+		let mut t = new_test_ext(1);
+
+		t.execute_with(|| {
+			frame_system::Account::<Runtime>::mutate(1, |account| account.nonce = 1);
+
+			assert_eq!(validate(&valid.encode()), Ok(()));
+			assert_eq!(validate(&stale.encode()), Err(InvalidTransaction::Stale.into()));
+			assert_eq!(validate(&unpayable.encode()), Err(InvalidTransaction::Payment.into()));
+			assert_eq!(validate(&[0xff]), Err(InvalidTransaction::Call.into()));
+		});
+	}
+
+	#[test]
+	fn storage_queue_bookkeeping_is_registered_in_block_weight() {
+		StorageQueueTxWeight::set(Weight::from_parts(7, 0));
+		new_test_ext(1).execute_with(|| {
+			let enqueue_txs =
+				RuntimeCall::System(SystemCall::enqueue_txs { txs: vec![(None, vec![1, 2])] });
+			assert_eq!(enqueue_txs.get_dispatch_info().weight, Weight::from_parts(7, 0));
+
+			System::set_block_number(1);
+			System::store_txs(vec![(None, vec![1]), (None, vec![2]), (None, vec![3])]);
+			Executive::initialize_block(&Header::new(
+				2,
+				H256::default(),
+				H256::default(),
+				[69u8; 32].into(),
+				Digest::default(),
+			));
+			let mandatory = || *System::block_weight().get(DispatchClass::Mandatory);
+			let initial = mandatory();
+
+			Executive::store_seed(&H256::repeat_byte(1));
+			assert_eq!(mandatory(), initial + Weight::from_parts(21, 0));
+
+			assert_eq!(Executive::pop_txs(2).len(), 2);
+			assert_eq!(mandatory(), initial + Weight::from_parts(35, 0));
+		});
+	}
+
 	#[test]
 	fn can_pay_for_tx_fee_on_full_lock() {
 		let id: LockIdentifier = *b"0       ";
@@ -2480,6 +2772,70 @@ mod tests {
 		});
 	}
 
+	#[test]
+	#[should_panic(expected = "A call was labelled as mandatory, but resulted in an Error.")]
+	fn reject_block_that_enqueues_more_txs_than_allowed() {
+		new_test_ext(1).execute_with(|| {
+			frame_system::StorageQueueLimitsOverride::<Runtime>::put(
+				frame_system::limits::StorageQueueLimits {
+					depth: 2,
+					max_txs: 1,
+					max_len: u32::MAX,
+				},
+			);
+
+			let secret_uri = "//Alice";
+			let keystore = sp_keystore::testing::KeyStore::new();
+
+			let key_pair =
+				sr25519::Pair::from_string(secret_uri, None).expect("Generates key pair");
+			keystore
+				.insert_unknown(AURA, secret_uri, key_pair.public().as_ref())
+				.expect("Inserts unknown key");
+
+			let pub_key_bytes = AsRef::<[u8; 32]>::as_ref(&key_pair.public())
+				.iter()
+				.cloned()
+				.collect::<Vec<_>>();
+
+			let txs = vec![
+				TestXt::new(call_transfer(2, 69), sign_extra(1, 0, 0)),
+				TestXt::new(call_transfer(2, 69), sign_extra(1, 1, 0)),
+			];
+
+			let enqueue_txs_inherent = TestXt::new(
+				enqueue_txs(txs.clone().iter().map(|t| (Some(2), t.encode())).collect::<Vec<_>>()),
+				None,
+			);
+			let tx_hashes_list = txs
+				.clone()
+				.iter()
+				.map(|tx| <Runtime as frame_system::Config>::Hashing::hash(&tx.encode()[..]))
+				.collect::<Vec<_>>();
+
+			Executive::execute_block_ver(
+				Block {
+					header: Header {
+						parent_hash: System::parent_hash(),
+						number: 1,
+						state_root: Default::default(),
+						extrinsics_root: Default::default(),
+						digest: Digest { logs: vec![DigestItem::Other(tx_hashes_list.encode())] },
+						count: 0,
+						seed: calculate_next_seed_from_bytes(
+							&keystore,
+							&key_pair.public(),
+							System::block_seed().as_bytes().to_vec(),
+						)
+						.unwrap(),
+					},
+					extrinsics: vec![enqueue_txs_inherent],
+				},
+				pub_key_bytes.clone(),
+			);
+		});
+	}
+
 	#[test]
 	#[should_panic(expected = "enqueue_txs inherent can only be called once per block")]
 	fn reject_block_that_enqueus_same_tx_multiple_times() {
@@ -1110,6 +1110,9 @@ mod mock {
 		type SS58Prefix = SS58Prefix;
 		type OnSetCode = ();
 		type MaxConsumers = ConstU32<16>;
+		type ShuffleStrategy = ();
+		type StorageQueueLimits = ();
+		type EnqueuedTxsValidator = ();
 	}
 
 	parameter_types! {
@@ -0,0 +1,51 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the sudo pallet.
+//!
+//! Runtimes that include `pallet-sudo-mangata` implement the API with the audit log of the
+//! pallet:
+//!
+//! ```ignore
+//! impl SudoAuditApi<Block, AccountId, Hash, BlockNumber> for Runtime {
+//! 	fn audit_log() -> Vec<SudoAuditRecord<AccountId, Hash, BlockNumber>> {
+//! 		Sudo::audit_log()
+//! 	}
+//! }
+//! ```
+//!
+//! None of the runtimes in this repository include the pallet, the node template and the
+//! kitchensink runtime keep `pallet-sudo` so their chain specs stay compatible with upstream.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_sudo_mangata::{SudoAction, SudoAuditRecord};
+
+sp_api::decl_runtime_apis! {
+	pub trait SudoAuditApi<AccountId, Hash, BlockNumber> where
+		AccountId: Codec,
+		Hash: Codec,
+		BlockNumber: Codec,
+	{
+		/// The most recent sudo actions, from the oldest to the newest, see
+		/// `Pallet::audit_log`.
+		fn audit_log() -> Vec<SudoAuditRecord<AccountId, Hash, BlockNumber>>;
+	}
+}
@@ -27,6 +27,11 @@
 //! or designate a new account to replace them as the sudo key.
 //! Only one account can be the sudo key at a time.
 //!
+//! Optionally, the single sudo key can be replaced by several sudo keys. In this mode a `Root`
+//! call is executed only after its hash was announced by one of the keys, approved by
+//! `threshold` of them and the announcement delay has passed. Any key can cancel an announced
+//! call.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -36,6 +41,24 @@
 //! * `sudo` - Make a `Root` call to a dispatchable function.
 //! * `set_key` - Assign a new account to be the sudo key.
 //!
+//! In the multi-key mode only the sudo keys can call:
+//!
+//! * `announce` - Announce the hash of a call to be executed with `Root` origin.
+//! * `approve` - Approve an announced call.
+//! * `cancel` - Cancel an announced call.
+//! * `execute_announced` - Execute an announced call with enough approvals once the delay has
+//!   passed.
+//!
+//! `set_sudo_keys` requires `Root` origin and switches the pallet to the multi-key mode, it is
+//! expected to be dispatched through `sudo` by the single sudo key.
+//!
+//! ### Call Filter and Audit Log
+//!
+//! Calls dispatched by sudo are checked against `SudoCallFilter` first, calls it does not
+//! contain are not dispatched and fail with `CallFiltered`. Every dispatched or rejected call is
+//! recorded in a bounded audit log, see [`Pallet::audit_log`]. Records are chained by their
+//! `digest`, so a gap or a modified record can be detected by recomputing the digests.
+//!
 //! ## Usage
 //!
 //! ### Executing Privileged Functions
@@ -92,10 +115,21 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use sp_runtime::{traits::StaticLookup, DispatchResult};
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{Hash, Saturating, StaticLookup},
+	DispatchResult, RuntimeDebug,
+};
 use sp_std::prelude::*;
 
-use frame_support::{dispatch::GetDispatchInfo, traits::UnfilteredDispatchable};
+use frame_support::{
+	dispatch::{DispatchResultWithPostInfo, GetDispatchInfo},
+	ensure,
+	traits::{Contains, Get, UnfilteredDispatchable},
+	weights::Weight,
+	BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
 
 #[cfg(test)]
 mod mock;
@@ -120,6 +154,78 @@ pub use pallet::*;
 
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 
+/// A call announced by one of the sudo keys, see [`Pallet::announce`].
+#[derive(
+	Encode,
+	Decode,
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxApprovals))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, BlockNumber: MaxEncodedLen))]
+pub struct Announcement<AccountId, BlockNumber, MaxApprovals>
+where
+	AccountId: Clone + PartialEq + Eq + sp_std::fmt::Debug,
+	BlockNumber: Clone + PartialEq + Eq + sp_std::fmt::Debug,
+	MaxApprovals: Get<u32>,
+{
+	/// The key which announced the call.
+	pub announcer: AccountId,
+	/// The block from which the call can be executed.
+	pub executable_at: BlockNumber,
+	/// The keys which approved the call, including the announcer.
+	pub approvals: BoundedVec<AccountId, MaxApprovals>,
+}
+
+type AnnouncementOf<T> = Announcement<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::BlockNumber,
+	<T as Config>::MaxSudoKeys,
+>;
+
+/// The way a call was dispatched by sudo.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SudoAction<AccountId> {
+	/// Dispatched with `Root` origin by `sudo`.
+	Sudo,
+	/// Dispatched with `Root` origin by `sudo_unchecked_weight`.
+	SudoUncheckedWeight,
+	/// Dispatched with `Signed` origin of `who` by `sudo_as`.
+	SudoAs { who: AccountId },
+	/// Dispatched with `Root` origin by `execute_announced`.
+	ExecuteAnnounced,
+}
+
+/// A call dispatched by sudo, as stored in the audit log.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SudoAuditRecord<AccountId, Hash, BlockNumber> {
+	/// Sequence number of the record, the first record has index zero.
+	pub index: u64,
+	/// The block in which the call was dispatched.
+	pub block_number: BlockNumber,
+	/// The sudo key which dispatched the call.
+	pub sudoer: AccountId,
+	/// The way the call was dispatched.
+	pub action: SudoAction<AccountId>,
+	/// The hash of the dispatched call.
+	pub call_hash: Hash,
+	/// The result of the dispatched call.
+	pub result: DispatchResult,
+	/// Hash of `(previous digest, index, block_number, sudoer, action, call_hash, result)`, the
+	/// previous digest of the first record is the default hash.
+	pub digest: Hash,
+}
+
+type SudoAuditRecordOf<T> = SudoAuditRecord<
+	<T as frame_system::Config>::AccountId,
+	<T as frame_system::Config>::Hash,
+	<T as frame_system::Config>::BlockNumber,
+>;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::{DispatchResult, *};
@@ -135,6 +241,17 @@ pub mod pallet {
 		type RuntimeCall: Parameter
 			+ UnfilteredDispatchable<RuntimeOrigin = Self::RuntimeOrigin>
 			+ GetDispatchInfo;
+
+		/// The maximum number of sudo keys in the multi-key mode.
+		#[pallet::constant]
+		type MaxSudoKeys: Get<u32>;
+
+		/// Calls which can be dispatched by sudo, all other calls are rejected.
+		type SudoCallFilter: Contains<<Self as Config>::RuntimeCall>;
+
+		/// The number of most recent sudo actions kept in the audit log.
+		#[pallet::constant]
+		type MaxAuditLogEntries: Get<u32>;
 	}
 
 	#[pallet::pallet]
@@ -150,12 +267,13 @@ pub mod pallet {
 		/// - O(1).
 		/// - Limited storage reads.
 		/// - One DB write (event).
+		/// - Audit log DB changes.
 		/// - Weight of derivative `call` execution + 10,000.
 		/// # </weight>
 		#[pallet::call_index(0)]
 		#[pallet::weight({
 			let dispatch_info = call.get_dispatch_info();
-			(dispatch_info.weight, dispatch_info.class)
+			(dispatch_info.weight.saturating_add(Pallet::<T>::audit_log_weight()), dispatch_info.class)
 		})]
 		pub fn sudo(
 			origin: OriginFor<T>,
@@ -165,10 +283,10 @@ pub mod pallet {
 			let sender = ensure_signed(origin)?;
 			ensure!(Self::key().map_or(false, |k| sender == k), Error::<T>::RequireSudo);
 
-			let res = call.clone().dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
-			Self::deposit_event(Event::Sudid {
-				sudo_result: res.clone().map(|_| ()).map_err(|e| e.error),
-			});
+			let res = Self::dispatch_allowed(&call, frame_system::RawOrigin::Root.into());
+			let sudo_result = res.clone().map(|_| ()).map_err(|e| e.error);
+			Self::record_sudo_action(sender, SudoAction::Sudo, &call, sudo_result);
+			Self::deposit_event(Event::Sudid { sudo_result });
 			alert_log!(info, "A sudo action was performed: Call - {:?}, Result - {:?}!", call, res);
 			// Sudo user does not pay a fee.
 			Ok(Pays::No.into())
@@ -195,10 +313,10 @@ pub mod pallet {
 			let sender = ensure_signed(origin)?;
 			ensure!(Self::key().map_or(false, |k| sender == k), Error::<T>::RequireSudo);
 
-			let res = call.clone().dispatch_bypass_filter(frame_system::RawOrigin::Root.into());
-			Self::deposit_event(Event::Sudid {
-				sudo_result: res.clone().map(|_| ()).map_err(|e| e.error),
-			});
+			let res = Self::dispatch_allowed(&call, frame_system::RawOrigin::Root.into());
+			let sudo_result = res.clone().map(|_| ()).map_err(|e| e.error);
+			Self::record_sudo_action(sender, SudoAction::SudoUncheckedWeight, &call, sudo_result);
+			Self::deposit_event(Event::Sudid { sudo_result });
 			alert_log!(
 				info,
 				"A sudo action was performed with unchecked weight: Call - {:?}, Result - {:?}!",
@@ -254,7 +372,8 @@ pub mod pallet {
 			(
 				dispatch_info.weight
 					// AccountData for inner call origin accountdata.
-					.saturating_add(T::DbWeight::get().reads_writes(1, 1)),
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+					.saturating_add(Pallet::<T>::audit_log_weight()),
 				dispatch_info.class,
 			)
 		})]
@@ -269,13 +388,17 @@ pub mod pallet {
 
 			let who = T::Lookup::lookup(who)?;
 
-			let res = call
-				.clone()
-				.dispatch_bypass_filter(frame_system::RawOrigin::Signed(who.clone()).into());
+			let res =
+				Self::dispatch_allowed(&call, frame_system::RawOrigin::Signed(who.clone()).into());
+			let sudo_result = res.clone().map(|_| ()).map_err(|e| e.error);
+			Self::record_sudo_action(
+				sender,
+				SudoAction::SudoAs { who: who.clone() },
+				&call,
+				sudo_result,
+			);
 
-			Self::deposit_event(Event::SudoAsDone {
-				sudo_result: res.clone().map(|_| ()).map_err(|e| e.error),
-			});
+			Self::deposit_event(Event::SudoAsDone { sudo_result });
 			alert_log!(
 				info,
 				"A sudo_as action was performed: Who - {:?}, Call - {:?}, Result - {:?}!",
@@ -286,6 +409,219 @@ pub mod pallet {
 			// Sudo user does not pay a fee.
 			Ok(Pays::No.into())
 		}
+
+		/// Switch to the multi-key mode with the given sudo `keys`. The single sudo key is
+		/// removed.
+		///
+		/// An announced call is executable after `delay` blocks, once approved by `threshold`
+		/// of the `keys`.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// # <weight>
+		/// - O(K) where K is the number of keys.
+		/// - One storage read.
+		/// - Four DB changes.
+		/// # </weight>
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 4))]
+		pub fn set_sudo_keys(
+			origin: OriginFor<T>,
+			keys: Vec<T::AccountId>,
+			threshold: u32,
+			delay: T::BlockNumber,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(
+				threshold > 0 && threshold as usize <= keys.len(),
+				Error::<T>::InvalidThreshold
+			);
+			let mut sorted = keys.clone();
+			sorted.sort();
+			sorted.dedup();
+			ensure!(sorted.len() == keys.len(), Error::<T>::DuplicateSudoKey);
+			let bounded_keys: BoundedVec<T::AccountId, T::MaxSudoKeys> =
+				keys.clone().try_into().map_err(|_| Error::<T>::TooManySudoKeys)?;
+
+			if let Some(old_sudoer) = Key::<T>::take() {
+				Self::deposit_event(Event::KeyChanged { old_sudoer: Some(old_sudoer) });
+			}
+			SudoKeys::<T>::put(bounded_keys);
+			Threshold::<T>::put(threshold);
+			AnnouncementDelay::<T>::put(delay);
+
+			Self::deposit_event(Event::SudoKeysSet { keys: keys.clone(), threshold, delay });
+			alert_log!(
+				info,
+				"sudo keys were changed: Keys - {:?}, Threshold - {:?}, Delay - {:?}!",
+				keys,
+				threshold,
+				delay
+			);
+			Ok(())
+		}
+
+		/// Announce the hash of a call to be executed with `Root` origin. The announcement
+		/// counts as an approval of the sender.
+		///
+		/// The dispatch origin for this call must be _Signed_ by one of the sudo keys.
+		///
+		/// # <weight>
+		/// - O(K) where K is the number of keys.
+		/// - Limited storage reads.
+		/// - One DB write.
+		/// # </weight>
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(3, 1))]
+		pub fn announce(origin: OriginFor<T>, call_hash: T::Hash) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_sudo_key(&sender)?;
+			ensure!(!Announcements::<T>::contains_key(call_hash), Error::<T>::AlreadyAnnounced);
+
+			let executable_at = <frame_system::Pallet<T>>::block_number()
+				.saturating_add(AnnouncementDelay::<T>::get());
+			let mut approvals = BoundedVec::default();
+			approvals.try_push(sender.clone()).map_err(|_| Error::<T>::TooManySudoKeys)?;
+			Announcements::<T>::insert(
+				call_hash,
+				Announcement { announcer: sender.clone(), executable_at, approvals },
+			);
+
+			Self::deposit_event(Event::CallAnnounced {
+				call_hash,
+				announcer: sender.clone(),
+				executable_at,
+			});
+			alert_log!(
+				info,
+				"A sudo call was announced: Hash - {:?}, Announcer - {:?}, Executable at - {:?}!",
+				call_hash,
+				sender,
+				executable_at
+			);
+			// Sudo user does not pay a fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Approve an announced call.
+		///
+		/// The dispatch origin for this call must be _Signed_ by one of the sudo keys.
+		///
+		/// # <weight>
+		/// - O(K) where K is the number of keys.
+		/// - Limited storage reads.
+		/// - One DB write.
+		/// # </weight>
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+		pub fn approve(origin: OriginFor<T>, call_hash: T::Hash) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let keys = Self::ensure_sudo_key(&sender)?;
+
+			let approvals = Announcements::<T>::try_mutate(call_hash, |announcement| {
+				let announcement = announcement.as_mut().ok_or(Error::<T>::NotAnnounced)?;
+				ensure!(!announcement.approvals.contains(&sender), Error::<T>::AlreadyApproved);
+				// Approvals of keys removed in the meantime do not count anymore.
+				announcement.approvals.retain(|approver| keys.contains(approver));
+				announcement
+					.approvals
+					.try_push(sender.clone())
+					.map_err(|_| Error::<T>::TooManySudoKeys)?;
+				Ok::<_, Error<T>>(announcement.approvals.len() as u32)
+			})?;
+
+			Self::deposit_event(Event::CallApproved { call_hash, approver: sender, approvals });
+			// Sudo user does not pay a fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Cancel an announced call.
+		///
+		/// The dispatch origin for this call must be _Signed_ by one of the sudo keys, not
+		/// necessarily the one which announced the call.
+		///
+		/// # <weight>
+		/// - O(K) where K is the number of keys.
+		/// - Limited storage reads.
+		/// - One DB write.
+		/// # </weight>
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(2, 1))]
+		pub fn cancel(origin: OriginFor<T>, call_hash: T::Hash) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::ensure_sudo_key(&sender)?;
+			Announcements::<T>::take(call_hash).ok_or(Error::<T>::NotAnnounced)?;
+
+			Self::deposit_event(Event::AnnouncementCancelled {
+				call_hash,
+				canceller: sender.clone(),
+			});
+			alert_log!(
+				info,
+				"A sudo call announcement was cancelled: Hash - {:?}, Canceller - {:?}!",
+				call_hash,
+				sender
+			);
+			// Sudo user does not pay a fee.
+			Ok(Pays::No.into())
+		}
+
+		/// Dispatch an announced call with `Root` origin, once it has been approved by enough
+		/// sudo keys and the announcement delay has passed.
+		///
+		/// The dispatch origin for this call must be _Signed_ by one of the sudo keys.
+		///
+		/// # <weight>
+		/// - O(K) where K is the number of keys.
+		/// - Limited storage reads.
+		/// - One DB write and one DB write (event).
+		/// - Audit log DB changes.
+		/// - Weight of derivative `call` execution.
+		/// # </weight>
+		#[pallet::call_index(8)]
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				dispatch_info
+					.weight
+					.saturating_add(T::DbWeight::get().reads_writes(3, 1))
+					.saturating_add(Pallet::<T>::audit_log_weight()),
+				dispatch_info.class,
+			)
+		})]
+		pub fn execute_announced(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let keys = Self::ensure_sudo_key(&sender)?;
+
+			let call_hash = T::Hashing::hash_of(&call);
+			let announcement =
+				Announcements::<T>::get(call_hash).ok_or(Error::<T>::NotAnnounced)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= announcement.executable_at,
+				Error::<T>::NotYetExecutable
+			);
+			let approvals =
+				announcement.approvals.iter().filter(|approver| keys.contains(approver)).count()
+					as u32;
+			ensure!(approvals >= Threshold::<T>::get(), Error::<T>::NotEnoughApprovals);
+			Announcements::<T>::remove(call_hash);
+
+			let res = Self::dispatch_allowed(&call, frame_system::RawOrigin::Root.into());
+			let sudo_result = res.clone().map(|_| ()).map_err(|e| e.error);
+			Self::record_sudo_action(sender, SudoAction::ExecuteAnnounced, &call, sudo_result);
+			Self::deposit_event(Event::AnnouncedCallExecuted { call_hash, sudo_result });
+			alert_log!(
+				info,
+				"An announced sudo action was performed: Call - {:?}, Result - {:?}!",
+				call,
+				res
+			);
+			// Sudo user does not pay a fee.
+			Ok(Pays::No.into())
+		}
 	}
 
 	#[pallet::event]
@@ -297,6 +633,16 @@ pub mod pallet {
 		KeyChanged { old_sudoer: Option<T::AccountId> },
 		/// A sudo just took place. \[result\]
 		SudoAsDone { sudo_result: DispatchResult },
+		/// The multi-key mode was set up with new sudo keys.
+		SudoKeysSet { keys: Vec<T::AccountId>, threshold: u32, delay: T::BlockNumber },
+		/// A call was announced by one of the sudo keys.
+		CallAnnounced { call_hash: T::Hash, announcer: T::AccountId, executable_at: T::BlockNumber },
+		/// An announced call was approved, `approvals` is the current number of approvals.
+		CallApproved { call_hash: T::Hash, approver: T::AccountId, approvals: u32 },
+		/// An announced call was cancelled.
+		AnnouncementCancelled { call_hash: T::Hash, canceller: T::AccountId },
+		/// An announced call was dispatched with `Root` origin. \[result\]
+		AnnouncedCallExecuted { call_hash: T::Hash, sudo_result: DispatchResult },
 	}
 
 	#[pallet::error]
@@ -304,6 +650,24 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// Sender must be the Sudo account
 		RequireSudo,
+		/// More sudo keys than `MaxSudoKeys` were given
+		TooManySudoKeys,
+		/// The same sudo key was given more than once
+		DuplicateSudoKey,
+		/// Threshold must be at least one and at most the number of sudo keys
+		InvalidThreshold,
+		/// The call has already been announced
+		AlreadyAnnounced,
+		/// The call has not been announced
+		NotAnnounced,
+		/// The sender has already approved the call
+		AlreadyApproved,
+		/// The announcement delay of the call has not passed yet
+		NotYetExecutable,
+		/// The call has not been approved by enough sudo keys
+		NotEnoughApprovals,
+		/// The call is not allowed to be dispatched by sudo
+		CallFiltered,
 	}
 
 	/// The `AccountId` of the sudo key.
@@ -311,6 +675,41 @@ pub mod pallet {
 	#[pallet::getter(fn key)]
 	pub(super) type Key<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+	/// The sudo keys of the multi-key mode.
+	#[pallet::storage]
+	#[pallet::getter(fn sudo_keys)]
+	pub(super) type SudoKeys<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxSudoKeys>, ValueQuery>;
+
+	/// The number of sudo keys which need to approve an announced call.
+	#[pallet::storage]
+	#[pallet::getter(fn threshold)]
+	pub(super) type Threshold<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The number of blocks after which an announced call can be executed.
+	#[pallet::storage]
+	#[pallet::getter(fn announcement_delay)]
+	pub(super) type AnnouncementDelay<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// Calls announced by the sudo keys, by call hash.
+	#[pallet::storage]
+	#[pallet::getter(fn announcements)]
+	pub(super) type Announcements<T: Config> =
+		StorageMap<_, Identity, T::Hash, AnnouncementOf<T>, OptionQuery>;
+
+	/// The most recent sudo actions, by `index % MaxAuditLogEntries` of the record.
+	#[pallet::storage]
+	pub(super) type AuditLog<T: Config> =
+		StorageMap<_, Twox64Concat, u32, SudoAuditRecordOf<T>, OptionQuery>;
+
+	/// The number of sudo actions ever recorded in the audit log.
+	#[pallet::storage]
+	pub(super) type AuditLogCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// The digest of the most recent record of the audit log.
+	#[pallet::storage]
+	pub(super) type AuditLogDigest<T: Config> = StorageValue<_, T::Hash, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		/// The `AccountId` of the sudo key.
@@ -333,3 +732,80 @@ pub mod pallet {
 		}
 	}
 }
+
+impl<T: Config> Pallet<T> {
+	/// The most recent sudo actions, from the oldest to the newest.
+	pub fn audit_log() -> Vec<SudoAuditRecordOf<T>> {
+		let max = T::MaxAuditLogEntries::get() as u64;
+		if max == 0 {
+			return Vec::new()
+		}
+		let count = AuditLogCount::<T>::get();
+		(count.saturating_sub(max)..count)
+			.filter_map(|index| {
+				// Skip records left behind by a different `MaxAuditLogEntries`.
+				AuditLog::<T>::get((index % max) as u32).filter(|record| record.index == index)
+			})
+			.collect()
+	}
+
+	/// Weight of recording a sudo action in the audit log.
+	fn audit_log_weight() -> Weight {
+		T::DbWeight::get().reads_writes(2, 3)
+	}
+
+	/// Dispatch `call` with `origin` if `SudoCallFilter` contains it, fails with
+	/// [`Error::CallFiltered`] otherwise, so the rejection is recorded like any failed call.
+	fn dispatch_allowed(
+		call: &<T as Config>::RuntimeCall,
+		origin: T::RuntimeOrigin,
+	) -> DispatchResultWithPostInfo {
+		if !T::SudoCallFilter::contains(call) {
+			return Err(Error::<T>::CallFiltered.into())
+		}
+		call.clone().dispatch_bypass_filter(origin)
+	}
+
+	/// Append a dispatched call to the audit log, the oldest record is overwritten once the log
+	/// holds `MaxAuditLogEntries` records.
+	fn record_sudo_action(
+		sudoer: T::AccountId,
+		action: SudoAction<T::AccountId>,
+		call: &<T as Config>::RuntimeCall,
+		result: DispatchResult,
+	) {
+		let max = T::MaxAuditLogEntries::get();
+		if max == 0 {
+			return
+		}
+
+		let index = AuditLogCount::<T>::get();
+		let block_number = <frame_system::Pallet<T>>::block_number();
+		let call_hash = T::Hashing::hash_of(call);
+		let digest = T::Hashing::hash_of(&(
+			AuditLogDigest::<T>::get(),
+			index,
+			block_number,
+			&sudoer,
+			&action,
+			call_hash,
+			result,
+		));
+
+		AuditLog::<T>::insert(
+			(index % max as u64) as u32,
+			SudoAuditRecord { index, block_number, sudoer, action, call_hash, result, digest },
+		);
+		AuditLogCount::<T>::put(index.saturating_add(1));
+		AuditLogDigest::<T>::put(digest);
+	}
+
+	/// Ensure `who` is one of the sudo keys of the multi-key mode, returns the keys.
+	fn ensure_sudo_key(
+		who: &T::AccountId,
+	) -> Result<BoundedVec<T::AccountId, T::MaxSudoKeys>, Error<T>> {
+		let keys = SudoKeys::<T>::get();
+		ensure!(keys.contains(who), Error::<T>::RequireSudo);
+		Ok(keys)
+	}
+}
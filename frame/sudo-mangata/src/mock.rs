@@ -40,7 +40,7 @@ pub mod logger {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
-		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 	}
 
 	#[pallet::pallet]
@@ -48,6 +48,7 @@ pub mod logger {
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
 		#[pallet::weight(*weight)]
 		pub fn privileged_i32_log(
 			origin: OriginFor<T>,
@@ -61,6 +62,7 @@ pub mod logger {
 			Ok(().into())
 		}
 
+		#[pallet::call_index(1)]
 		#[pallet::weight(*weight)]
 		pub fn non_privileged_log(
 			origin: OriginFor<T>,
@@ -113,8 +115,8 @@ parameter_types! {
 }
 
 pub struct BlockEverything;
-impl Contains<Call> for BlockEverything {
-	fn contains(_: &Call) -> bool {
+impl Contains<RuntimeCall> for BlockEverything {
+	fn contains(_: &RuntimeCall) -> bool {
 		false
 	}
 }
@@ -124,8 +126,8 @@ impl frame_system::Config for Test {
 	type BlockWeights = ();
 	type BlockLength = ();
 	type DbWeight = ();
-	type Origin = Origin;
-	type Call = Call;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
 	type Index = u64;
 	type BlockNumber = u64;
 	type Hash = H256;
@@ -133,7 +135,7 @@ impl frame_system::Config for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = Event;
+	type RuntimeEvent = RuntimeEvent;
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
@@ -144,17 +146,31 @@ impl frame_system::Config for Test {
 	type SS58Prefix = ();
 	type OnSetCode = ();
 	type MaxConsumers = ConstU32<16>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = ();
 }
 
 // Implement the logger module's `Config` on the Test runtime.
 impl logger::Config for Test {
-	type Event = Event;
+	type RuntimeEvent = RuntimeEvent;
+}
+
+// Sudo may not upgrade the runtime.
+pub struct SudoCallFilter;
+impl Contains<RuntimeCall> for SudoCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		!matches!(call, RuntimeCall::System(frame_system::Call::set_code { .. }))
+	}
 }
 
 // Implement the sudo module's `Config` on the Test runtime.
 impl Config for Test {
-	type Event = Event;
-	type Call = Call;
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type MaxSudoKeys = ConstU32<3>;
+	type SudoCallFilter = SudoCallFilter;
+	type MaxAuditLogEntries = ConstU32<3>;
 }
 
 // New types for dispatchable functions.
@@ -20,9 +20,10 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok, weights::Weight};
 use mock::{
-	new_test_ext, Call, Event as TestEvent, Logger, LoggerCall, Origin, Sudo, SudoCall, System,
-	Test,
+	new_test_ext, Logger, LoggerCall, RuntimeCall, RuntimeEvent as TestEvent, RuntimeOrigin, Sudo,
+	SudoCall, System, Test,
 };
+use sp_core::H256;
 
 #[test]
 fn test_setup_works() {
@@ -39,19 +40,19 @@ fn sudo_basics() {
 	// Configure a default test environment and set the root `key` to 1.
 	new_test_ext(1).execute_with(|| {
 		// A privileged function should work when `sudo` is passed the root `key` as `origin`.
-		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
 			i: 42,
 			weight: Weight::from_parts(1_000, 0),
 		}));
-		assert_ok!(Sudo::sudo(Origin::signed(1), call));
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), call));
 		assert_eq!(Logger::i32_log(), vec![42i32]);
 
 		// A privileged function should not work when `sudo` is passed a non-root `key` as `origin`.
-		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
 			i: 42,
 			weight: Weight::from_parts(1_000, 0),
 		}));
-		assert_noop!(Sudo::sudo(Origin::signed(2), call), Error::<Test>::RequireSudo);
+		assert_noop!(Sudo::sudo(RuntimeOrigin::signed(2), call), Error::<Test>::RequireSudo);
 	});
 }
 
@@ -62,11 +63,11 @@ fn sudo_emits_events_correctly() {
 		System::set_block_number(1);
 
 		// Should emit event to indicate success when called with the root `key` and `call` is `Ok`.
-		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
 			i: 42,
 			weight: Weight::from_parts(1, 0),
 		}));
-		assert_ok!(Sudo::sudo(Origin::signed(1), call));
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), call));
 		System::assert_has_event(TestEvent::Sudo(Event::Sudid { sudo_result: Ok(()) }));
 	})
 }
@@ -75,31 +76,35 @@ fn sudo_emits_events_correctly() {
 fn sudo_unchecked_weight_basics() {
 	new_test_ext(1).execute_with(|| {
 		// A privileged function should work when `sudo` is passed the root `key` as origin.
-		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
 			i: 42,
 			weight: Weight::from_parts(1_000, 0),
 		}));
 		assert_ok!(Sudo::sudo_unchecked_weight(
-			Origin::signed(1),
+			RuntimeOrigin::signed(1),
 			call,
 			Weight::from_parts(1_000, 0)
 		));
 		assert_eq!(Logger::i32_log(), vec![42i32]);
 
 		// A privileged function should not work when called with a non-root `key`.
-		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
 			i: 42,
 			weight: Weight::from_parts(1_000, 0),
 		}));
 		assert_noop!(
-			Sudo::sudo_unchecked_weight(Origin::signed(2), call, Weight::from_parts(1_000, 0)),
+			Sudo::sudo_unchecked_weight(
+				RuntimeOrigin::signed(2),
+				call,
+				Weight::from_parts(1_000, 0)
+			),
 			Error::<Test>::RequireSudo,
 		);
 		// `I32Log` is unchanged after unsuccessful call.
 		assert_eq!(Logger::i32_log(), vec![42i32]);
 
 		// Controls the dispatched weight.
-		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
 			i: 42,
 			weight: Weight::from_parts(1, 0),
 		}));
@@ -117,12 +122,12 @@ fn sudo_unchecked_weight_emits_events_correctly() {
 		System::set_block_number(1);
 
 		// Should emit event to indicate success when called with the root `key` and `call` is `Ok`.
-		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
 			i: 42,
 			weight: Weight::from_parts(1, 0),
 		}));
 		assert_ok!(Sudo::sudo_unchecked_weight(
-			Origin::signed(1),
+			RuntimeOrigin::signed(1),
 			call,
 			Weight::from_parts(1_000, 0)
 		));
@@ -134,14 +139,14 @@ fn sudo_unchecked_weight_emits_events_correctly() {
 fn set_key_basics() {
 	new_test_ext(1).execute_with(|| {
 		// A root `key` can change the root `key`
-		assert_ok!(Sudo::set_key(Origin::signed(1), 2));
+		assert_ok!(Sudo::set_key(RuntimeOrigin::signed(1), 2));
 		assert_eq!(Sudo::key(), Some(2u64));
 	});
 
 	new_test_ext(1).execute_with(|| {
 		// A non-root `key` will trigger a `RequireSudo` error and a non-root `key` cannot change
 		// the root `key`.
-		assert_noop!(Sudo::set_key(Origin::signed(2), 3), Error::<Test>::RequireSudo);
+		assert_noop!(Sudo::set_key(RuntimeOrigin::signed(2), 3), Error::<Test>::RequireSudo);
 	});
 }
 
@@ -152,10 +157,10 @@ fn set_key_emits_events_correctly() {
 		System::set_block_number(1);
 
 		// A root `key` can change the root `key`.
-		assert_ok!(Sudo::set_key(Origin::signed(1), 2));
+		assert_ok!(Sudo::set_key(RuntimeOrigin::signed(1), 2));
 		System::assert_has_event(TestEvent::Sudo(Event::KeyChanged { old_sudoer: Some(1) }));
 		// Double check.
-		assert_ok!(Sudo::set_key(Origin::signed(2), 4));
+		assert_ok!(Sudo::set_key(RuntimeOrigin::signed(2), 4));
 		System::assert_has_event(TestEvent::Sudo(Event::KeyChanged { old_sudoer: Some(2) }));
 	});
 }
@@ -164,27 +169,27 @@ fn set_key_emits_events_correctly() {
 fn sudo_as_basics() {
 	new_test_ext(1).execute_with(|| {
 		// A privileged function will not work when passed to `sudo_as`.
-		let call = Box::new(Call::Logger(LoggerCall::privileged_i32_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
 			i: 42,
 			weight: Weight::from_parts(1_000, 0),
 		}));
-		assert_ok!(Sudo::sudo_as(Origin::signed(1), 2, call));
+		assert_ok!(Sudo::sudo_as(RuntimeOrigin::signed(1), 2, call));
 		assert!(Logger::i32_log().is_empty());
 		assert!(Logger::account_log().is_empty());
 
 		// A non-privileged function should not work when called with a non-root `key`.
-		let call = Box::new(Call::Logger(LoggerCall::non_privileged_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::non_privileged_log {
 			i: 42,
 			weight: Weight::from_parts(1, 0),
 		}));
-		assert_noop!(Sudo::sudo_as(Origin::signed(3), 2, call), Error::<Test>::RequireSudo);
+		assert_noop!(Sudo::sudo_as(RuntimeOrigin::signed(3), 2, call), Error::<Test>::RequireSudo);
 
 		// A non-privileged function will work when passed to `sudo_as` with the root `key`.
-		let call = Box::new(Call::Logger(LoggerCall::non_privileged_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::non_privileged_log {
 			i: 42,
 			weight: Weight::from_parts(1, 0),
 		}));
-		assert_ok!(Sudo::sudo_as(Origin::signed(1), 2, call));
+		assert_ok!(Sudo::sudo_as(RuntimeOrigin::signed(1), 2, call));
 		assert_eq!(Logger::i32_log(), vec![42i32]);
 		// The correct user makes the call within `sudo_as`.
 		assert_eq!(Logger::account_log(), vec![2]);
@@ -198,11 +203,336 @@ fn sudo_as_emits_events_correctly() {
 		System::set_block_number(1);
 
 		// A non-privileged function will work when passed to `sudo_as` with the root `key`.
-		let call = Box::new(Call::Logger(LoggerCall::non_privileged_log {
+		let call = Box::new(RuntimeCall::Logger(LoggerCall::non_privileged_log {
 			i: 42,
 			weight: Weight::from_parts(1, 0),
 		}));
-		assert_ok!(Sudo::sudo_as(Origin::signed(1), 2, call));
+		assert_ok!(Sudo::sudo_as(RuntimeOrigin::signed(1), 2, call));
 		System::assert_has_event(TestEvent::Sudo(Event::SudoAsDone { sudo_result: Ok(()) }));
 	});
 }
+
+fn set_sudo_keys(keys: Vec<u64>, threshold: u32, delay: u64) -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::Sudo(SudoCall::set_sudo_keys { keys, threshold, delay }))
+}
+
+fn logger_call(i: i32) -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::Logger(LoggerCall::privileged_i32_log {
+		i,
+		weight: Weight::from_parts(1, 0),
+	}))
+}
+
+#[test]
+fn set_sudo_keys_basics() {
+	new_test_ext(1).execute_with(|| {
+		// Multi-key mode is set up through the single sudo key.
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), set_sudo_keys(vec![1, 2, 3], 2, 5)));
+		assert_eq!(Sudo::key(), None);
+		assert_eq!(Sudo::sudo_keys().to_vec(), vec![1, 2, 3]);
+		assert_eq!(Sudo::threshold(), 2);
+		assert_eq!(Sudo::announcement_delay(), 5);
+
+		// The single sudo key does not work anymore.
+		assert_noop!(
+			Sudo::sudo(RuntimeOrigin::signed(1), logger_call(42)),
+			Error::<Test>::RequireSudo
+		);
+	});
+
+	new_test_ext(1).execute_with(|| {
+		// Only `Root` can set the sudo keys.
+		assert_noop!(
+			Sudo::set_sudo_keys(RuntimeOrigin::signed(1), vec![1, 2], 1, 0),
+			sp_runtime::traits::BadOrigin
+		);
+		assert_noop!(
+			Sudo::set_sudo_keys(RuntimeOrigin::root(), vec![1, 2], 0, 0),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			Sudo::set_sudo_keys(RuntimeOrigin::root(), vec![1, 2], 3, 0),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			Sudo::set_sudo_keys(RuntimeOrigin::root(), vec![], 0, 0),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			Sudo::set_sudo_keys(RuntimeOrigin::root(), vec![1, 2, 1], 2, 0),
+			Error::<Test>::DuplicateSudoKey
+		);
+		assert_noop!(
+			Sudo::set_sudo_keys(RuntimeOrigin::root(), vec![1, 2, 3, 4], 2, 0),
+			Error::<Test>::TooManySudoKeys
+		);
+	});
+}
+
+#[test]
+fn set_sudo_keys_emits_events_correctly() {
+	new_test_ext(1).execute_with(|| {
+		// Set block number to 1 because events are not emitted on block 0.
+		System::set_block_number(1);
+
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), set_sudo_keys(vec![1, 2, 3], 2, 5)));
+		System::assert_has_event(TestEvent::Sudo(Event::KeyChanged { old_sudoer: Some(1) }));
+		System::assert_has_event(TestEvent::Sudo(Event::SudoKeysSet {
+			keys: vec![1, 2, 3],
+			threshold: 2,
+			delay: 5,
+		}));
+	});
+}
+
+#[test]
+fn announced_call_basics() {
+	new_test_ext(1).execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), set_sudo_keys(vec![1, 2, 3], 2, 5)));
+
+		let call = logger_call(42);
+		let call_hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+
+		// Only sudo keys can announce calls.
+		assert_noop!(
+			Sudo::announce(RuntimeOrigin::signed(4), call_hash),
+			Error::<Test>::RequireSudo
+		);
+		assert_ok!(Sudo::announce(RuntimeOrigin::signed(1), call_hash));
+		assert_noop!(
+			Sudo::announce(RuntimeOrigin::signed(2), call_hash),
+			Error::<Test>::AlreadyAnnounced
+		);
+		assert_eq!(Sudo::announcements(call_hash).unwrap().executable_at, 6);
+
+		// Announcement delay has not passed yet.
+		assert_noop!(
+			Sudo::execute_announced(RuntimeOrigin::signed(1), call.clone()),
+			Error::<Test>::NotYetExecutable
+		);
+
+		// Announcer approves the call implicitly, but threshold is 2.
+		System::set_block_number(6);
+		assert_noop!(
+			Sudo::approve(RuntimeOrigin::signed(1), call_hash),
+			Error::<Test>::AlreadyApproved
+		);
+		assert_noop!(
+			Sudo::execute_announced(RuntimeOrigin::signed(1), call.clone()),
+			Error::<Test>::NotEnoughApprovals
+		);
+
+		assert_noop!(
+			Sudo::approve(RuntimeOrigin::signed(4), call_hash),
+			Error::<Test>::RequireSudo
+		);
+		assert_ok!(Sudo::approve(RuntimeOrigin::signed(2), call_hash));
+
+		// Any of the sudo keys can execute the call.
+		assert_noop!(
+			Sudo::execute_announced(RuntimeOrigin::signed(4), call.clone()),
+			Error::<Test>::RequireSudo
+		);
+		assert_ok!(Sudo::execute_announced(RuntimeOrigin::signed(3), call.clone()));
+		assert_eq!(Logger::i32_log(), vec![42i32]);
+
+		// The announcement is consumed.
+		assert_eq!(Sudo::announcements(call_hash), None);
+		assert_noop!(
+			Sudo::execute_announced(RuntimeOrigin::signed(3), call),
+			Error::<Test>::NotAnnounced
+		);
+	});
+}
+
+#[test]
+fn announced_call_emits_events_correctly() {
+	new_test_ext(1).execute_with(|| {
+		// Set block number to 1 because events are not emitted on block 0.
+		System::set_block_number(1);
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), set_sudo_keys(vec![1, 2, 3], 2, 0)));
+
+		let call = logger_call(42);
+		let call_hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+
+		assert_ok!(Sudo::announce(RuntimeOrigin::signed(1), call_hash));
+		System::assert_has_event(TestEvent::Sudo(Event::CallAnnounced {
+			call_hash,
+			announcer: 1,
+			executable_at: 1,
+		}));
+
+		assert_ok!(Sudo::approve(RuntimeOrigin::signed(3), call_hash));
+		System::assert_has_event(TestEvent::Sudo(Event::CallApproved {
+			call_hash,
+			approver: 3,
+			approvals: 2,
+		}));
+
+		assert_ok!(Sudo::execute_announced(RuntimeOrigin::signed(2), call));
+		System::assert_has_event(TestEvent::Sudo(Event::AnnouncedCallExecuted {
+			call_hash,
+			sudo_result: Ok(()),
+		}));
+	});
+}
+
+#[test]
+fn cancel_announced_call_works() {
+	new_test_ext(1).execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), set_sudo_keys(vec![1, 2, 3], 1, 0)));
+
+		let call = logger_call(42);
+		let call_hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+		assert_ok!(Sudo::announce(RuntimeOrigin::signed(1), call_hash));
+
+		// Any key can cancel the announcement, not only the announcer.
+		assert_noop!(Sudo::cancel(RuntimeOrigin::signed(4), call_hash), Error::<Test>::RequireSudo);
+		assert_ok!(Sudo::cancel(RuntimeOrigin::signed(2), call_hash));
+		System::assert_has_event(TestEvent::Sudo(Event::AnnouncementCancelled {
+			call_hash,
+			canceller: 2,
+		}));
+
+		assert_noop!(
+			Sudo::cancel(RuntimeOrigin::signed(2), call_hash),
+			Error::<Test>::NotAnnounced
+		);
+		assert_noop!(
+			Sudo::execute_announced(RuntimeOrigin::signed(1), call),
+			Error::<Test>::NotAnnounced
+		);
+		assert!(Logger::i32_log().is_empty());
+	});
+}
+
+#[test]
+fn approvals_of_removed_keys_do_not_count() {
+	new_test_ext(1).execute_with(|| {
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), set_sudo_keys(vec![1, 2, 3], 2, 0)));
+
+		let call = logger_call(42);
+		let call_hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+		assert_ok!(Sudo::announce(RuntimeOrigin::signed(1), call_hash));
+		assert_ok!(Sudo::approve(RuntimeOrigin::signed(2), call_hash));
+
+		// Key 2 is replaced by key 4 through an announced call.
+		let replace_keys = set_sudo_keys(vec![1, 3, 4], 2, 0);
+		let replace_keys_hash = <Test as frame_system::Config>::Hashing::hash_of(&replace_keys);
+		assert_ok!(Sudo::announce(RuntimeOrigin::signed(1), replace_keys_hash));
+		assert_ok!(Sudo::approve(RuntimeOrigin::signed(3), replace_keys_hash));
+		assert_ok!(Sudo::execute_announced(RuntimeOrigin::signed(1), replace_keys));
+		assert_eq!(Sudo::sudo_keys().to_vec(), vec![1, 3, 4]);
+
+		// Only the approval of key 1 is left.
+		assert_noop!(
+			Sudo::execute_announced(RuntimeOrigin::signed(1), call.clone()),
+			Error::<Test>::NotEnoughApprovals
+		);
+		assert_ok!(Sudo::approve(RuntimeOrigin::signed(4), call_hash));
+		assert_eq!(Sudo::announcements(call_hash).unwrap().approvals.to_vec(), vec![1, 4]);
+		assert_ok!(Sudo::execute_announced(RuntimeOrigin::signed(1), call));
+		assert_eq!(Logger::i32_log(), vec![42i32]);
+	});
+}
+
+#[test]
+fn sudo_call_filter_works() {
+	new_test_ext(1).execute_with(|| {
+		System::set_block_number(1);
+		let set_code = Box::new(RuntimeCall::System(frame_system::Call::set_code { code: vec![] }));
+		let filtered: DispatchResult = Err(Error::<Test>::CallFiltered.into());
+		let last_result = || Sudo::audit_log().last().map(|record| record.result);
+
+		// Rejected calls are not dispatched but recorded in the audit log.
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), set_code.clone()));
+		System::assert_last_event(TestEvent::Sudo(Event::Sudid { sudo_result: filtered }));
+		assert_eq!(last_result(), Some(filtered));
+		assert_ok!(Sudo::sudo_unchecked_weight(
+			RuntimeOrigin::signed(1),
+			set_code.clone(),
+			Weight::from_parts(1_000, 0)
+		));
+		System::assert_last_event(TestEvent::Sudo(Event::Sudid { sudo_result: filtered }));
+		assert_eq!(last_result(), Some(filtered));
+		assert_ok!(Sudo::sudo_as(RuntimeOrigin::signed(1), 2, set_code.clone()));
+		System::assert_last_event(TestEvent::Sudo(Event::SudoAsDone { sudo_result: filtered }));
+		assert_eq!(last_result(), Some(filtered));
+
+		// Announced calls are filtered on execution, the announcement is consumed.
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), set_sudo_keys(vec![1, 2], 1, 0)));
+		let call_hash = <Test as frame_system::Config>::Hashing::hash_of(&set_code);
+		assert_ok!(Sudo::announce(RuntimeOrigin::signed(1), call_hash));
+		assert_ok!(Sudo::execute_announced(RuntimeOrigin::signed(1), set_code));
+		System::assert_last_event(TestEvent::Sudo(Event::AnnouncedCallExecuted {
+			call_hash,
+			sudo_result: filtered,
+		}));
+		let record = Sudo::audit_log().pop().unwrap();
+		assert_eq!(record.action, SudoAction::ExecuteAnnounced);
+		assert_eq!(record.result, filtered);
+		assert!(Sudo::announcements(call_hash).is_none());
+	});
+}
+
+#[test]
+fn audit_log_records_sudo_actions() {
+	new_test_ext(1).execute_with(|| {
+		System::set_block_number(1);
+		assert!(Sudo::audit_log().is_empty());
+
+		let call = logger_call(42);
+		assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), call.clone()));
+		System::set_block_number(2);
+		// A privileged function fails when passed to `sudo_as`, which is recorded as well.
+		assert_ok!(Sudo::sudo_as(RuntimeOrigin::signed(1), 2, logger_call(42)));
+
+		let log = Sudo::audit_log();
+		assert_eq!(log.len(), 2);
+		assert_eq!(log[0].index, 0);
+		assert_eq!(log[0].block_number, 1);
+		assert_eq!(log[0].sudoer, 1);
+		assert_eq!(log[0].action, SudoAction::Sudo);
+		assert_eq!(log[0].call_hash, <Test as frame_system::Config>::Hashing::hash_of(&call));
+		assert_eq!(log[0].result, Ok(()));
+		assert_eq!(log[1].index, 1);
+		assert_eq!(log[1].block_number, 2);
+		assert_eq!(log[1].action, SudoAction::SudoAs { who: 2 });
+		assert_eq!(log[1].result, Err(sp_runtime::DispatchError::BadOrigin));
+
+		// Records are chained by their digests.
+		let digest = |previous: H256, record: &SudoAuditRecord<u64, H256, u64>| {
+			<Test as frame_system::Config>::Hashing::hash_of(&(
+				previous,
+				record.index,
+				record.block_number,
+				record.sudoer,
+				&record.action,
+				record.call_hash,
+				record.result,
+			))
+		};
+		assert_eq!(log[0].digest, digest(H256::default(), &log[0]));
+		assert_eq!(log[1].digest, digest(log[0].digest, &log[1]));
+	});
+}
+
+#[test]
+fn audit_log_keeps_most_recent_records() {
+	new_test_ext(1).execute_with(|| {
+		for i in 0..5 {
+			assert_ok!(Sudo::sudo(RuntimeOrigin::signed(1), logger_call(i)));
+		}
+		assert_eq!(Logger::i32_log(), vec![0, 1, 2, 3, 4]);
+
+		// Only `MaxAuditLogEntries` most recent records are kept.
+		let log = Sudo::audit_log();
+		assert_eq!(log.iter().map(|record| record.index).collect::<Vec<_>>(), vec![2, 3, 4]);
+		assert_eq!(
+			log[2].call_hash,
+			<Test as frame_system::Config>::Hashing::hash_of(&logger_call(4))
+		);
+	});
+}
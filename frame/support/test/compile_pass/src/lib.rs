@@ -76,6 +76,9 @@ impl frame_system::Config for Runtime {
 	type OnKilledAccount = ();
 	type OnSetCode = ();
 	type MaxConsumers = ConstU32<16>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = ();
 	type SystemWeightInfo = ();
 	type SS58Prefix = ConstU16<0>;
 }
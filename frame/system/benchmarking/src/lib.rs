@@ -20,17 +20,45 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Encode;
-use frame_benchmarking::v1::{benchmarks, whitelisted_caller};
+use frame_benchmarking::v1::{account, benchmarks, whitelisted_caller};
 use frame_support::{dispatch::DispatchClass, storage, traits::Get};
-use frame_system::{Call, Pallet as System, RawOrigin};
-use sp_core::storage::well_known_keys;
-use sp_runtime::traits::Hash;
+use frame_system::{limits::StorageQueueLimits, Call, Pallet as System, RawOrigin};
+use sp_core::{storage::well_known_keys, H256};
+use sp_runtime::traits::{Hash, One};
 use sp_std::{prelude::*, vec};
 
 mod mock;
 
+/// Maximal number of txs enqueued by the storage queue benchmarks.
+#[allow(dead_code)]
+const MAX_ENQUEUED_TXS: u32 = 1000;
+
+/// Length of the txs shuffled and popped by the storage queue benchmarks.
+#[allow(dead_code)]
+const QUEUED_TX_LEN: u32 = 256;
+
 pub struct Pallet<T: Config>(System<T>);
-pub trait Config: frame_system::Config {}
+pub trait Config: frame_system::Config {
+	/// Builds the `i`-th tx enqueued by the storage queue benchmarks, `len` bytes long.
+	///
+	/// Txs rejected by [`frame_system::Config::EnqueuedTxsValidator`] are not stored, runtimes
+	/// that validate enqueued txs should build valid signed txs to benchmark the validation too.
+	fn enqueued_tx(i: u32, len: u32) -> Vec<u8> {
+		let mut tx = i.encode();
+		tx.resize(tx.len().max(len as usize), 0);
+		tx
+	}
+}
+
+/// Stores `t` txs in the storage queue and moves to the next block, so they can be shuffled.
+#[allow(dead_code)]
+fn enqueue_txs_in_previous_block<T: Config>(t: u32) {
+	let txs = (0..t)
+		.map(|i| (Some(account("caller", i, 0)), T::enqueued_tx(i, QUEUED_TX_LEN)))
+		.collect::<Vec<_>>();
+	System::<T>::store_txs(txs);
+	System::<T>::set_block_number(System::<T>::block_number() + One::one());
+}
 
 benchmarks! {
 	remark {
@@ -48,6 +76,48 @@ benchmarks! {
 	set_heap_pages {
 	}: _(RawOrigin::Root, Default::default())
 
+	set_storage_queue_limits {
+		let limits = StorageQueueLimits { depth: 1, max_txs: 1, max_len: 1 };
+	}: _(RawOrigin::Root, Some(limits))
+	verify {
+		assert_eq!(System::<T>::storage_queue_limits(), limits);
+	}
+
+	enqueue_txs {
+		let t in 0 .. T::StorageQueueLimits::get().max_txs.min(MAX_ENQUEUED_TXS);
+		let b in 0 .. T::StorageQueueLimits::get().max_len
+			.min(*T::BlockLength::get().max.get(DispatchClass::Normal));
+		let txs = (0..t)
+			.map(|i| (Some(account("caller", i, 0)), T::enqueued_tx(i, b / t)))
+			.collect::<Vec<_>>();
+	}: _(RawOrigin::None, txs)
+	verify {
+		assert!(frame_system::DidStoreTxs::<T>::get());
+	}
+
+	set_block_seed {
+		let t in 0 .. MAX_ENQUEUED_TXS;
+		enqueue_txs_in_previous_block::<T>(t);
+		let seed = H256::repeat_byte(1);
+	}: {
+		System::<T>::set_block_seed(&seed);
+	}
+	verify {
+		assert_eq!(System::<T>::block_seed(), seed);
+		assert_eq!(System::<T>::get_previous_blocks_txs().len(), t as usize);
+	}
+
+	pop_txs {
+		let t in 0 .. MAX_ENQUEUED_TXS;
+		enqueue_txs_in_previous_block::<T>(t);
+		System::<T>::set_block_seed(&H256::repeat_byte(1));
+	}: {
+		System::<T>::pop_txs(t as usize);
+	}
+	verify {
+		assert_eq!(System::<T>::enqueued_blocks_count(), 0);
+	}
+
 	// `set_code` was not benchmarked because it is pretty hard to come up with a real
 	// Wasm runtime to test the upgrade with. But this is okay because we will make
 	// `set_code` take a full block anyway.
@@ -20,11 +20,12 @@ use codec::{Decode, Encode};
 use frame_support::dispatch::DispatchInfo;
 use scale_info::TypeInfo;
 use sp_runtime::{
-	traits::{DispatchInfoOf, Dispatchable, One, SignedExtension},
+	traits::{DispatchInfoOf, Dispatchable, One, Saturating, SignedExtension},
 	transaction_validity::{
 		InvalidTransaction, TransactionLongevity, TransactionValidity, TransactionValidityError,
 		ValidTransaction,
 	},
+	SaturatedConversion,
 };
 use sp_std::vec;
 
@@ -104,18 +105,18 @@ where
 		_info: &DispatchInfoOf<Self::Call>,
 		_len: usize,
 	) -> TransactionValidity {
-		// check index
+		// check index, txs waiting in the storage queue already consumed their nonces
 		let account = crate::Account::<T>::get(who);
-		if self.0 < account.nonce {
+		let enqueued: T::Index =
+			(crate::Pallet::<T>::enqueued_txs_count(who) as u64).saturated_into();
+		let nonce = account.nonce.saturating_add(enqueued);
+		if self.0 < nonce {
 			return InvalidTransaction::Stale.into()
 		}
 
 		let provides = vec![Encode::encode(&(who, self.0))];
-		let requires = if account.nonce < self.0 {
-			vec![Encode::encode(&(who, self.0 - One::one()))]
-		} else {
-			vec![]
-		};
+		let requires =
+			if nonce < self.0 { vec![Encode::encode(&(who, self.0 - One::one()))] } else { vec![] };
 
 		Ok(ValidTransaction {
 			priority: 0,
@@ -168,4 +169,47 @@ mod tests {
 			);
 		})
 	}
+
+	#[test]
+	fn signed_ext_check_nonce_takes_enqueued_txs_into_account() {
+		new_test_ext().execute_with(|| {
+			crate::Account::<Test>::insert(
+				1,
+				crate::AccountInfo {
+					nonce: 1,
+					consumers: 0,
+					providers: 0,
+					sufficients: 0,
+					data: 0,
+				},
+			);
+			crate::Pallet::<Test>::enqueue_txs(
+				crate::mock::RuntimeOrigin::none(),
+				vec![(Some(1), vec![1]), (Some(2), vec![2]), (Some(1), vec![3])],
+			)
+			.unwrap();
+			let info = DispatchInfo::default();
+			let len = 0_usize;
+			// already enqueued
+			assert_noop!(
+				CheckNonce::<Test>(2).validate(&1, CALL, &info, len),
+				InvalidTransaction::Stale
+			);
+			// correct, does not wait for enqueued txs
+			assert_eq!(
+				CheckNonce::<Test>(3).validate(&1, CALL, &info, len).unwrap().requires,
+				Vec::<Vec<u8>>::new()
+			);
+			// future
+			assert_eq!(
+				CheckNonce::<Test>(4).validate(&1, CALL, &info, len).unwrap().requires,
+				vec![(1u64, 3u64).encode()]
+			);
+			// enqueued txs are not executed yet
+			assert_noop!(
+				CheckNonce::<Test>(3).pre_dispatch(&1, CALL, &info, len),
+				InvalidTransaction::Future
+			);
+		})
+	}
 }
@@ -76,14 +76,16 @@ use sp_runtime::{
 		MaybeSerializeDeserialize, Member, One, SaturatedConversion, Saturating, SimpleBitOps,
 		StaticLookup, Zero,
 	},
-	DispatchError, RuntimeDebug,
+	transaction_validity::TransactionValidityError,
+	DispatchError, RuntimeDebug, TransactionOutcome,
 };
 #[cfg(any(feature = "std", test))]
 use sp_std::map;
 use sp_std::{fmt::Debug, marker::PhantomData, prelude::*};
 use sp_version::RuntimeVersion;
 
-use codec::{Decode, Encode, EncodeLike, FullCodec, MaxEncodedLen};
+use codec::{Decode, DecodeAll, Encode, EncodeLike, FullCodec, MaxEncodedLen};
+use extrinsic_shuffler::ShuffleStrategy;
 use frame_support::{
 	dispatch::{
 		extract_actual_pays_fee, extract_actual_weight, DispatchClass, DispatchInfo,
@@ -135,7 +137,8 @@ pub use extensions::check_mortality::CheckMortality as CheckEra;
 pub use frame_support::dispatch::RawOrigin;
 pub use weights::WeightInfo;
 
-pub type StorageQueueLimit = frame_support::traits::ConstU32<2>;
+/// Hard upper bound of the storage queue depth, see [`limits::StorageQueueLimits::depth`].
+pub type MaxStorageQueueDepth = frame_support::traits::ConstU32<16>;
 const LOG_TARGET: &str = "runtime::system";
 
 /// Compute the trie root of a list of extrinsics.
@@ -172,6 +175,23 @@ impl<T: Config> SetCode<T> for () {
 	}
 }
 
+/// Validate txs passed to the [`Pallet::enqueue_txs`] inherent before they are stored in the
+/// storage queue.
+pub trait EnqueuedTxsValidator<T: Config> {
+	/// Check that encoded `tx` can be executed once popped from the storage queue, e.g. that its
+	/// signer is able to pay fees.
+	///
+	/// Called with [`TxPrevalidation`] set, within a storage layer that is rolled back once all
+	/// txs enqueued by the block are validated.
+	fn validate_enqueued_tx(tx: &[u8]) -> Result<(), TransactionValidityError>;
+}
+
+impl<T: Config> EnqueuedTxsValidator<T> for () {
+	fn validate_enqueued_tx(_tx: &[u8]) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+}
+
 /// Numeric limits over the ability to add a consumer ref using `inc_consumers`.
 pub trait ConsumerLimits {
 	/// The number of consumers over which `inc_consumers` will cease to work.
@@ -361,9 +381,27 @@ pub mod pallet {
 
 		/// The maximum number of consumers allowed on a single account.
 		type MaxConsumers: ConsumerLimits;
+
+		/// Algorithm used to figure out execution order of txs stored in the storage queue, use
+		/// `()` for the default round robin shuffling.
+		type ShuffleStrategy: extrinsic_shuffler::ShuffleStrategy;
+
+		/// Limits of the storage queue, used unless overridden by root with
+		/// [`Pallet::set_storage_queue_limits`]. Use `()` for the default limits (queue depth of 2
+		/// blocks, no limits of txs enqueued by a single block).
+		#[pallet::constant]
+		type StorageQueueLimits: Get<limits::StorageQueueLimits>;
+
+		/// Prevalidation of txs passed to the [`Pallet::enqueue_txs`] inherent, txs that are
+		/// rejected are not stored in the storage queue. Use `()` to accept all txs.
+		type EnqueuedTxsValidator: EnqueuedTxsValidator<Self>;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
 
@@ -373,6 +411,10 @@ pub mod pallet {
 		fn integrity_test() {
 			sp_io::TestExternalities::default().execute_with(|| {
 				T::BlockWeights::get().validate().expect("The weights are invalid.");
+				assert!(
+					T::StorageQueueLimits::get().is_valid(),
+					"The storage queue limits are invalid."
+				);
 			});
 		}
 	}
@@ -382,9 +424,15 @@ pub mod pallet {
 		/// Persists list of encoded txs into the storage queue. There is an dedicated
 		/// check in [Executive](https://storage.googleapis.com/mangata-docs-node/frame_executive/struct.Executive.html) that verifies that passed binary data can be
 		/// decoded into extrinsics.
+		///
+		/// Txs rejected by [`Config::EnqueuedTxsValidator`] are skipped and reported with
+		/// [`Event::EnqueuedTxRejected`].
 		#[pallet::call_index(0)]
 		#[pallet::weight((
-			0,
+			T::SystemWeightInfo::enqueue_txs(
+				txs.len() as u32,
+				txs.iter().fold(0u32, |len, (_, tx)| len.saturating_add(tx.len() as u32)),
+			),
 			DispatchClass::Mandatory
 		))]
 		pub fn enqueue_txs(
@@ -398,6 +446,8 @@ pub mod pallet {
 			);
 			DidStoreTxs::<T>::put(true);
 			ensure!(txs.is_empty() || Self::can_enqueue_txs(), Error::<T>::StorageQueueFull);
+			Self::ensure_enqueue_limits(&txs)?;
+			let txs = Self::prevalidate_txs(txs)?;
 			let hashes =
 				txs.iter().map(|(_, data)| T::Hashing::hash(&data[..])).collect::<Vec<_>>();
 			Self::deposit_log(generic::DigestItem::Other(hashes.encode()));
@@ -521,6 +571,32 @@ pub mod pallet {
 			Self::deposit_event(Event::Remarked { sender: who, hash });
 			Ok(().into())
 		}
+
+		/// Override limits of the storage queue, `None` restores limits configured by the runtime
+		/// ([`Config::StorageQueueLimits`]).
+		///
+		/// Lowering the queue depth below the number of blocks currently held in the queue does not
+		/// drop any txs, new txs are not enqueued until the queue is drained.
+		#[pallet::call_index(9)]
+		#[pallet::weight((
+			T::SystemWeightInfo::set_storage_queue_limits(),
+			DispatchClass::Operational,
+		))]
+		pub fn set_storage_queue_limits(
+			origin: OriginFor<T>,
+			limits: Option<limits::StorageQueueLimits>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			ensure!(
+				limits.as_ref().map_or(true, |limits| limits.is_valid()),
+				Error::<T>::InvalidStorageQueueLimits
+			);
+			StorageQueueLimitsOverride::<T>::set(limits);
+			Self::deposit_event(Event::StorageQueueLimitsSet {
+				limits: Self::storage_queue_limits(),
+			});
+			Ok(().into())
+		}
 	}
 
 	/// Event for the System pallet.
@@ -540,6 +616,11 @@ pub mod pallet {
 		Remarked { sender: T::AccountId, hash: T::Hash },
 		/// On stored txs
 		TxsEnqueued { count: u64 },
+		/// Limits of the storage queue have been changed.
+		StorageQueueLimitsSet { limits: limits::StorageQueueLimits },
+		/// Tx passed to the `enqueue_txs` inherent has been rejected by the prevalidation and
+		/// was not stored in the storage queue.
+		EnqueuedTxRejected { hash: T::Hash, error: TransactionValidityError },
 	}
 
 	/// Error for the System pallet
@@ -563,6 +644,12 @@ pub mod pallet {
 		CallFiltered,
 		/// the storage queue is empty and cannot accept any new txs
 		StorageQueueFull,
+		/// Too many txs passed to a single `enqueue_txs` inherent.
+		TooManyEnqueuedTxs,
+		/// Total length of txs passed to a single `enqueue_txs` inherent is too big.
+		EnqueuedTxsTooLarge,
+		/// Storage queue depth is either zero or exceeds [`MaxStorageQueueDepth`].
+		InvalidStorageQueueLimits,
 	}
 
 	/// Exposed trait-generic origin type.
@@ -627,16 +714,34 @@ pub mod pallet {
 	/// - enqueuing new txs using [`Pallet::enqueue_txs`] inherent
 	/// - poping txs from the queue using [`Pallet::pop_txs`] that is exposed throught RuntimeApi
 	///   call
+	///
+	/// # Limits
+	/// Number of blocks held in the queue and txs enqueued by a single block are limited by
+	/// [`Config::StorageQueueLimits`], root can override them with
+	/// [`Pallet::set_storage_queue_limits`].
 	#[pallet::storage]
 	pub type StorageQueue<T: Config> = StorageValue<
 		_,
 		BoundedVec<
 			(T::BlockNumber, Option<u32>, Vec<(Option<T::AccountId>, EncodedTx)>),
-			StorageQueueLimit,
+			MaxStorageQueueDepth,
 		>,
 		ValueQuery,
 	>;
 
+	/// Number of not yet executed txs held in the [`StorageQueue`] per signer, kept in sync by
+	/// [`Pallet::store_txs`] and [`Pallet::pop_txs`] so that nonce validation does not need to
+	/// decode the whole queue. Chains that enqueued txs before it was introduced need to run
+	/// [`migrations::v1::MigrateToV1`].
+	#[pallet::storage]
+	pub type EnqueuedTxsCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Limits of the storage queue set by root, override [`Config::StorageQueueLimits`].
+	#[pallet::storage]
+	pub type StorageQueueLimitsOverride<T: Config> =
+		StorageValue<_, limits::StorageQueueLimits, OptionQuery>;
+
 	/// Map of block numbers to block shuffling seeds
 	#[pallet::storage]
 	pub type DidStoreTxs<T: Config> = StorageValue<_, bool, ValueQuery>;
@@ -1396,23 +1501,26 @@ impl<T: Config> Pallet<T> {
 		});
 	}
 
-	/// store seed and shuffle extrinsics from precedesing block
-	pub fn set_block_seed(seed: &sp_core::H256) {
+	/// store seed and shuffle extrinsics from precedesing block, returns number of shuffled txs
+	pub fn set_block_seed(seed: &sp_core::H256) -> usize {
 		sp_runtime::runtime_logger::RuntimeLogger::init();
 		<BlockSeed<T>>::put(seed);
 		let mut queue = <StorageQueue<T>>::get();
 		let current_block = Self::block_number().saturated_into::<u32>();
 		log::debug!( target: "runtime::ver", "storing seed {} for block {}", seed, current_block);
+		let mut shuffled_count = 0;
 		if let Some((nr, index, txs)) = queue.last_mut() {
 			if Self::block_number() == *nr + One::one() {
 				// index is only set when txs has been shuffled already
 				assert!(index.is_none());
-				let shuffled = extrinsic_shuffler::shuffle_using_seed(txs.clone(), seed);
+				let shuffled = T::ShuffleStrategy::shuffle(txs.clone(), seed);
+				shuffled_count = shuffled.len();
 				let _ = sp_std::mem::replace(txs, shuffled);
 				let _ = sp_std::mem::replace(index, Some(0));
 			}
 		}
 		<StorageQueue<T>>::put(queue);
+		shuffled_count
 	}
 
 	// part of block creation mechanims, used to ignore nonces when prevalidating txs
@@ -1425,6 +1533,9 @@ impl<T: Config> Pallet<T> {
 		sp_runtime::runtime_logger::RuntimeLogger::init();
 		if !txs.is_empty() {
 			log::debug!( target: "runtime::ver", "storing {} txs at block {}", block_number, txs.len() );
+			for who in txs.iter().filter_map(|(who, _)| who.as_ref()) {
+				EnqueuedTxsCount::<T>::mutate(who, |count| *count = count.saturating_add(1));
+			}
 			let mut queue = <StorageQueue<T>>::take();
 			queue.try_push((Self::block_number(), None, txs)).unwrap();
 			<StorageQueue<T>>::put(queue);
@@ -1435,7 +1546,59 @@ impl<T: Config> Pallet<T> {
 
 	pub fn can_enqueue_txs() -> bool {
 		let queue = <StorageQueue<T>>::get();
-		<StorageQueueLimit as Get<u32>>::get() > queue.len() as u32
+		Self::storage_queue_limits().depth > queue.len() as u32
+	}
+
+	/// returns limits of the storage queue currently in force, see
+	/// [`Pallet::set_storage_queue_limits`]
+	pub fn storage_queue_limits() -> limits::StorageQueueLimits {
+		StorageQueueLimitsOverride::<T>::get().unwrap_or_else(T::StorageQueueLimits::get)
+	}
+
+	/// checks that `txs` can be enqueued by a single [`Pallet::enqueue_txs`] inherent
+	pub fn ensure_enqueue_limits(
+		txs: &[(Option<T::AccountId>, EncodedTx)],
+	) -> Result<(), Error<T>> {
+		let limits = Self::storage_queue_limits();
+		ensure!(txs.len() <= limits.max_txs as usize, Error::<T>::TooManyEnqueuedTxs);
+		let len = txs.iter().fold(0usize, |len, (_, tx)| len.saturating_add(tx.len()));
+		ensure!(len <= limits.max_len as usize, Error::<T>::EnqueuedTxsTooLarge);
+		Ok(())
+	}
+
+	/// filters out `txs` rejected by [`Config::EnqueuedTxsValidator`], all of them are validated
+	/// one after another within a single storage layer that is rolled back afterwards, so fees
+	/// paid by txs of the same signer add up
+	pub fn prevalidate_txs(
+		txs: Vec<(Option<T::AccountId>, EncodedTx)>,
+	) -> Result<Vec<(Option<T::AccountId>, EncodedTx)>, DispatchError> {
+		let results = storage::with_transaction(|| {
+			TxPrevalidation::<T>::put(true);
+			let results = txs
+				.iter()
+				.map(|(_, tx)| T::EnqueuedTxsValidator::validate_enqueued_tx(&tx[..]))
+				.collect::<Vec<_>>();
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>(results))
+		})?;
+
+		Ok(txs
+			.into_iter()
+			.zip(results)
+			.filter_map(|((who, tx), result)| match result {
+				Ok(()) => Some((who, tx)),
+				Err(error) => {
+					let hash = T::Hashing::hash(&tx[..]);
+					log::debug!(
+						target: "runtime::ver",
+						"enqueued tx {:?} rejected: {:?}",
+						hash,
+						error
+					);
+					Self::deposit_event(Event::EnqueuedTxRejected { hash, error });
+					None
+				},
+			})
+			.collect())
 	}
 
 	/// returns list of all not executed txs held in storage queue at the moment
@@ -1443,15 +1606,9 @@ impl<T: Config> Pallet<T> {
 		<StorageQueue<T>>::get().len() as u64
 	}
 
-	/// returns amount of txs in storage queue signed by particular user
+	/// returns amount of txs in storage queue signed by particular user that are not executed yet
 	pub fn enqueued_txs_count(acc: &T::AccountId) -> usize {
-		let queue = <StorageQueue<T>>::get();
-		queue
-			.iter()
-			.map(|(_, _, txs)| txs)
-			.flatten()
-			.filter(|(who, _)| who.clone() == Some(acc.clone()))
-			.count()
+		EnqueuedTxsCount::<T>::get(acc) as usize
 	}
 
 	pub fn get_previous_blocks_txs() -> Vec<Vec<u8>> {
@@ -1469,6 +1626,26 @@ impl<T: Config> Pallet<T> {
 			.collect::<Vec<_>>()
 	}
 
+	/// returns hashes of txs enqueued in the current block as deposited in the digest by
+	/// [`Pallet::enqueue_txs`], `None` if current block did not enqueue any txs
+	pub fn enqueued_txs_hashes() -> Option<Vec<T::Hash>> {
+		let current_block = Self::block_number();
+		if !<StorageQueue<T>>::get().iter().any(|(nr, _, _)| *nr == current_block) {
+			return None
+		}
+		Self::digest()
+			.logs
+			.iter()
+			.filter_map(|item| item.as_other())
+			.find_map(|data| Vec::<T::Hash>::decode_all(&mut &data[..]).ok())
+	}
+
+	/// returns storage key of the storage queue, used for generating storage proofs of
+	/// enqueued txs
+	pub fn storage_queue_key() -> Vec<u8> {
+		<StorageQueue<T>>::hashed_key().to_vec()
+	}
+
 	/// Dequeue particular number of txs from storage queue.
 	/// It modifies the storage
 	pub fn pop_txs(mut len: usize) -> Vec<EncodedTx> {
@@ -1511,6 +1688,11 @@ impl<T: Config> Pallet<T> {
 			log::debug!( target: "runtime::ver", "{} blocks to be removed from queue, len {} -> {}", fully_executed_blocks, size_before, queue.len());
 		}
 		<StorageQueue<T>>::put(queue);
+		for who in result.iter().filter_map(|(who, _)| who.as_ref()) {
+			EnqueuedTxsCount::<T>::mutate_exists(who, |count| {
+				*count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+			});
+		}
 		result.iter().map(|(_, data)| data.clone()).collect()
 	}
 
@@ -24,9 +24,13 @@
 //! `frame_system` tracks consumption of each of these resources separately for each
 //! `DispatchClass`. This module contains configuration object for both resources,
 //! which should be passed to `frame_system` configuration when runtime is being set up.
+//!
+//! Additionally, [`StorageQueueLimits`] limit txs held in the storage queue, that are executed
+//! in the following blocks.
 
 use frame_support::{
 	dispatch::{DispatchClass, OneOrMany, PerDispatchClass},
+	traits::Get,
 	weights::{constants, Weight},
 };
 use scale_info::TypeInfo;
@@ -69,6 +73,47 @@ impl BlockLength {
 	}
 }
 
+/// Storage queue limits configuration.
+///
+/// Limits are enforced by `enqueue_txs` inherent and can be overridden by root, without a runtime
+/// upgrade.
+#[derive(
+	RuntimeDebug,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	codec::Encode,
+	codec::Decode,
+	codec::MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct StorageQueueLimits {
+	/// Maximal number of blocks whose enqueued txs are held in the storage queue at the same time.
+	///
+	/// New txs can not be enqueued while the queue is full. Can not exceed
+	/// [`crate::MaxStorageQueueDepth`].
+	pub depth: u32,
+	/// Maximal number of txs enqueued by a single `enqueue_txs` inherent.
+	pub max_txs: u32,
+	/// Maximal total length in bytes of encoded txs enqueued by a single `enqueue_txs` inherent.
+	pub max_len: u32,
+}
+
+impl Default for StorageQueueLimits {
+	fn default() -> Self {
+		Self { depth: 2, max_txs: u32::MAX, max_len: u32::MAX }
+	}
+}
+
+impl StorageQueueLimits {
+	/// Checks that the queue can hold at least one, and at most [`crate::MaxStorageQueueDepth`]
+	/// blocks.
+	pub fn is_valid(&self) -> bool {
+		self.depth > 0 && self.depth <= <crate::MaxStorageQueueDepth as Get<u32>>::get()
+	}
+}
+
 #[derive(Default, RuntimeDebug)]
 pub struct ValidationErrors {
 	pub has_errors: bool,
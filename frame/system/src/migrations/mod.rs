@@ -120,3 +120,80 @@ pub fn migrate_from_dual_to_triple_ref_count<V: V2ToV3, T: Config>() -> Weight {
 	<UpgradedToTripleRefCount<T>>::put(true);
 	Weight::MAX
 }
+
+/// Migrations introducing [`crate::EnqueuedTxsCount`].
+pub mod v1 {
+	use super::*;
+	use crate::{EnqueuedTxsCount, StorageQueue};
+	use frame_support::traits::{Get, GetStorageVersion, OnRuntimeUpgrade};
+	use sp_std::collections::btree_map::BTreeMap;
+
+	/// Counts txs of every signer that are held in the storage queue and have not been
+	/// executed yet, as they were enqueued before [`EnqueuedTxsCount`] was kept in sync.
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config> MigrateToV1<T> {
+		fn pending_txs_counts() -> BTreeMap<T::AccountId, u32> {
+			let mut counts = BTreeMap::<T::AccountId, u32>::new();
+			for (_, index, txs) in StorageQueue::<T>::get() {
+				let pending = txs.into_iter().skip(index.unwrap_or_default() as usize);
+				for who in pending.filter_map(|(who, _)| who) {
+					let count = counts.entry(who).or_default();
+					*count = count.saturating_add(1);
+				}
+			}
+			counts
+		}
+	}
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() < 1,
+				"this migration can be deleted"
+			);
+			Ok(Vec::new())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let current = Pallet::<T>::current_storage_version();
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain > 0 {
+				log::info!(target: LOG_TARGET, "MigrateToV1 should be removed");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let counts = Self::pending_txs_counts();
+			let accounts = counts.len() as u64;
+			let _ = EnqueuedTxsCount::<T>::clear(u32::MAX, None);
+			for (who, count) in counts {
+				EnqueuedTxsCount::<T>::insert(who, count);
+			}
+
+			current.put::<Pallet<T>>();
+			log::info!(
+				target: LOG_TARGET,
+				"Counted txs held in the storage queue for {:?} accounts.",
+				accounts
+			);
+
+			T::DbWeight::get().reads_writes(2, accounts.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 1,
+				"this migration needs to be run"
+			);
+			frame_support::ensure!(
+				Self::pending_txs_counts() ==
+					EnqueuedTxsCount::<T>::iter().collect::<BTreeMap<_, _>>(),
+				"enqueued txs are not counted correctly"
+			);
+			Ok(())
+		}
+	}
+}
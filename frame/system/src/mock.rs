@@ -24,6 +24,7 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	transaction_validity::InvalidTransaction,
 	BuildStorage, Perbill,
 };
 
@@ -90,6 +91,24 @@ impl OnKilledAccount<u64> for RecordKilled {
 	}
 }
 
+/// Txs starting with this byte are rejected by [`MockEnqueuedTxsValidator`].
+pub const UNPAYABLE_TX_MARKER: u8 = 0xff;
+/// Storage key written by [`MockEnqueuedTxsValidator`] for every validated tx.
+pub const PREVALIDATED_TXS_KEY: &[u8] = b":prevalidated_txs";
+
+pub struct MockEnqueuedTxsValidator;
+impl EnqueuedTxsValidator<Test> for MockEnqueuedTxsValidator {
+	fn validate_enqueued_tx(tx: &[u8]) -> Result<(), TransactionValidityError> {
+		assert!(TxPrevalidation::<Test>::get());
+		let validated = storage::unhashed::get_or_default::<u32>(PREVALIDATED_TXS_KEY);
+		storage::unhashed::put(PREVALIDATED_TXS_KEY, &(validated + 1));
+		match tx.first() {
+			Some(&UNPAYABLE_TX_MARKER) => Err(InvalidTransaction::Payment.into()),
+			_ => Ok(()),
+		}
+	}
+}
+
 impl Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = RuntimeBlockWeights;
@@ -115,6 +134,9 @@ impl Config for Test {
 	type SS58Prefix = ();
 	type OnSetCode = ();
 	type MaxConsumers = ConstU32<16>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = MockEnqueuedTxsValidator;
 }
 
 pub type SysEvent = frame_system::Event<Test>;
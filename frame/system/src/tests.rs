@@ -24,6 +24,7 @@ use mock::{RuntimeOrigin, *};
 use sp_core::H256;
 use sp_runtime::{
 	traits::{BlakeTwo256, Header},
+	transaction_validity::InvalidTransaction,
 	DispatchError, DispatchErrorWithPostInfo,
 };
 
@@ -800,7 +801,7 @@ fn do_not_allow_for_storing_txs_when_queue_is_full() {
 			H256::from_str("0x0876d51dc2c109b2e9bca322e8706879d68984a8031a537d76d0b21693a3dbd0")
 				.unwrap();
 
-		for i in 1u32..<StorageQueueLimit as Get<u32>>::get() + 1 {
+		for i in 1u32..System::storage_queue_limits().depth + 1 {
 			println!("iter");
 			assert!(System::can_enqueue_txs());
 			System::enqueue_txs(RuntimeOrigin::none(), dummy_txs.clone()).unwrap();
@@ -820,3 +821,169 @@ fn do_not_allow_for_storing_txs_when_queue_is_full() {
 		System::enqueue_txs(RuntimeOrigin::none(), dummy_txs.clone()).unwrap();
 	});
 }
+
+#[test]
+fn storage_queue_limits_can_be_overridden_by_root() {
+	new_test_ext().execute_with(|| {
+		let limits = limits::StorageQueueLimits { depth: 1, max_txs: 10, max_len: 100 };
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+
+		assert_noop!(
+			System::set_storage_queue_limits(RuntimeOrigin::signed(1), Some(limits)),
+			DispatchError::BadOrigin
+		);
+		for depth in [0, <MaxStorageQueueDepth as Get<u32>>::get() + 1] {
+			assert_noop!(
+				System::set_storage_queue_limits(
+					RuntimeOrigin::root(),
+					Some(limits::StorageQueueLimits { depth, ..limits })
+				),
+				Error::<Test>::InvalidStorageQueueLimits
+			);
+		}
+
+		assert_eq!(System::storage_queue_limits(), Default::default());
+		assert_ok!(System::set_storage_queue_limits(RuntimeOrigin::root(), Some(limits)));
+		assert_eq!(System::storage_queue_limits(), limits);
+		System::assert_last_event(Event::StorageQueueLimitsSet { limits }.into());
+
+		System::enqueue_txs(RuntimeOrigin::none(), vec![(Some(0), vec![1])]).unwrap();
+		assert!(!System::can_enqueue_txs());
+
+		assert_ok!(System::set_storage_queue_limits(RuntimeOrigin::root(), None));
+		assert_eq!(System::storage_queue_limits(), Default::default());
+		assert!(System::can_enqueue_txs());
+	});
+}
+
+#[test]
+fn enqueue_txs_respects_storage_queue_limits() {
+	new_test_ext().execute_with(|| {
+		let limits = limits::StorageQueueLimits { depth: 2, max_txs: 2, max_len: 4 };
+		assert_ok!(System::set_storage_queue_limits(RuntimeOrigin::root(), Some(limits)));
+
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		assert_err!(
+			System::enqueue_txs(RuntimeOrigin::none(), vec![(Some(0), vec![1]); 3]),
+			Error::<Test>::TooManyEnqueuedTxs
+		);
+		System::finalize();
+
+		System::initialize(&2, &[0u8; 32].into(), &Default::default());
+		assert_err!(
+			System::enqueue_txs(RuntimeOrigin::none(), vec![(Some(0), vec![1, 2, 3, 4, 5])]),
+			Error::<Test>::EnqueuedTxsTooLarge
+		);
+		System::finalize();
+
+		System::initialize(&3, &[0u8; 32].into(), &Default::default());
+		assert_ok!(System::enqueue_txs(
+			RuntimeOrigin::none(),
+			vec![(Some(0), vec![1, 2]), (Some(1), vec![3, 4])]
+		));
+		assert_eq!(System::enqueued_blocks_count(), 1);
+	});
+}
+
+#[test]
+fn enqueue_txs_skips_txs_rejected_by_prevalidation() {
+	new_test_ext().execute_with(|| {
+		let rejected = vec![UNPAYABLE_TX_MARKER, 1];
+		let txs = vec![(Some(0), vec![1]), (Some(1), rejected.clone()), (Some(2), vec![2])];
+
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		assert_ok!(System::enqueue_txs(RuntimeOrigin::none(), txs));
+
+		assert_eq!(
+			StorageQueue::<Test>::get().last().unwrap().2,
+			vec![(Some(0), vec![1]), (Some(2), vec![2])]
+		);
+		assert_eq!(
+			System::events().into_iter().map(|e| e.event).collect::<Vec<_>>(),
+			vec![
+				RuntimeEvent::System(SysEvent::EnqueuedTxRejected {
+					hash: BlakeTwo256::hash(&rejected),
+					error: InvalidTransaction::Payment.into(),
+				}),
+				RuntimeEvent::System(SysEvent::TxsEnqueued { count: 2 }),
+			]
+		);
+
+		// prevalidation does not leave any traces in the state
+		assert!(!TxPrevalidation::<Test>::get());
+		assert_eq!(storage::unhashed::get::<u32>(PREVALIDATED_TXS_KEY), None);
+	});
+}
+
+#[test]
+fn enqueued_txs_hashes_are_read_from_digest() {
+	new_test_ext().execute_with(|| {
+		let txs = vec![(Some(0), b"first".to_vec()), (Some(1), b"second".to_vec())];
+
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		System::enqueue_txs(RuntimeOrigin::none(), vec![]).unwrap();
+		assert_eq!(System::enqueued_txs_hashes(), None);
+		System::finalize();
+
+		System::initialize(&2, &[0u8; 32].into(), &Default::default());
+		System::enqueue_txs(RuntimeOrigin::none(), txs.clone()).unwrap();
+		assert_eq!(
+			System::enqueued_txs_hashes(),
+			Some(
+				txs.iter()
+					.map(|(_, tx)| <BlakeTwo256 as sp_runtime::traits::Hash>::hash(&tx[..]))
+					.collect()
+			)
+		);
+	});
+}
+
+#[test]
+fn enqueued_txs_count_skips_executed_txs() {
+	new_test_ext().execute_with(|| {
+		let dummy_seed =
+			H256::from_str("0x0876d51dc2c109b2e9bca322e8706879d68984a8031a537d76d0b21693a3dbd0")
+				.unwrap();
+		let txs = vec![(Some(0), vec![1]), (Some(0), vec![2]), (Some(1), vec![3])];
+
+		System::initialize(&1, &[0u8; 32].into(), &Default::default());
+		System::enqueue_txs(RuntimeOrigin::none(), txs).unwrap();
+		System::finalize();
+		assert_eq!(System::enqueued_txs_count(&0), 2);
+		assert_eq!(System::enqueued_txs_count(&1), 1);
+
+		System::initialize(&2, &[0u8; 32].into(), &Default::default());
+		System::set_block_seed(&dummy_seed);
+		let executed = System::pop_txs(1);
+		assert_eq!(executed.len(), 1);
+		assert_eq!(System::enqueued_txs_count(&0) + System::enqueued_txs_count(&1), 2);
+
+		assert_eq!(System::pop_txs(2).len(), 2);
+		assert_eq!(System::enqueued_txs_count(&0), 0);
+		assert_eq!(System::enqueued_txs_count(&1), 0);
+		assert!(!EnqueuedTxsCount::<Test>::contains_key(0));
+		assert!(!EnqueuedTxsCount::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn migrate_to_v1_counts_txs_held_in_storage_queue() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<System>();
+		let txs = vec![(Some(0), vec![1]), (Some(0), vec![2]), (None, vec![3]), (Some(1), vec![4])];
+		// first tx of block 1 has been executed already, txs of block 2 are not shuffled yet
+		StorageQueue::<Test>::put::<BoundedVec<_, _>>(
+			vec![(1, Some(1), txs.clone()), (2, None, txs)].try_into().unwrap(),
+		);
+		EnqueuedTxsCount::<Test>::insert(2, 1);
+
+		migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(System::enqueued_txs_count(&0), 3);
+		assert_eq!(System::enqueued_txs_count(&1), 2);
+		assert!(!EnqueuedTxsCount::<Test>::contains_key(2));
+		assert_eq!(System::on_chain_storage_version(), 1);
+	});
+}
@@ -54,6 +54,10 @@ pub trait WeightInfo {
 	fn set_storage(i: u32, ) -> Weight;
 	fn kill_storage(i: u32, ) -> Weight;
 	fn kill_prefix(p: u32, ) -> Weight;
+	fn set_storage_queue_limits() -> Weight;
+	fn enqueue_txs(t: u32, b: u32, ) -> Weight;
+	fn set_block_seed(t: u32, ) -> Weight;
+	fn pop_txs(t: u32, ) -> Weight;
 }
 
 /// Weights for frame_system using the Substrate node and recommended hardware.
@@ -132,6 +136,76 @@ impl<T: crate::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
 			.saturating_add(Weight::from_parts(0, 70).saturating_mul(p.into()))
 	}
+	/// PLACEHOLDER: not generated. Estimated by hand until the benchmark is run with
+	/// `benchmark pallet` on the reference hardware.
+	/// Storage: System StorageQueueLimitsOverride (r:0 w:1)
+	/// Proof Skipped: System StorageQueueLimitsOverride (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Events (r:1 w:1)
+	/// Proof Skipped: System Events (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_storage_queue_limits() -> Weight {
+		Weight::from_parts(6_134_000, 495)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// PLACEHOLDER: not generated. Estimated by hand until the benchmark is run with
+	/// `benchmark pallet` on the reference hardware.
+	/// Storage: System DidStoreTxs (r:1 w:1)
+	/// Proof Skipped: System DidStoreTxs (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System StorageQueue (r:1 w:1)
+	/// Proof Skipped: System StorageQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System StorageQueueLimitsOverride (r:1 w:0)
+	/// Proof Skipped: System StorageQueueLimitsOverride (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System TxPrevalidation (r:0 w:1)
+	/// Proof Skipped: System TxPrevalidation (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Digest (r:1 w:1)
+	/// Proof Skipped: System Digest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Events (r:1 w:1)
+	/// Proof Skipped: System Events (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System EnqueuedTxsCount (r:1000 w:1000)
+	/// Proof Skipped: System EnqueuedTxsCount (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 1000]`.
+	/// The range of component `b` is `[0, 3932160]`.
+	fn enqueue_txs(t: u32, b: u32, ) -> Weight {
+		Weight::from_parts(10_211_000, 2614)
+			.saturating_add(Weight::from_parts(2_136_412, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_874, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(t.into())))
+	}
+	/// PLACEHOLDER: not generated. Estimated by hand until the benchmark is run with
+	/// `benchmark pallet` on the reference hardware.
+	/// Storage: System StorageQueue (r:1 w:1)
+	/// Proof Skipped: System StorageQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Number (r:1 w:0)
+	/// Proof: System Number (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: System BlockSeed (r:0 w:1)
+	/// Proof Skipped: System BlockSeed (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 1000]`.
+	fn set_block_seed(t: u32, ) -> Weight {
+		Weight::from_parts(5_412_000, 1489)
+			.saturating_add(Weight::from_parts(587_213, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(Weight::from_parts(0, 301).saturating_mul(t.into()))
+	}
+	/// PLACEHOLDER: not generated. Estimated by hand until the benchmark is run with
+	/// `benchmark pallet` on the reference hardware.
+	/// Storage: System StorageQueue (r:1 w:1)
+	/// Proof Skipped: System StorageQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System EnqueuedTxsCount (r:1000 w:1000)
+	/// Proof Skipped: System EnqueuedTxsCount (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 1000]`.
+	fn pop_txs(t: u32, ) -> Weight {
+		Weight::from_parts(3_876_000, 1489)
+			.saturating_add(Weight::from_parts(312_457, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(Weight::from_parts(0, 301).saturating_mul(t.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -209,4 +283,74 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
 			.saturating_add(Weight::from_parts(0, 70).saturating_mul(p.into()))
 	}
+	/// PLACEHOLDER: not generated. Estimated by hand until the benchmark is run with
+	/// `benchmark pallet` on the reference hardware.
+	/// Storage: System StorageQueueLimitsOverride (r:0 w:1)
+	/// Proof Skipped: System StorageQueueLimitsOverride (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Events (r:1 w:1)
+	/// Proof Skipped: System Events (max_values: Some(1), max_size: None, mode: Measured)
+	fn set_storage_queue_limits() -> Weight {
+		Weight::from_parts(6_134_000, 495)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// PLACEHOLDER: not generated. Estimated by hand until the benchmark is run with
+	/// `benchmark pallet` on the reference hardware.
+	/// Storage: System DidStoreTxs (r:1 w:1)
+	/// Proof Skipped: System DidStoreTxs (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System StorageQueue (r:1 w:1)
+	/// Proof Skipped: System StorageQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System StorageQueueLimitsOverride (r:1 w:0)
+	/// Proof Skipped: System StorageQueueLimitsOverride (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System TxPrevalidation (r:0 w:1)
+	/// Proof Skipped: System TxPrevalidation (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Digest (r:1 w:1)
+	/// Proof Skipped: System Digest (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Events (r:1 w:1)
+	/// Proof Skipped: System Events (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System EnqueuedTxsCount (r:1000 w:1000)
+	/// Proof Skipped: System EnqueuedTxsCount (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 1000]`.
+	/// The range of component `b` is `[0, 3932160]`.
+	fn enqueue_txs(t: u32, b: u32, ) -> Weight {
+		Weight::from_parts(10_211_000, 2614)
+			.saturating_add(Weight::from_parts(2_136_412, 0).saturating_mul(t.into()))
+			.saturating_add(Weight::from_parts(1_874, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(t.into())))
+	}
+	/// PLACEHOLDER: not generated. Estimated by hand until the benchmark is run with
+	/// `benchmark pallet` on the reference hardware.
+	/// Storage: System StorageQueue (r:1 w:1)
+	/// Proof Skipped: System StorageQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System Number (r:1 w:0)
+	/// Proof: System Number (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: System BlockSeed (r:0 w:1)
+	/// Proof Skipped: System BlockSeed (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 1000]`.
+	fn set_block_seed(t: u32, ) -> Weight {
+		Weight::from_parts(5_412_000, 1489)
+			.saturating_add(Weight::from_parts(587_213, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(Weight::from_parts(0, 301).saturating_mul(t.into()))
+	}
+	/// PLACEHOLDER: not generated. Estimated by hand until the benchmark is run with
+	/// `benchmark pallet` on the reference hardware.
+	/// Storage: System StorageQueue (r:1 w:1)
+	/// Proof Skipped: System StorageQueue (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: System EnqueuedTxsCount (r:1000 w:1000)
+	/// Proof Skipped: System EnqueuedTxsCount (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `t` is `[0, 1000]`.
+	fn pop_txs(t: u32, ) -> Weight {
+		Weight::from_parts(3_876_000, 1489)
+			.saturating_add(Weight::from_parts(312_457, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(t.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(t.into())))
+			.saturating_add(Weight::from_parts(0, 301).saturating_mul(t.into()))
+	}
 }
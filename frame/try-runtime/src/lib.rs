@@ -22,6 +22,7 @@
 
 pub use frame_support::traits::{TryStateSelect, UpgradeCheckSelect};
 use frame_support::weights::Weight;
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
 	/// Runtime api for testing the execution of a runtime upgrade.
@@ -49,5 +50,19 @@ sp_api::decl_runtime_apis! {
 			signature_check: bool,
 			try_state: TryStateSelect,
 		) -> Weight;
+
+		/// Execute the given VER block the same way collators do, but optionally disable
+		/// state-root and signature checks.
+		///
+		/// `public` is the sr25519 public key of the block author, the shuffling seed of the block
+		/// is verified against it.
+		#[api_version(2)]
+		fn execute_block_ver(
+			block: Block,
+			public: Vec<u8>,
+			state_root_check: bool,
+			signature_check: bool,
+			try_state: TryStateSelect,
+		) -> Weight;
 	}
 }
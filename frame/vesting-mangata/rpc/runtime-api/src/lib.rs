@@ -0,0 +1,55 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for vesting pallet.
+//!
+//! Runtimes that include `pallet-vesting-mangata` implement the API with the schedules at the
+//! current block:
+//!
+//! ```ignore
+//! impl VestingApi<Block, AccountId, TokenId, Balance, BlockNumber> for Runtime {
+//! 	fn vesting_schedules(
+//! 		who: AccountId,
+//! 	) -> Vec<VestingScheduleDetails<TokenId, Balance, BlockNumber>> {
+//! 		Vesting::vesting_schedules_at(&who, System::block_number())
+//! 	}
+//! }
+//! ```
+//!
+//! None of the runtimes in this repository include the pallet, as it requires a
+//! `MultiTokenLockableCurrency` implementation (e.g. `orml-tokens`).
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::traits::MaybeDisplay;
+use sp_std::vec::Vec;
+
+pub use pallet_vesting_mangata::{VestingInfo, VestingScheduleDetails};
+
+sp_api::decl_runtime_apis! {
+	pub trait VestingApi<AccountId, TokenId, Balance, BlockNumber> where
+		AccountId: Codec,
+		TokenId: Codec,
+		Balance: Codec + MaybeDisplay,
+		BlockNumber: Codec,
+	{
+		/// Vesting schedules of `who` across all tokens, along with amounts locked, vested and
+		/// claimable at the current block, see `Pallet::vesting_schedules_at`.
+		fn vesting_schedules(who: AccountId) -> Vec<VestingScheduleDetails<TokenId, Balance, BlockNumber>>;
+	}
+}
@@ -0,0 +1,176 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for the vesting pallet.
+//!
+//! Requires the runtime to implement [`VestingRuntimeApi`], the node registers it along with
+//! other RPC extensions:
+//!
+//! ```ignore
+//! module.merge(Vesting::<_, (Block, Balance)>::new(client.clone()).into_rpc())?;
+//! ```
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorCode, ErrorObject},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::{AtLeast32BitUnsigned, Block as BlockT, MaybeDisplay};
+
+pub use pallet_vesting_mangata_rpc_runtime_api::VestingApi as VestingRuntimeApi;
+
+#[rpc(client, server)]
+pub trait VestingApi<BlockHash, AccountId, TokenId, BlockNumber> {
+	/// Returns vesting schedules of `who` across all tokens, along with amounts locked, vested
+	/// and claimable at the given block (or the best block if not specified).
+	#[method(name = "vesting_getVestingSchedules")]
+	fn vesting_schedules(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<VestingSchedule<TokenId, BlockNumber>>>;
+}
+
+/// Vesting schedule along with amounts computed at particular block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingSchedule<TokenId, BlockNumber> {
+	/// token vested by the schedule
+	pub token_id: TokenId,
+	/// index of the schedule among schedules of the token
+	pub index: u32,
+	/// amount locked at schedule creation
+	pub total: NumberOrHex,
+	/// amount that gets unlocked every block after `starting_block`
+	pub per_block: NumberOrHex,
+	/// starting block for unlocking
+	pub starting_block: BlockNumber,
+	/// block before which nothing is unlocked
+	pub cliff: Option<BlockNumber>,
+	/// block at which the schedule is fully vested
+	pub ending_block: BlockNumber,
+	/// amount still locked by the schedule
+	pub locked: NumberOrHex,
+	/// amount vested by the schedule so far, schedules are not updated by `vest`, so it includes
+	/// amounts unlocked already
+	pub vested: NumberOrHex,
+	/// amount vested since the vesting lock was last updated, that can be unlocked with `vest`,
+	/// not known for locks that have not been updated since the runtime started to track it
+	pub claimable: Option<NumberOrHex>,
+}
+
+/// Provides RPC methods to query vesting schedules.
+pub struct Vesting<C, P> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Vesting<C, P> {
+	/// Creates a new instance of the Vesting Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+impl<C, Block, AccountId, TokenId, Balance, BlockNumber>
+	VestingApiServer<<Block as BlockT>::Hash, AccountId, TokenId, BlockNumber>
+	for Vesting<C, (Block, Balance)>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: VestingRuntimeApi<Block, AccountId, TokenId, Balance, BlockNumber>,
+	AccountId: Codec + Send + Sync + 'static,
+	TokenId: Codec + Send + Sync + 'static,
+	Balance: Codec
+		+ MaybeDisplay
+		+ AtLeast32BitUnsigned
+		+ Copy
+		+ TryInto<NumberOrHex>
+		+ Send
+		+ Sync
+		+ 'static,
+	BlockNumber: Codec + AtLeast32BitUnsigned + Copy + Send + Sync + 'static,
+{
+	fn vesting_schedules(
+		&self,
+		who: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<VestingSchedule<TokenId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash);
+
+		let schedules = api.vesting_schedules(at, who).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to query vesting schedules.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		let try_into_rpc_balance = |value: Balance| {
+			value.try_into().map_err(|_| {
+				JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+					ErrorCode::InvalidParams.code(),
+					format!("{} doesn't fit in NumberOrHex representation", value),
+					None::<()>,
+				)))
+			})
+		};
+
+		schedules
+			.into_iter()
+			.map(|details| {
+				Ok(VestingSchedule {
+					token_id: details.token_id,
+					index: details.index,
+					total: try_into_rpc_balance(details.schedule.locked())?,
+					per_block: try_into_rpc_balance(details.schedule.per_block())?,
+					starting_block: details.schedule.starting_block(),
+					cliff: details.schedule.cliff(),
+					ending_block: details.ending_block,
+					locked: try_into_rpc_balance(details.locked)?,
+					vested: try_into_rpc_balance(details.vested)?,
+					claimable: details.claimable.map(try_into_rpc_balance).transpose()?,
+				})
+			})
+			.collect()
+	}
+}
@@ -197,6 +197,44 @@ benchmarks! {
 		);
 	}
 
+	vested_transfer {
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 0 .. T::MAX_VESTING_SCHEDULES - 1;
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Tokens::make_free_balance_be(NATIVE_CURRENCY_ID.into(), &caller, BalanceOf::<T>::max_value());
+
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
+		// Give target existing locks
+		add_locks::<T>(&target, l as u8);
+		// Add one less than max vesting schedules
+		let mut expected_balance = add_vesting_schedules::<T>(target_lookup.clone(), s)?;
+
+		let transfer_amount = T::MinVestedTransfer::get();
+		let per_block = transfer_amount.checked_div(&20u32.into()).unwrap();
+		expected_balance += transfer_amount;
+
+		let vesting_schedule = VestingInfo::new_with_cliff(
+			transfer_amount,
+			per_block,
+			1u32.into(),
+			Some(10u32.into()),
+		);
+	}: _(RawOrigin::Signed(caller), NATIVE_CURRENCY_ID.into(), target_lookup, vesting_schedule)
+	verify {
+		assert_eq!(
+			expected_balance,
+			T::Tokens::free_balance(NATIVE_CURRENCY_ID.into(), &target),
+			"Transfer didn't happen",
+		);
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&target, NATIVE_CURRENCY_ID.into()),
+			Some(expected_balance),
+			"Lock not correctly updated",
+		);
+	}
+
 	force_vested_transfer {
 		let l in 0 .. MaxLocksOf::<T>::get() - 1;
 		let s in 0 .. T::MAX_VESTING_SCHEDULES - 1;
@@ -345,6 +383,58 @@ benchmarks! {
 		);
 	}
 
+	sudo_unlock_all_vesting_tokens {
+		let l in 0 .. MaxLocksOf::<T>::get() - 1;
+		let s in 1 .. T::MAX_VESTING_SCHEDULES;
+
+		let target: T::AccountId = account("target", 0, SEED);
+		let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
+		// Give target existing locks.
+		add_locks::<T>(&target, l as u8);
+		let expected_balance = add_vesting_schedules::<T>(target_lookup.clone(), s)?;
+
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&target, NATIVE_CURRENCY_ID.into()),
+			Some(expected_balance),
+			"Vesting schedule not added",
+		);
+	}: _(RawOrigin::Root, target_lookup, NATIVE_CURRENCY_ID.into())
+	verify {
+		assert_eq!(
+			Vesting::<T>::vesting_balance(&target, NATIVE_CURRENCY_ID.into()),
+			None,
+			"Vesting schedule was not removed",
+		);
+	}
+
+	sudo_unlock_all_vesting_tokens_batch {
+		let n in 1 .. T::MaxSudoUnlockBatch::get();
+
+		let mut accounts: Vec<T::AccountId> = Vec::new();
+		let mut targets: Vec<(<T::Lookup as StaticLookup>::Source, TokenIdOf<T>)> = Vec::new();
+		for i in 0 .. n {
+			let target: T::AccountId = account("target", i, SEED);
+			let target_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(target.clone());
+			// Every target has max locks and max vesting schedules.
+			add_locks::<T>(&target, (MaxLocksOf::<T>::get() - 1) as u8);
+			add_vesting_schedules::<T>(target_lookup.clone(), T::MAX_VESTING_SCHEDULES)?;
+
+			accounts.push(target);
+			targets.push((target_lookup, NATIVE_CURRENCY_ID.into()));
+		}
+		let targets: BoundedVec<_, T::MaxSudoUnlockBatch> =
+			targets.try_into().map_err(|_| "too many targets")?;
+	}: _(RawOrigin::Root, targets)
+	verify {
+		for target in accounts {
+			assert_eq!(
+				Vesting::<T>::vesting_balance(&target, NATIVE_CURRENCY_ID.into()),
+				None,
+				"Vesting schedule was not removed",
+			);
+		}
+	}
+
 	impl_benchmark_test_suite!(
 		Vesting,
 		crate::mock::ExtBuilder::default().existential_deposit(256).build(),
@@ -26,6 +26,9 @@
 //! pallet ensures that there is a lock in place preventing the balance to drop below the *unvested*
 //! amount for any reason other than transaction fee payment.
 //!
+//! Schedules can have a cliff, a block before which nothing unlocks. Amount vested by the cliff
+//! unlocks at once and the schedule continues linearly afterwards.
+//!
 //! As the amount vested increases over time, the amount unvested reduces. However, locks remain in
 //! place and explicit action is needed on behalf of the user to ensure that the amount locked is
 //! equivalent to the amount remaining to be vested. This is done through a dispatchable function,
@@ -41,10 +44,12 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `vested_transfer` - Transfer funds to another account, locking them under a vesting schedule.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -68,7 +73,7 @@ use scale_info::TypeInfo;
 use sp_runtime::{
 	traits::{
 		AtLeast32BitUnsigned, Bounded, CheckedSub, Convert, MaybeSerializeDeserialize, One,
-		Saturating, StaticLookup, Zero,
+		Saturating, StaticLookup, UniqueSaturatedFrom, UniqueSaturatedInto, Zero,
 	},
 	RuntimeDebug,
 };
@@ -93,6 +98,7 @@ const VESTING_ID: LockIdentifier = *b"vesting ";
 enum Releases {
 	V0,
 	V1,
+	V2,
 }
 
 impl Default for Releases {
@@ -145,6 +151,29 @@ impl<T: Config> Get<u32> for MaxVestingSchedulesGet<T> {
 	}
 }
 
+/// Vesting schedule of a particular token along with amounts computed at a given block, see
+/// [`Pallet::vesting_schedules_at`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct VestingScheduleDetails<TokenId, Balance, BlockNumber> {
+	/// Token vested by the schedule.
+	pub token_id: TokenId,
+	/// Index of the schedule among schedules of the token.
+	pub index: u32,
+	/// The vesting schedule.
+	pub schedule: VestingInfo<Balance, BlockNumber>,
+	/// Amount still locked by the schedule, `locked_at` the given block.
+	pub locked: Balance,
+	/// Amount vested by the schedule so far, i.e. `schedule.locked()` minus `locked`. Schedules
+	/// are not updated by `vest`, so it includes amounts unlocked already.
+	pub vested: Balance,
+	/// Amount vested by the schedule since the vesting lock was last updated, i.e. the amount
+	/// `vest` would unlock. `None` if the lock was last updated before the pallet started to
+	/// track it in [`LockUpdatedAt`].
+	pub claimable: Option<Balance>,
+	/// Block at which the schedule is fully vested.
+	pub ending_block: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -164,6 +193,11 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinVestedTransfer: Get<BalanceOf<Self>>;
 
+		/// Maximum number of `(account, token)` pairs unlocked by a single
+		/// `sudo_unlock_all_vesting_tokens_batch` call.
+		#[pallet::constant]
+		type MaxSudoUnlockBatch: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 
@@ -171,6 +205,28 @@ pub mod pallet {
 		const MAX_VESTING_SCHEDULES: u32;
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			migrations::v2::pre_migrate::<T>()
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get() == Releases::V1 {
+				StorageVersion::<T>::put(Releases::V2);
+				migrations::v2::migrate::<T>().saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			migrations::v2::post_migrate::<T>(state)
+		}
+	}
+
 	#[pallet::extra_constants]
 	impl<T: Config> Pallet<T> {
 		// TODO: rename to snake case after https://github.com/paritytech/substrate/issues/8826 fixed.
@@ -192,6 +248,18 @@ pub mod pallet {
 		BoundedVec<VestingInfo<BalanceOf<T>, T::BlockNumber>, MaxVestingSchedulesGet<T>>,
 	>;
 
+	/// Block at which the vesting lock of an account was last updated. Amounts vested by its
+	/// schedules up to this block have been unlocked already.
+	#[pallet::storage]
+	pub type LockUpdatedAt<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		TokenIdOf<T>,
+		T::BlockNumber,
+	>;
+
 	/// Storage version of the pallet.
 	///
 	/// New networks start with latest version, as determined by the genesis build.
@@ -218,7 +286,7 @@ pub mod pallet {
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
 			// Genesis uses the latest storage version.
-			StorageVersion::<T>::put(Releases::V1);
+			StorageVersion::<T>::put(Releases::V2);
 
 			// Generate initial vesting configuration
 			// * who - Account which we are generating vesting configuration for
@@ -238,6 +306,7 @@ pub mod pallet {
 
 				let reasons = WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE;
 				T::Tokens::set_lock(token_id, VESTING_ID, who, locked, reasons);
+				LockUpdatedAt::<T>::insert(who, token_id, T::BlockNumber::zero());
 			}
 		}
 	}
@@ -251,6 +320,12 @@ pub mod pallet {
 		VestingUpdated(T::AccountId, TokenIdOf<T>, BalanceOf<T>),
 		/// An \[account\] has become fully vested.
 		VestingCompleted(T::AccountId, TokenIdOf<T>),
+		/// All vesting schedules of an account were removed by a batched sudo unlock.
+		/// \[index, account, token_id\]
+		SudoUnlockItemSucceeded(u32, T::AccountId, TokenIdOf<T>),
+		/// An item of a batched sudo unlock failed, other items were still processed.
+		/// \[index, account, token_id, error\]
+		SudoUnlockItemFailed(u32, T::AccountId, TokenIdOf<T>, DispatchError),
 	}
 
 	/// Error for the vesting pallet.
@@ -276,6 +351,8 @@ pub mod pallet {
 		InvalidVestingIndex,
 		/// An overflow or underflow has occured
 		MathError,
+		/// Schedules that are waiting for different cliffs cannot be merged.
+		ScheduleCliffMismatch,
 	}
 
 	#[pallet::call]
@@ -380,6 +457,8 @@ pub mod pallet {
 		///   current_block)`.
 		/// - `ending_block`: `MAX(schedule1.ending_block, schedule2.ending_block)`.
 		/// - `locked`: `schedule1.locked_at(current_block) + schedule2.locked_at(current_block)`.
+		/// - `cliff`: the cliff of both schedules if it has not passed yet. Schedules that are
+		///   waiting for different cliffs cannot be merged.
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
@@ -414,10 +493,23 @@ pub mod pallet {
 			Ok(())
 		}
 
-		// TODO
-		// Needs to be benchmarked
+		/// Remove all vesting schedules of `target` for `token_id`, unlocking all of its funds.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// Emits `VestingCompleted`.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - DbWeight: 2 Reads, 2 Writes
+		///     - Reads: Vesting Storage, Balances Locks
+		///     - Writes: Vesting Storage, Balances Locks
+		/// # </weight>
 		#[pallet::call_index(4)]
-		#[pallet::weight(400_000_000u64)]
+		#[pallet::weight(T::WeightInfo::sudo_unlock_all_vesting_tokens(
+			MaxLocksOf::<T>::get(),
+			T::MAX_VESTING_SCHEDULES
+		))]
 		pub fn sudo_unlock_all_vesting_tokens(
 			origin: OriginFor<T>,
 			target: <T::Lookup as StaticLookup>::Source,
@@ -431,17 +523,130 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Create a vested transfer.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `target`: The account receiving the vested funds.
+		/// - `schedule`: The vesting schedule attached to the transfer, its `locked` amount must be
+		///   at least `MinVestedTransfer`.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// NOTE: This will unlock all schedules through the current block.
+		///
+		/// # <weight>
+		/// - `O(1)`.
+		/// - DbWeight: 3 Reads, 3 Writes
+		///     - Reads: Vesting Storage, Balances Locks, Target Account, [Sender Account]
+		///     - Writes: Vesting Storage, Balances Locks, Target Account, [Sender Account]
+		/// # </weight>
+		#[pallet::call_index(5)]
+		#[pallet::weight(
+			T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+		)]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			token_id: TokenIdOf<T>,
+			target: <T::Lookup as StaticLookup>::Source,
+			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			let transactor = ensure_signed(origin)?;
+			let transactor = <T::Lookup as StaticLookup>::unlookup(transactor);
+			Self::do_vested_transfer(transactor, target, schedule, token_id)
+		}
+
+		/// Remove all vesting schedules of every `(target, token_id)` pair in `targets`.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		///
+		/// All accounts are looked up first, the whole call fails if any of them cannot be.
+		/// Items are then processed independently, an item fails if its account is not vesting
+		/// `token_id`. Failed items do not affect the rest of the batch.
+		///
+		/// - `targets`: At most `MaxSudoUnlockBatch` pairs of accounts and tokens to unlock.
+		///
+		/// Emits `SudoUnlockItemSucceeded` or `SudoUnlockItemFailed` for every item.
+		///
+		/// # <weight>
+		/// - `O(N)` where `N` is the number of `targets`.
+		/// - DbWeight: 2N Reads, 2N Writes
+		///     - Reads: Vesting Storage, Balances Locks
+		///     - Writes: Vesting Storage, Balances Locks
+		/// # </weight>
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::sudo_unlock_all_vesting_tokens_batch(targets.len() as u32))]
+		pub fn sudo_unlock_all_vesting_tokens_batch(
+			origin: OriginFor<T>,
+			targets: BoundedVec<
+				(<T::Lookup as StaticLookup>::Source, TokenIdOf<T>),
+				T::MaxSudoUnlockBatch,
+			>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let targets = targets
+				.into_iter()
+				.map(|(target, token_id)| Ok((T::Lookup::lookup(target)?, token_id)))
+				.collect::<Result<Vec<_>, DispatchError>>()?;
+
+			for (index, (who, token_id)) in targets.into_iter().enumerate() {
+				let index = index as u32;
+				let event = match Self::try_unlock_all(who.clone(), token_id) {
+					Ok(()) => Event::<T>::SudoUnlockItemSucceeded(index, who, token_id),
+					Err(error) => Event::<T>::SudoUnlockItemFailed(index, who, token_id, error),
+				};
+				Self::deposit_event(event);
+			}
+
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Vesting schedules of `who` across all tokens, along with amounts locked, vested and
+	/// claimable at block `now`.
+	pub fn vesting_schedules_at(
+		who: &T::AccountId,
+		now: T::BlockNumber,
+	) -> Vec<VestingScheduleDetails<TokenIdOf<T>, BalanceOf<T>, T::BlockNumber>> {
+		Vesting::<T>::iter_prefix(who)
+			.flat_map(|(token_id, schedules)| {
+				let lock_updated_at = LockUpdatedAt::<T>::get(who, token_id);
+				schedules.into_iter().enumerate().map(move |(index, schedule)| {
+					let locked = schedule.locked_at::<T::BlockNumberToBalance>(now);
+					let ending_block = T::BlockNumber::unique_saturated_from(
+						UniqueSaturatedInto::<u128>::unique_saturated_into(
+							schedule.ending_block_as_balance::<T::BlockNumberToBalance>(),
+						),
+					);
+					VestingScheduleDetails {
+						token_id,
+						index: index as u32,
+						schedule,
+						locked,
+						vested: schedule.locked().saturating_sub(locked),
+						claimable: lock_updated_at.map(|updated_at| {
+							schedule
+								.locked_at::<T::BlockNumberToBalance>(updated_at)
+								.saturating_sub(locked)
+						}),
+						ending_block,
+					}
+				})
+			})
+			.collect()
+	}
+
 	// Create a new `VestingInfo`, based off of two other `VestingInfo`s.
 	// NOTE: We assume both schedules have had funds unlocked up through the current block.
 	fn merge_vesting_info(
 		now: T::BlockNumber,
 		schedule1: VestingInfo<BalanceOf<T>, T::BlockNumber>,
 		schedule2: VestingInfo<BalanceOf<T>, T::BlockNumber>,
-	) -> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>> {
+	) -> Result<Option<VestingInfo<BalanceOf<T>, T::BlockNumber>>, DispatchError> {
 		let schedule1_ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>();
 		let schedule2_ending_block = schedule2.ending_block_as_balance::<T::BlockNumberToBalance>();
 		let now_as_balance = T::BlockNumberToBalance::convert(now);
@@ -449,15 +654,20 @@ impl<T: Config> Pallet<T> {
 		// Check if one or both schedules have ended.
 		match (schedule1_ending_block <= now_as_balance, schedule2_ending_block <= now_as_balance) {
 			// If both schedules have ended, we don't merge and exit early.
-			(true, true) => return None,
+			(true, true) => return Ok(None),
 			// If one schedule has ended, we treat the one that has not ended as the new
 			// merged schedule.
-			(true, false) => return Some(schedule2),
-			(false, true) => return Some(schedule1),
+			(true, false) => return Ok(Some(schedule2)),
+			(false, true) => return Ok(Some(schedule1)),
 			// If neither schedule has ended don't exit early.
 			_ => {},
 		}
 
+		// The merged schedule has a single cliff, applying it to the whole merged amount would
+		// either unlock funds before their cliff or lock funds that are not subject to it.
+		let cliff = schedule1.pending_cliff(now);
+		ensure!(cliff == schedule2.pending_cliff(now), Error::<T>::ScheduleCliffMismatch);
+
 		let locked = schedule1
 			.locked_at::<T::BlockNumberToBalance>(now)
 			.saturating_add(schedule2.locked_at::<T::BlockNumberToBalance>(now));
@@ -478,10 +688,10 @@ impl<T: Config> Pallet<T> {
 			(locked / duration).max(One::one())
 		};
 
-		let schedule = VestingInfo::new(locked, per_block, starting_block);
+		let schedule = VestingInfo::new_with_cliff(locked, per_block, starting_block, cliff);
 		debug_assert!(schedule.is_valid(), "merge_vesting_info schedule validation check failed");
 
-		Some(schedule)
+		Ok(Some(schedule))
 	}
 
 	// Execute a vested transfer from `source` to `target` with the given `schedule`.
@@ -525,18 +735,43 @@ impl<T: Config> Pallet<T> {
 		)?;
 
 		// We can't let this fail because the currency transfer has already happened.
-		let res = Self::add_vesting_schedule(
-			&target,
-			schedule.locked(),
-			schedule.per_block(),
-			schedule.starting_block(),
-			token_id,
-		);
+		let res = Self::do_add_vesting_schedule(&target, schedule, token_id);
 		debug_assert!(res.is_ok(), "Failed to add a schedule when we had to succeed.");
 
 		Ok(())
 	}
 
+	/// Add `schedule` to `who` and update the lock accordingly, see
+	/// [`MultiTokenVestingSchedule::add_vesting_schedule`].
+	fn do_add_vesting_schedule(
+		who: &T::AccountId,
+		schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		token_id: TokenIdOf<T>,
+	) -> DispatchResult {
+		if schedule.locked().is_zero() {
+			return Ok(())
+		}
+
+		// Check for `per_block` or `locked` of 0.
+		if !schedule.is_valid() {
+			return Err(Error::<T>::InvalidScheduleParams.into())
+		};
+
+		let mut schedules = Self::vesting(who, token_id).unwrap_or_default();
+
+		// NOTE: we must push the new schedule so that `exec_action`
+		// will give the correct new locked amount.
+		ensure!(schedules.try_push(schedule).is_ok(), Error::<T>::AtMaxVestingSchedules);
+
+		let (schedules, locked_now) =
+			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
+
+		Self::write_vesting(&who, schedules, token_id)?;
+		Self::write_lock(who, locked_now, token_id);
+
+		Ok(())
+	}
+
 	/// Iterate through the schedules to track the current locked amount and
 	/// filter out completed and specified schedules.
 	///
@@ -574,10 +809,12 @@ impl<T: Config> Pallet<T> {
 	fn write_lock(who: &T::AccountId, total_locked_now: BalanceOf<T>, token_id: TokenIdOf<T>) {
 		if total_locked_now.is_zero() {
 			T::Tokens::remove_lock(token_id, VESTING_ID, who);
+			LockUpdatedAt::<T>::remove(who, token_id);
 			Self::deposit_event(Event::<T>::VestingCompleted(who.clone(), token_id));
 		} else {
 			let reasons = WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE;
 			T::Tokens::set_lock(token_id, VESTING_ID, who, total_locked_now, reasons);
+			LockUpdatedAt::<T>::insert(who, token_id, <frame_system::Pallet<T>>::block_number());
 			Self::deposit_event(Event::<T>::VestingUpdated(
 				who.clone(),
 				token_id,
@@ -627,6 +864,14 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Unlock all funds of a single item of a batched sudo unlock. All checks are done before
+	/// anything is written, so a failed item leaves no changes behind.
+	fn try_unlock_all(who: T::AccountId, token_id: TokenIdOf<T>) -> DispatchResult {
+		ensure!(Vesting::<T>::contains_key(&who, token_id), Error::<T>::NotVesting);
+
+		Self::do_unlock_all(who, token_id)
+	}
+
 	/// Execute a `VestingAction` against the given `schedules`. Returns the updated schedules
 	/// and locked amount.
 	fn exec_action(
@@ -647,7 +892,7 @@ impl<T: Config> Pallet<T> {
 					Self::report_schedule_updates(schedules.to_vec(), action);
 
 				let now = <frame_system::Pallet<T>>::block_number();
-				if let Some(new_schedule) = Self::merge_vesting_info(now, schedule1, schedule2) {
+				if let Some(new_schedule) = Self::merge_vesting_info(now, schedule1, schedule2)? {
 					// Merging created a new schedule so we:
 					// 1) need to add it to the accounts vesting schedule collection,
 					schedules.push(new_schedule);
@@ -682,6 +927,8 @@ pub trait MultiTokenVestingLocks<AccountId, BlockNumber> {
 	/// Finds a vesting schedule with locked_at value greater than unlock_amount
 	/// Removes that old vesting schedule, adds a new one with new_locked and new_per_block
 	/// reflecting old locked_at - unlock_amount, to be unlocked by old ending block.
+	/// Schedules that are still waiting for their cliff are skipped, see
+	/// `unlock_tokens_with_cliff`
 	/// This does not transfer funds
 	fn unlock_tokens(
 		who: &AccountId,
@@ -692,9 +939,33 @@ pub trait MultiTokenVestingLocks<AccountId, BlockNumber> {
 		DispatchError,
 	>;
 
+	/// Same as `unlock_tokens`, but also unlocks tokens from schedules that are still waiting
+	/// for their cliff. Returns the cliff of the old schedule along with its start and ending
+	/// block, so that the unlocked amount can be locked again with `lock_tokens_with_cliff`
+	/// without skipping the cliff.
+	/// Implementations that do not support cliffs don't need to override it.
+	fn unlock_tokens_with_cliff(
+		who: &AccountId,
+		token_id: <Self::Currency as MultiTokenCurrency<AccountId>>::CurrencyId,
+		unlock_amount: <Self::Currency as MultiTokenCurrency<AccountId>>::Balance,
+	) -> Result<
+		(
+			BlockNumber,
+			<Self::Currency as MultiTokenCurrency<AccountId>>::Balance,
+			Option<BlockNumber>,
+		),
+		DispatchError,
+	> {
+		Self::unlock_tokens(who, token_id, unlock_amount).map(
+			|(start_block, ending_block_as_balance)| (start_block, ending_block_as_balance, None),
+		)
+	}
+
 	/// Finds the vesting schedule with the provided index
 	/// Removes that old vesting schedule, adds a new one with new_locked and new_per_block
 	/// reflecting old locked_at - unlock_amount, to be unlocked by old ending block.
+	/// Fails if the schedule is still waiting for its cliff, see
+	/// `unlock_tokens_by_vesting_index_with_cliff`
 	/// This does not transfer funds
 	fn unlock_tokens_by_vesting_index(
 		who: &AccountId,
@@ -712,6 +983,36 @@ pub trait MultiTokenVestingLocks<AccountId, BlockNumber> {
 		DispatchError,
 	>;
 
+	/// Same as `unlock_tokens_by_vesting_index`, but also unlocks tokens from a schedule that is
+	/// still waiting for its cliff, and returns the cliff of the old schedule.
+	/// Implementations that do not support cliffs don't need to override it.
+	fn unlock_tokens_by_vesting_index_with_cliff(
+		who: &AccountId,
+		token_id: <Self::Currency as MultiTokenCurrency<AccountId>>::CurrencyId,
+		vesting_index: u32,
+		unlock_some_amount_or_all: Option<
+			<Self::Currency as MultiTokenCurrency<AccountId>>::Balance,
+		>,
+	) -> Result<
+		(
+			<Self::Currency as MultiTokenCurrency<AccountId>>::Balance,
+			BlockNumber,
+			<Self::Currency as MultiTokenCurrency<AccountId>>::Balance,
+			Option<BlockNumber>,
+		),
+		DispatchError,
+	> {
+		Self::unlock_tokens_by_vesting_index(
+			who,
+			token_id,
+			vesting_index,
+			unlock_some_amount_or_all,
+		)
+		.map(|(unlocked_amount, start_block, ending_block_as_balance)| {
+			(unlocked_amount, start_block, ending_block_as_balance, None)
+		})
+	}
+
 	/// Constructs a vesting schedule based on the given data starting from now
 	/// And places it into the appropriate (who, token_id) storage
 	/// This does not transfer funds
@@ -722,21 +1023,39 @@ pub trait MultiTokenVestingLocks<AccountId, BlockNumber> {
 		starting_block_as_balance: Option<BlockNumber>,
 		ending_block_as_balance: <Self::Currency as MultiTokenCurrency<AccountId>>::Balance,
 	) -> DispatchResult;
-}
 
-impl<T: Config> MultiTokenVestingLocks<T::AccountId, T::BlockNumber> for Pallet<T>
-where
-	BalanceOf<T>: MaybeSerializeDeserialize + Debug,
-	TokenIdOf<T>: MaybeSerializeDeserialize + Debug,
-{
-	type Currency = T::Tokens;
-	type Moment = T::BlockNumber;
+	/// Same as `lock_tokens`, but nothing unlocks before `cliff`, if provided
+	/// Implementations that do not support cliffs don't need to override it, they fail to lock
+	/// tokens with a cliff.
+	fn lock_tokens_with_cliff(
+		who: &AccountId,
+		token_id: <Self::Currency as MultiTokenCurrency<AccountId>>::CurrencyId,
+		lock_amount: <Self::Currency as MultiTokenCurrency<AccountId>>::Balance,
+		starting_block_as_balance: Option<BlockNumber>,
+		ending_block_as_balance: <Self::Currency as MultiTokenCurrency<AccountId>>::Balance,
+		cliff: Option<BlockNumber>,
+	) -> DispatchResult {
+		ensure!(cliff.is_none(), DispatchError::Other("Vesting cliffs are not supported"));
+		Self::lock_tokens(
+			who,
+			token_id,
+			lock_amount,
+			starting_block_as_balance,
+			ending_block_as_balance,
+		)
+	}
+}
 
-	fn unlock_tokens(
+impl<T: Config> Pallet<T> {
+	/// See [`MultiTokenVestingLocks::unlock_tokens_with_cliff`]. Schedules that are still
+	/// waiting for their cliff are only considered `with_cliff`, as otherwise the caller can't
+	/// lock the unlocked amount again without skipping the cliff.
+	fn do_unlock_tokens(
 		who: &T::AccountId,
 		token_id: TokenIdOf<T>,
 		unlock_amount: BalanceOf<T>,
-	) -> Result<(T::BlockNumber, BalanceOf<T>), DispatchError> {
+		with_cliff: bool,
+	) -> Result<(T::BlockNumber, BalanceOf<T>, Option<T::BlockNumber>), DispatchError> {
 		let now = <frame_system::Pallet<T>>::block_number();
 		// First we get the schedules of who
 		let schedules: Vec<VestingInfo<BalanceOf<T>, T::BlockNumber>> =
@@ -753,6 +1072,9 @@ where
 			BalanceOf<T>,
 		)> = None;
 		for (i, schedule) in schedules.clone().into_iter().enumerate() {
+			if !with_cliff && schedule.pending_cliff(now).is_some() {
+				continue
+			}
 			let schedule_locked_at = schedule.locked_at::<T::BlockNumberToBalance>(now);
 			match (schedule_locked_at >= unlock_amount, selected_schedule) {
 				(true, None) =>
@@ -816,7 +1138,13 @@ where
 			// .max in length_as_balance computation protects against unsafe div
 			let new_per_block = (new_locked / length_as_balance).max(One::one());
 
-			let vesting_schedule = VestingInfo::new(new_locked, new_per_block, start_block);
+			// The remaining amount still respects the cliff of the selected schedule.
+			let vesting_schedule = VestingInfo::new_with_cliff(
+				new_locked,
+				new_per_block,
+				start_block,
+				selected_schedule.1.cliff(),
+			);
 
 			ensure!(vesting_schedule.is_valid(), Error::<T>::InvalidScheduleParams);
 
@@ -832,16 +1160,20 @@ where
 		Self::write_vesting(&who, updated_schedules, token_id)?;
 		Self::write_lock(who, locked_now, token_id);
 
-		// Start block, end block
-		Ok((start_block, selected_schedule.3))
+		// Start block, end block, cliff
+		Ok((start_block, selected_schedule.3, selected_schedule.1.cliff()))
 	}
 
-	fn unlock_tokens_by_vesting_index(
+	/// See [`MultiTokenVestingLocks::unlock_tokens_by_vesting_index_with_cliff`], schedules that
+	/// are still waiting for their cliff are only unlocked `with_cliff`.
+	fn do_unlock_tokens_by_vesting_index(
 		who: &T::AccountId,
 		token_id: TokenIdOf<T>,
 		vesting_index: u32,
 		unlock_some_amount_or_all: Option<BalanceOf<T>>,
-	) -> Result<(BalanceOf<T>, T::BlockNumber, BalanceOf<T>), DispatchError> {
+		with_cliff: bool,
+	) -> Result<(BalanceOf<T>, T::BlockNumber, BalanceOf<T>, Option<T::BlockNumber>), DispatchError>
+	{
 		let now = <frame_system::Pallet<T>>::block_number();
 
 		// First we get the schedules of who
@@ -867,7 +1199,7 @@ where
 					schedule_locked_at >= unlock_amount
 				} else {
 					true
-				};
+				} && (with_cliff || schedule.pending_cliff(now).is_none());
 
 			match (i == vesting_index as usize && schedule_locked_at_satisfied, selected_schedule) {
 				(true, None) =>
@@ -919,7 +1251,13 @@ where
 			// .max in length_as_balance computation protects against unsafe div
 			let new_per_block = (new_locked / length_as_balance).max(One::one());
 
-			let vesting_schedule = VestingInfo::new(new_locked, new_per_block, start_block);
+			// The remaining amount still respects the cliff of the selected schedule.
+			let vesting_schedule = VestingInfo::new_with_cliff(
+				new_locked,
+				new_per_block,
+				start_block,
+				selected_schedule.1.cliff(),
+			);
 
 			ensure!(vesting_schedule.is_valid(), Error::<T>::InvalidScheduleParams);
 
@@ -935,16 +1273,18 @@ where
 		Self::write_vesting(&who, updated_schedules, token_id)?;
 		Self::write_lock(who, locked_now, token_id);
 
-		// Unlocked amount, start block, end block
-		Ok((unlocked_amount, start_block, selected_schedule.3))
+		// Unlocked amount, start block, end block, cliff
+		Ok((unlocked_amount, start_block, selected_schedule.3, selected_schedule.1.cliff()))
 	}
 
-	fn lock_tokens(
+	/// See [`MultiTokenVestingLocks::lock_tokens_with_cliff`].
+	fn do_lock_tokens(
 		who: &T::AccountId,
 		token_id: TokenIdOf<T>,
 		lock_amount: BalanceOf<T>,
 		starting_block: Option<T::BlockNumber>,
 		ending_block_as_balance: BalanceOf<T>,
+		cliff: Option<T::BlockNumber>,
 	) -> DispatchResult {
 		let starting_block: T::BlockNumber =
 			starting_block.unwrap_or(<frame_system::Pallet<T>>::block_number());
@@ -962,7 +1302,8 @@ where
 			.max(One::one());
 		let per_block = (lock_amount / length_as_balance).max(One::one());
 
-		let vesting_schedule = VestingInfo::new(lock_amount, per_block, starting_block);
+		let vesting_schedule =
+			VestingInfo::new_with_cliff(lock_amount, per_block, starting_block, cliff);
 		ensure!(vesting_schedule.is_valid(), Error::<T>::InvalidScheduleParams);
 
 		let mut schedules = Self::vesting(who, token_id).unwrap_or_default();
@@ -978,6 +1319,101 @@ where
 	}
 }
 
+impl<T: Config> MultiTokenVestingLocks<T::AccountId, T::BlockNumber> for Pallet<T>
+where
+	BalanceOf<T>: MaybeSerializeDeserialize + Debug,
+	TokenIdOf<T>: MaybeSerializeDeserialize + Debug,
+{
+	type Currency = T::Tokens;
+	type Moment = T::BlockNumber;
+
+	fn unlock_tokens(
+		who: &T::AccountId,
+		token_id: TokenIdOf<T>,
+		unlock_amount: BalanceOf<T>,
+	) -> Result<(T::BlockNumber, BalanceOf<T>), DispatchError> {
+		Self::do_unlock_tokens(who, token_id, unlock_amount, false)
+			.map(|(start_block, ending_block_as_balance, _)| (start_block, ending_block_as_balance))
+	}
+
+	fn unlock_tokens_with_cliff(
+		who: &T::AccountId,
+		token_id: TokenIdOf<T>,
+		unlock_amount: BalanceOf<T>,
+	) -> Result<(T::BlockNumber, BalanceOf<T>, Option<T::BlockNumber>), DispatchError> {
+		Self::do_unlock_tokens(who, token_id, unlock_amount, true)
+	}
+
+	fn unlock_tokens_by_vesting_index(
+		who: &T::AccountId,
+		token_id: TokenIdOf<T>,
+		vesting_index: u32,
+		unlock_some_amount_or_all: Option<BalanceOf<T>>,
+	) -> Result<(BalanceOf<T>, T::BlockNumber, BalanceOf<T>), DispatchError> {
+		Self::do_unlock_tokens_by_vesting_index(
+			who,
+			token_id,
+			vesting_index,
+			unlock_some_amount_or_all,
+			false,
+		)
+		.map(|(unlocked_amount, start_block, ending_block_as_balance, _)| {
+			(unlocked_amount, start_block, ending_block_as_balance)
+		})
+	}
+
+	fn unlock_tokens_by_vesting_index_with_cliff(
+		who: &T::AccountId,
+		token_id: TokenIdOf<T>,
+		vesting_index: u32,
+		unlock_some_amount_or_all: Option<BalanceOf<T>>,
+	) -> Result<(BalanceOf<T>, T::BlockNumber, BalanceOf<T>, Option<T::BlockNumber>), DispatchError>
+	{
+		Self::do_unlock_tokens_by_vesting_index(
+			who,
+			token_id,
+			vesting_index,
+			unlock_some_amount_or_all,
+			true,
+		)
+	}
+
+	fn lock_tokens(
+		who: &T::AccountId,
+		token_id: TokenIdOf<T>,
+		lock_amount: BalanceOf<T>,
+		starting_block: Option<T::BlockNumber>,
+		ending_block_as_balance: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::do_lock_tokens(
+			who,
+			token_id,
+			lock_amount,
+			starting_block,
+			ending_block_as_balance,
+			None,
+		)
+	}
+
+	fn lock_tokens_with_cliff(
+		who: &T::AccountId,
+		token_id: TokenIdOf<T>,
+		lock_amount: BalanceOf<T>,
+		starting_block: Option<T::BlockNumber>,
+		ending_block_as_balance: BalanceOf<T>,
+		cliff: Option<T::BlockNumber>,
+	) -> DispatchResult {
+		Self::do_lock_tokens(
+			who,
+			token_id,
+			lock_amount,
+			starting_block,
+			ending_block_as_balance,
+			cliff,
+		)
+	}
+}
+
 /// A vesting schedule over a currency. This allows a particular currency to have vesting limits
 /// applied to it.
 pub trait MultiTokenVestingSchedule<AccountId> {
@@ -1069,29 +1505,11 @@ where
 		starting_block: T::BlockNumber,
 		token_id: TokenIdOf<T>,
 	) -> DispatchResult {
-		if locked.is_zero() {
-			return Ok(())
-		}
-
-		let vesting_schedule = VestingInfo::new(locked, per_block, starting_block);
-		// Check for `per_block` or `locked` of 0.
-		if !vesting_schedule.is_valid() {
-			return Err(Error::<T>::InvalidScheduleParams.into())
-		};
-
-		let mut schedules = Self::vesting(who, token_id).unwrap_or_default();
-
-		// NOTE: we must push the new schedule so that `exec_action`
-		// will give the correct new locked amount.
-		ensure!(schedules.try_push(vesting_schedule).is_ok(), Error::<T>::AtMaxVestingSchedules);
-
-		let (schedules, locked_now) =
-			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
-
-		Self::write_vesting(&who, schedules, token_id)?;
-		Self::write_lock(who, locked_now, token_id);
-
-		Ok(())
+		Self::do_add_vesting_schedule(
+			who,
+			VestingInfo::new(locked, per_block, starting_block),
+			token_id,
+		)
 	}
 
 	// Ensure we can call `add_vesting_schedule` without error. This should always
@@ -0,0 +1,103 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the vesting pallet.
+
+use super::*;
+
+// Migration from linear schedules to schedules with an optional cliff.
+pub mod v2 {
+	use super::*;
+
+	/// `VestingInfo` as stored before cliffs were introduced.
+	#[derive(Encode, Decode, Clone, RuntimeDebug)]
+	pub struct OldVestingInfo<Balance, BlockNumber> {
+		pub locked: Balance,
+		pub per_block: Balance,
+		pub starting_block: BlockNumber,
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate<T: Config>() -> Result<Vec<u8>, &'static str> {
+		assert!(StorageVersion::<T>::get() == Releases::V1, "Storage version too high.");
+
+		let count = Vesting::<T>::iter_keys().count() as u32;
+
+		log::debug!(
+			target: "runtime::vesting",
+			"migration: Vesting storage version v2 PRE migration checks succesful!"
+		);
+
+		Ok(count.encode())
+	}
+
+	/// Migrate schedules to the new format, none of the existing schedules has a cliff.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut reads_writes = 0;
+
+		Vesting::<T>::translate::<Vec<OldVestingInfo<BalanceOf<T>, T::BlockNumber>>, _>(
+			|_who, _token_id, schedules| {
+				reads_writes += 1;
+				let v: Option<
+					BoundedVec<
+						VestingInfo<BalanceOf<T>, T::BlockNumber>,
+						MaxVestingSchedulesGet<T>,
+					>,
+				> = schedules
+					.into_iter()
+					.map(|old| VestingInfo::new(old.locked, old.per_block, old.starting_block))
+					.collect::<Vec<_>>()
+					.try_into()
+					.ok();
+
+				if v.is_none() {
+					log::warn!(
+						target: "runtime::vesting",
+						"migration: Failed to move vesting schedules into a BoundedVec"
+					);
+				}
+
+				v
+			},
+		);
+
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate<T: Config>(state: Vec<u8>) -> Result<(), &'static str> {
+		assert_eq!(StorageVersion::<T>::get(), Releases::V2);
+
+		let count: u32 =
+			Decode::decode(&mut &state[..]).map_err(|_| "invalid pre migrate state")?;
+		let mut migrated = 0u32;
+		for (_who, _token_id, schedules) in Vesting::<T>::iter() {
+			migrated += 1;
+			assert!(
+				schedules.iter().all(|s| s.cliff().is_none()),
+				"Migrated schedules should not have a cliff."
+			);
+		}
+		assert_eq!(count, migrated, "Vesting schedules were lost during migration.");
+
+		log::debug!(
+			target: "runtime::vesting",
+			"migration: Vesting storage version v2 POST migration checks successful!"
+		);
+		Ok(())
+	}
+}
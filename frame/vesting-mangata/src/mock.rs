@@ -80,6 +80,9 @@ impl frame_system::Config for Test {
 	type SystemWeightInfo = ();
 	type Version = ();
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = ();
 }
 parameter_type_with_key! {
 	pub ExistentialDeposits: |currency_id: TokenId| -> Balance {
@@ -116,6 +119,7 @@ impl orml_tokens::Config for Test {
 }
 parameter_types! {
 	pub const MinVestedTransfer: u64 = 256 * 2;
+	pub const MaxSudoUnlockBatch: u32 = 3;
 	pub static ExistentialDeposit: u64 = 0;
 }
 impl Config for Test {
@@ -124,6 +128,7 @@ impl Config for Test {
 	type Event = Event;
 	const MAX_VESTING_SCHEDULES: u32 = 3;
 	type MinVestedTransfer = MinVestedTransfer;
+	type MaxSudoUnlockBatch = MaxSudoUnlockBatch;
 	type WeightInfo = ();
 }
 
@@ -21,8 +21,8 @@ use sp_runtime::{traits::BadOrigin, SaturatedConversion};
 
 use super::{Vesting as VestingStorage, *};
 use crate::mock::{
-	usable_native_balance, Balance, BlockNumber, ExtBuilder, System, Test, TokenId, Tokens,
-	Vesting, NATIVE_CURRENCY_ID,
+	usable_native_balance, Balance, BlockNumber, ExtBuilder, MaxSudoUnlockBatch, System, Test,
+	TokenId, Tokens, Vesting, NATIVE_CURRENCY_ID,
 };
 use orml_tokens::MultiTokenCurrencyExtended;
 use orml_traits::MultiCurrency;
@@ -1146,12 +1146,55 @@ fn multiple_schedules_from_genesis_config_errors() {
 }
 
 #[test]
-fn build_genesis_has_storage_version_v1() {
+fn build_genesis_has_storage_version_v2() {
 	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
-		assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V1);
+		assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V2);
 	});
 }
 
+#[test]
+fn migrate_to_v2_keeps_schedules_without_cliff() {
+	ExtBuilder::default()
+		.existential_deposit(ED)
+		.vesting_genesis_config(vec![])
+		.build()
+		.execute_with(|| {
+			use migrations::v2::OldVestingInfo;
+			let old_schedule = |locked, per_block, starting_block: BlockNumber| OldVestingInfo {
+				locked,
+				per_block,
+				starting_block,
+			};
+			frame_support::storage::unhashed::put(
+				&VestingStorage::<Test>::hashed_key_for(&1, NATIVE_CURRENCY_ID),
+				&vec![old_schedule(ED * 5, 128, 0)],
+			);
+			frame_support::storage::unhashed::put(
+				&VestingStorage::<Test>::hashed_key_for(&2, NATIVE_CURRENCY_ID),
+				&vec![old_schedule(ED * 20, ED, 10), old_schedule(ED * 10, ED, 0)],
+			);
+			crate::pallet::StorageVersion::<Test>::put(Releases::V1);
+
+			<Vesting as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade();
+
+			assert_eq!(crate::pallet::StorageVersion::<Test>::get(), Releases::V2);
+			assert_eq!(
+				Vesting::vesting(&1, NATIVE_CURRENCY_ID).unwrap(),
+				vec![VestingInfo::new(ED * 5, 128, 0)]
+			);
+			assert_eq!(
+				Vesting::vesting(&2, NATIVE_CURRENCY_ID).unwrap(),
+				vec![VestingInfo::new(ED * 20, ED, 10), VestingInfo::new(ED * 10, ED, 0)]
+			);
+
+			// Migration runs only once.
+			assert_eq!(
+				<Vesting as frame_support::traits::OnRuntimeUpgrade>::on_runtime_upgrade(),
+				<Test as frame_system::Config>::DbWeight::get().reads(1)
+			);
+		});
+}
+
 #[test]
 fn merge_vesting_handles_per_block_0() {
 	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
@@ -1171,7 +1214,7 @@ fn merge_vesting_handles_per_block_0() {
 		);
 
 		let merged = VestingInfo::new(764, 1, 10);
-		assert_eq!(Vesting::merge_vesting_info(5, sched0, sched1), Some(merged));
+		assert_eq!(Vesting::merge_vesting_info(5, sched0, sched1), Ok(Some(merged)));
 	});
 }
 
@@ -1463,3 +1506,371 @@ fn unlock_tokens_works() {
 		);
 	});
 }
+
+#[test]
+fn vesting_schedules_at_reports_amounts_of_all_schedules() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let sched0 = VestingInfo::new(ED * 20, ED, 10);
+		let sched1 = VestingInfo::new(ED * 10, ED, 0);
+		assert_ok!(Vesting::do_vested_transfer(4u64, 2, sched1, NATIVE_CURRENCY_ID));
+
+		assert_eq!(
+			Vesting::vesting_schedules_at(&2, 15),
+			vec![
+				VestingScheduleDetails {
+					token_id: NATIVE_CURRENCY_ID,
+					index: 0,
+					schedule: sched0,
+					locked: ED * 15,
+					vested: ED * 5,
+					claimable: Some(ED * 5),
+					ending_block: 30,
+				},
+				VestingScheduleDetails {
+					token_id: NATIVE_CURRENCY_ID,
+					index: 1,
+					schedule: sched1,
+					locked: 0,
+					vested: ED * 10,
+					// the lock was updated by the transfer at block 1
+					claimable: Some(ED * 9),
+					ending_block: 10,
+				},
+			]
+		);
+
+		// Amounts are computed at the given block, not the current one.
+		assert_eq!(System::block_number(), 1);
+		assert_eq!(Vesting::vesting_schedules_at(&2, 10)[0].locked, ED * 20);
+
+		// Accounts without schedules have nothing vested.
+		assert_eq!(Vesting::vesting_schedules_at(&3, 15), vec![]);
+	});
+}
+
+#[test]
+fn vesting_schedules_at_excludes_unlocked_amounts_from_claimable() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let sched0 = VestingInfo::new(ED * 20, ED, 10);
+		assert_eq!(Vesting::vesting(&2, NATIVE_CURRENCY_ID).unwrap(), vec![sched0]);
+
+		System::set_block_number(12);
+		assert_ok!(Vesting::vest(Some(2).into(), NATIVE_CURRENCY_ID));
+		assert_eq!(LockUpdatedAt::<Test>::get(&2, NATIVE_CURRENCY_ID), Some(12));
+
+		let details = Vesting::vesting_schedules_at(&2, 15);
+		assert_eq!(details.len(), 1);
+		assert_eq!(details[0].vested, ED * 5);
+		assert_eq!(details[0].claimable, Some(ED * 3));
+
+		// Claimable amount is not known for locks written before it was tracked.
+		LockUpdatedAt::<Test>::remove(&2, NATIVE_CURRENCY_ID);
+		assert_eq!(Vesting::vesting_schedules_at(&2, 15)[0].claimable, None);
+
+		// Tracking stops once the lock is removed.
+		System::set_block_number(30);
+		assert_ok!(Vesting::vest(Some(2).into(), NATIVE_CURRENCY_ID));
+		assert_eq!(LockUpdatedAt::<Test>::get(&2, NATIVE_CURRENCY_ID), None);
+	});
+}
+
+#[test]
+fn vesting_info_with_cliff_works() {
+	// Cliff in the middle of the schedule unlocks amount vested so far at once.
+	let sched = VestingInfo::<Balance, BlockNumber>::new_with_cliff(100, 10, 0, Some(5));
+	assert_eq!(sched.cliff(), Some(5));
+	assert_eq!(sched.locked_at::<<Test as Config>::BlockNumberToBalance>(4), 100);
+	assert_eq!(sched.locked_at::<<Test as Config>::BlockNumberToBalance>(5), 50);
+	assert_eq!(sched.locked_at::<<Test as Config>::BlockNumberToBalance>(10), 0);
+	assert_eq!(sched.ending_block_as_balance::<<Test as Config>::BlockNumberToBalance>(), 10);
+
+	// Cliff after the end of the linear curve unlocks everything at the cliff.
+	let sched = VestingInfo::<Balance, BlockNumber>::new_with_cliff(100, 10, 0, Some(20));
+	assert_eq!(sched.locked_at::<<Test as Config>::BlockNumberToBalance>(19), 100);
+	assert_eq!(sched.locked_at::<<Test as Config>::BlockNumberToBalance>(20), 0);
+	assert_eq!(sched.ending_block_as_balance::<<Test as Config>::BlockNumberToBalance>(), 20);
+
+	// Cliff before the start has no effect.
+	let sched = VestingInfo::<Balance, BlockNumber>::new_with_cliff(100, 10, 5, Some(1));
+	let linear = VestingInfo::<Balance, BlockNumber>::new(100, 10, 5);
+	for n in 0..20 {
+		assert_eq!(
+			sched.locked_at::<<Test as Config>::BlockNumberToBalance>(n),
+			linear.locked_at::<<Test as Config>::BlockNumberToBalance>(n)
+		);
+	}
+}
+
+#[test]
+fn signed_vested_transfer_with_cliff_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Account 4 should not have any vesting yet.
+		assert_eq!(Vesting::vesting(&4, NATIVE_CURRENCY_ID), None);
+		// Vesting over 20 blocks, nothing unlocks before block 20.
+		let schedule = VestingInfo::new_with_cliff(ED * 5, 64, 10, Some(20));
+		assert_ok!(Vesting::vested_transfer(Some(3).into(), NATIVE_CURRENCY_ID, 4, schedule));
+
+		assert_eq!(Vesting::vesting(&4, NATIVE_CURRENCY_ID).unwrap(), vec![schedule]);
+		assert_eq!(Tokens::free_balance(0u32, &3), ED * 25);
+		assert_eq!(Tokens::free_balance(0u32, &4), ED * 45);
+
+		// Schedule has started, but the cliff has not passed yet.
+		System::set_block_number(19);
+		assert_eq!(Vesting::vesting_balance(&4, NATIVE_CURRENCY_ID), Some(ED * 5));
+		assert_ok!(Vesting::vest(Some(4).into(), NATIVE_CURRENCY_ID));
+		assert_eq!(usable_native_balance::<Test>(4), ED * 40);
+
+		// Amount vested by the cliff unlocks at once.
+		System::set_block_number(20);
+		assert_eq!(Vesting::vesting_balance(&4, NATIVE_CURRENCY_ID), Some(ED * 5 - 64 * 10));
+
+		System::set_block_number(30);
+		assert_eq!(Vesting::vesting_balance(&4, NATIVE_CURRENCY_ID), Some(0));
+		vest_and_assert_no_vesting::<Test>(4);
+	});
+}
+
+#[test]
+fn signed_vested_transfer_correctly_fails() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let schedule = VestingInfo::<Balance, BlockNumber>::new(
+			<Test as Config>::MinVestedTransfer::get().into(),
+			64,
+			10,
+		);
+
+		// Only signed origins can create vested transfers.
+		assert_noop!(
+			Vesting::vested_transfer(RawOrigin::Root.into(), NATIVE_CURRENCY_ID, 4, schedule),
+			BadOrigin
+		);
+
+		// Fails due to too low transfer amount.
+		let schedule_too_low = VestingInfo::<Balance, BlockNumber>::new(
+			(<Test as Config>::MinVestedTransfer::get() - 1).into(),
+			64,
+			10,
+		);
+		assert_noop!(
+			Vesting::vested_transfer(Some(3).into(), NATIVE_CURRENCY_ID, 4, schedule_too_low),
+			Error::<Test>::AmountLow,
+		);
+
+		// `per_block` is 0, which would result in a schedule with infinite duration.
+		let schedule_per_block_0 = VestingInfo::<Balance, BlockNumber>::new(
+			<Test as Config>::MinVestedTransfer::get().into(),
+			0,
+			10,
+		);
+		assert_noop!(
+			Vesting::vested_transfer(Some(3).into(), NATIVE_CURRENCY_ID, 4, schedule_per_block_0),
+			Error::<Test>::InvalidScheduleParams,
+		);
+	});
+}
+
+#[test]
+fn merge_schedules_respects_cliff() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Account 2 should already have a vesting schedule.
+		let sched0 = VestingInfo::new(ED * 20, ED, 10);
+		assert_eq!(Vesting::vesting(&2, NATIVE_CURRENCY_ID).unwrap(), vec![sched0]);
+
+		// Vesting over 10 blocks, but nothing unlocks before block 25.
+		let sched1 = VestingInfo::new_with_cliff(ED * 10, ED, 10, Some(25));
+		assert_eq!(sched1.ending_block_as_balance::<<Test as Config>::BlockNumberToBalance>(), 25);
+		assert_ok!(Vesting::do_vested_transfer(3u64, 2, sched1, NATIVE_CURRENCY_ID));
+
+		// `sched0` is not subject to the cliff, so merging would lock it until block 25.
+		assert_noop!(
+			Vesting::merge_schedules(Some(2).into(), NATIVE_CURRENCY_ID, 0, 1),
+			Error::<Test>::ScheduleCliffMismatch
+		);
+
+		// Schedules waiting for the same cliff are merged and keep it.
+		let sched2 = VestingInfo::new_with_cliff(ED * 10, ED, 20, Some(25));
+		assert_ok!(Vesting::do_vested_transfer(3u64, 2, sched2, NATIVE_CURRENCY_ID));
+		assert_ok!(Vesting::merge_schedules(Some(2).into(), NATIVE_CURRENCY_ID, 1, 2));
+
+		let merged = VestingInfo::new_with_cliff(ED * 20, ED * 2, 20, Some(25));
+		assert_eq!(Vesting::vesting(&2, NATIVE_CURRENCY_ID).unwrap(), vec![sched0, merged]);
+
+		System::set_block_number(24);
+		assert_eq!(
+			Vesting::vesting_balance(&2, NATIVE_CURRENCY_ID),
+			Some(sched0.locked_at::<<Test as Config>::BlockNumberToBalance>(24) + ED * 20)
+		);
+
+		System::set_block_number(25);
+		assert_eq!(
+			Vesting::vesting_balance(&2, NATIVE_CURRENCY_ID),
+			Some(sched0.locked_at::<<Test as Config>::BlockNumberToBalance>(25) + ED * 10)
+		);
+	});
+}
+
+#[test]
+fn merge_schedules_drops_passed_cliff() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let sched0 = VestingInfo::new(ED * 20, ED, 10);
+		let sched1 = VestingInfo::new_with_cliff(ED * 20, ED, 10, Some(12));
+		assert_ok!(Vesting::do_vested_transfer(3u64, 2, sched1, NATIVE_CURRENCY_ID));
+
+		System::set_block_number(15);
+		assert_ok!(Vesting::merge_schedules(Some(2).into(), NATIVE_CURRENCY_ID, 0, 1));
+
+		// Both schedules are vesting linearly by now, so the cliff is not needed anymore.
+		let locked = sched0.locked_at::<<Test as Config>::BlockNumberToBalance>(15) * 2;
+		let merged = VestingInfo::new(locked, locked / 15, 15);
+		assert_eq!(Vesting::vesting(&2, NATIVE_CURRENCY_ID).unwrap(), vec![merged]);
+	});
+}
+
+#[test]
+fn unlock_tokens_respects_cliff() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Vesting over 10 blocks, nothing unlocks before block 8.
+		let sched = VestingInfo::new_with_cliff(10000, 1000, 1, Some(8));
+		assert_ok!(Vesting::do_vested_transfer(13u64, 999, sched, NATIVE_CURRENCY_ID));
+
+		System::set_block_number(5);
+		// Callers that can't lock the unlocked amount with the cliff again are refused.
+		assert_noop!(
+			<Pallet<Test> as MultiTokenVestingLocks<
+				<Test as frame_system::Config>::AccountId,
+				<Test as frame_system::Config>::BlockNumber,
+			>>::unlock_tokens(&999, NATIVE_CURRENCY_ID, 6000),
+			Error::<Test>::NoSuitableScheduleFound
+		);
+		assert_noop!(
+			<Pallet<Test> as MultiTokenVestingLocks<
+				<Test as frame_system::Config>::AccountId,
+				<Test as frame_system::Config>::BlockNumber,
+			>>::unlock_tokens_by_vesting_index(&999, NATIVE_CURRENCY_ID, 0, Some(6000)),
+			Error::<Test>::NoSuitableScheduleFound
+		);
+
+		assert_eq!(
+			<Pallet<Test> as MultiTokenVestingLocks<
+				<Test as frame_system::Config>::AccountId,
+				<Test as frame_system::Config>::BlockNumber,
+			>>::unlock_tokens_with_cliff(&999, NATIVE_CURRENCY_ID, 6000)
+			.unwrap(),
+			(5, 11, Some(8))
+		);
+
+		// The remaining amount is still locked until the cliff.
+		let remaining = VestingInfo::new_with_cliff(4000, 666, 5, Some(8));
+		assert_eq!(Vesting::vesting(&999, NATIVE_CURRENCY_ID).unwrap(), vec![remaining]);
+
+		System::set_block_number(7);
+		assert_eq!(Vesting::vesting_balance(&999, NATIVE_CURRENCY_ID), Some(4000));
+
+		System::set_block_number(8);
+		assert_eq!(Vesting::vesting_balance(&999, NATIVE_CURRENCY_ID), Some(4000 - 666 * 3));
+	});
+}
+
+#[test]
+fn relocked_tokens_keep_cliff() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		// Vesting over 10 blocks, nothing unlocks before block 8.
+		let sched = VestingInfo::new_with_cliff(10000, 1000, 1, Some(8));
+		assert_ok!(Vesting::do_vested_transfer(13u64, 999, sched, NATIVE_CURRENCY_ID));
+
+		System::set_block_number(5);
+		let (start_block, ending_block_as_balance, cliff) =
+			<Pallet<Test> as MultiTokenVestingLocks<
+				<Test as frame_system::Config>::AccountId,
+				<Test as frame_system::Config>::BlockNumber,
+			>>::unlock_tokens_with_cliff(&999, NATIVE_CURRENCY_ID, 6000)
+			.unwrap();
+
+		assert_ok!(<Pallet<Test> as MultiTokenVestingLocks<
+			<Test as frame_system::Config>::AccountId,
+			<Test as frame_system::Config>::BlockNumber,
+		>>::lock_tokens_with_cliff(
+			&999,
+			NATIVE_CURRENCY_ID,
+			6000,
+			Some(start_block),
+			ending_block_as_balance,
+			cliff
+		));
+
+		assert_eq!(
+			Vesting::vesting(&999, NATIVE_CURRENCY_ID).unwrap(),
+			vec![
+				VestingInfo::new_with_cliff(4000, 666, 5, Some(8)),
+				VestingInfo::new_with_cliff(6000, 1000, 5, Some(8)),
+			]
+		);
+
+		// Moving tokens between schedules does not unlock anything before the cliff.
+		System::set_block_number(7);
+		assert_eq!(Vesting::vesting_balance(&999, NATIVE_CURRENCY_ID), Some(10000));
+
+		System::set_block_number(8);
+		assert_eq!(
+			Vesting::vesting_balance(&999, NATIVE_CURRENCY_ID),
+			Some(4000 - 666 * 3 + 6000 - 1000 * 3)
+		);
+	});
+}
+
+#[test]
+fn sudo_unlock_all_vesting_tokens_batch_works() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		assert_eq!(usable_native_balance::<Test>(1), ED * 5);
+		assert_eq!(usable_native_balance::<Test>(2), 0);
+		// Account 3 is not vesting.
+		assert_eq!(Vesting::vesting(&3, NATIVE_CURRENCY_ID), None);
+
+		assert_ok!(Vesting::sudo_unlock_all_vesting_tokens_batch(
+			RawOrigin::Root.into(),
+			vec![(1, NATIVE_CURRENCY_ID), (3, NATIVE_CURRENCY_ID), (2, NATIVE_CURRENCY_ID)]
+				.try_into()
+				.unwrap(),
+		));
+
+		// Failed item does not affect the rest of the batch.
+		assert_eq!(Vesting::vesting(&1, NATIVE_CURRENCY_ID), None);
+		assert_eq!(Vesting::vesting(&2, NATIVE_CURRENCY_ID), None);
+		assert_eq!(usable_native_balance::<Test>(1), ED * 10);
+		assert_eq!(usable_native_balance::<Test>(2), ED * 20);
+
+		System::assert_has_event(crate::mock::Event::Vesting(Event::SudoUnlockItemSucceeded(
+			0,
+			1,
+			NATIVE_CURRENCY_ID,
+		)));
+		System::assert_has_event(crate::mock::Event::Vesting(Event::SudoUnlockItemFailed(
+			1,
+			3,
+			NATIVE_CURRENCY_ID,
+			Error::<Test>::NotVesting.into(),
+		)));
+		System::assert_last_event(crate::mock::Event::Vesting(Event::SudoUnlockItemSucceeded(
+			2,
+			2,
+			NATIVE_CURRENCY_ID,
+		)));
+	});
+}
+
+#[test]
+fn sudo_unlock_all_vesting_tokens_batch_correctly_fails() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		assert_noop!(
+			Vesting::sudo_unlock_all_vesting_tokens_batch(
+				Some(1).into(),
+				vec![(1, NATIVE_CURRENCY_ID)].try_into().unwrap(),
+			),
+			BadOrigin
+		);
+
+		// Batch can't exceed `MaxSudoUnlockBatch`.
+		let targets = vec![(1, NATIVE_CURRENCY_ID); MaxSudoUnlockBatch::get() as usize + 1];
+		assert!(BoundedVec::<_, MaxSudoUnlockBatch>::try_from(targets).is_err());
+	});
+}
@@ -28,6 +28,8 @@ pub struct VestingInfo<Balance, BlockNumber> {
 	per_block: Balance,
 	/// Starting block for unlocking(vesting).
 	starting_block: BlockNumber,
+	/// Block before which nothing unlocks, amount vested by then unlocks at once.
+	cliff: Option<BlockNumber>,
 }
 
 impl<Balance, BlockNumber> VestingInfo<Balance, BlockNumber>
@@ -41,7 +43,17 @@ where
 		per_block: Balance,
 		starting_block: BlockNumber,
 	) -> VestingInfo<Balance, BlockNumber> {
-		VestingInfo { locked, per_block, starting_block }
+		VestingInfo { locked, per_block, starting_block, cliff: None }
+	}
+
+	/// Instantiate a new `VestingInfo` that does not unlock anything before `cliff` block.
+	pub fn new_with_cliff(
+		locked: Balance,
+		per_block: Balance,
+		starting_block: BlockNumber,
+		cliff: Option<BlockNumber>,
+	) -> VestingInfo<Balance, BlockNumber> {
+		VestingInfo { locked, per_block, starting_block, cliff }
 	}
 
 	/// Validate parameters for `VestingInfo`. Note that this does not check
@@ -73,11 +85,25 @@ where
 		self.starting_block
 	}
 
+	/// Block before which nothing unlocks, if any.
+	pub fn cliff(&self) -> Option<BlockNumber> {
+		self.cliff
+	}
+
+	/// Cliff of the schedule, if it has not passed by block `n`.
+	pub fn pending_cliff(&self, n: BlockNumber) -> Option<BlockNumber> {
+		self.cliff.filter(|cliff| *cliff > n)
+	}
+
 	/// Amount locked at block `n`.
 	pub fn locked_at<BlockNumberToBalance: Convert<BlockNumber, Balance>>(
 		&self,
 		n: BlockNumber,
 	) -> Balance {
+		// Nothing unlocks before the cliff.
+		if self.cliff.map_or(false, |cliff| n < cliff) {
+			return self.locked
+		}
 		// Number of blocks that count toward vesting;
 		// saturating to 0 when n < starting_block.
 		let vested_block_count = n.saturating_sub(self.starting_block);
@@ -109,6 +135,11 @@ where
 				}
 		};
 
-		starting_block.saturating_add(duration)
+		let ending_block = starting_block.saturating_add(duration);
+		// Schedules with a cliff past the end of the linear curve unlock everything at the cliff.
+		match self.cliff {
+			Some(cliff) => ending_block.max(BlockNumberToBalance::convert(cliff)),
+			None => ending_block,
+		}
 	}
 }
@@ -49,9 +49,12 @@ pub trait WeightInfo {
 	fn vest_unlocked(l: u32, s: u32, ) -> Weight;
 	fn vest_other_locked(l: u32, s: u32, ) -> Weight;
 	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight;
+	fn vested_transfer(l: u32, s: u32, ) -> Weight;
 	fn force_vested_transfer(l: u32, s: u32, ) -> Weight;
 	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
 	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
+	fn sudo_unlock_all_vesting_tokens(l: u32, s: u32, ) -> Weight;
+	fn sudo_unlock_all_vesting_tokens_batch(n: u32, ) -> Weight;
 }
 
 /// Weights for pallet_vesting using the Substrate node and recommended hardware.
@@ -103,6 +106,18 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	// PLACEHOLDER: not generated. Reuses the `force_vested_transfer` weights until the
+	// `vested_transfer` benchmark is run against the reference hardware.
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Balances Locks (r:1 w:1)
+	fn vested_transfer(l: u32, s: u32, ) -> Weight {
+		Weight::from_parts(87_903_000 as u64, 0)
+			.saturating_add(Weight::from_parts(121_000 as u64, 0).saturating_mul(l as u64))
+			.saturating_add(Weight::from_parts(56_000 as u64, 0).saturating_mul(s as u64))
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
 	// Storage: Vesting Vesting (r:1 w:1)
 	// Storage: System Account (r:2 w:2)
 	// Storage: Balances Locks (r:1 w:1)
@@ -139,6 +154,27 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3 as u64))
 			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
+	// PLACEHOLDER: not generated. Estimated by hand until the
+	// `sudo_unlock_all_vesting_tokens` benchmark is run against the reference hardware.
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	fn sudo_unlock_all_vesting_tokens(l: u32, s: u32, ) -> Weight {
+		Weight::from_parts(38_512_000 as u64, 0)
+			.saturating_add(Weight::from_parts(118_000 as u64, 0).saturating_mul(l as u64))
+			.saturating_add(Weight::from_parts(96_000 as u64, 0).saturating_mul(s as u64))
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// PLACEHOLDER: not generated. Estimated by hand until the
+	// `sudo_unlock_all_vesting_tokens_batch` benchmark is run against the reference hardware.
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	fn sudo_unlock_all_vesting_tokens_batch(n: u32, ) -> Weight {
+		Weight::from_parts(11_284_000 as u64, 0)
+			.saturating_add(Weight::from_parts(47_926_000 as u64, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+			.saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -189,6 +225,18 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	// PLACEHOLDER: not generated. Reuses the `force_vested_transfer` weights until the
+	// `vested_transfer` benchmark is run against the reference hardware.
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Balances Locks (r:1 w:1)
+	fn vested_transfer(l: u32, s: u32, ) -> Weight {
+		Weight::from_parts(87_903_000 as u64, 0)
+			.saturating_add(Weight::from_parts(121_000 as u64, 0).saturating_mul(l as u64))
+			.saturating_add(Weight::from_parts(56_000 as u64, 0).saturating_mul(s as u64))
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
 	// Storage: Vesting Vesting (r:1 w:1)
 	// Storage: System Account (r:2 w:2)
 	// Storage: Balances Locks (r:1 w:1)
@@ -225,4 +273,25 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as u64))
 			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
+	// PLACEHOLDER: not generated. Estimated by hand until the
+	// `sudo_unlock_all_vesting_tokens` benchmark is run against the reference hardware.
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	fn sudo_unlock_all_vesting_tokens(l: u32, s: u32, ) -> Weight {
+		Weight::from_parts(38_512_000 as u64, 0)
+			.saturating_add(Weight::from_parts(118_000 as u64, 0).saturating_mul(l as u64))
+			.saturating_add(Weight::from_parts(96_000 as u64, 0).saturating_mul(s as u64))
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// PLACEHOLDER: not generated. Estimated by hand until the
+	// `sudo_unlock_all_vesting_tokens_batch` benchmark is run against the reference hardware.
+	// Storage: Vesting Vesting (r:1 w:1)
+	// Storage: Balances Locks (r:1 w:1)
+	fn sudo_unlock_all_vesting_tokens_batch(n: u32, ) -> Weight {
+		Weight::from_parts(11_284_000 as u64, 0)
+			.saturating_add(Weight::from_parts(47_926_000 as u64, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(n as u64)))
+			.saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(n as u64)))
+	}
 }
@@ -42,6 +42,49 @@ pub enum ApplyExtrinsicFailed {
 	Application(#[source] Box<dyn 'static + std::error::Error + Send + Sync>),
 }
 
+/// Step of VER (delayed execution) block building that failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerFailure {
+	/// Fetching txs that are ready for execution from the storage queue.
+	PreviousBlockTxs,
+	/// Checking if there is room for new txs in the storage queue.
+	CanEnqueueTxs,
+	/// Preparing the state used for prevalidation of txs collected from the pool.
+	Prevalidation,
+	/// Fetching limits of txs that can be enqueued in a single block.
+	EnqueueLimits,
+	/// Storing the shuffling seed.
+	StoreSeed,
+	/// Removing executed txs from the storage queue.
+	PopTxs,
+	/// Creating the `enqueue_txs` inherent.
+	CreateEnqueueTxsInherent,
+	/// Applying the `enqueue_txs` inherent.
+	ApplyEnqueueTxsInherent,
+}
+
+impl VerFailure {
+	/// Short name of the failed step, suitable for metric labels.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			VerFailure::PreviousBlockTxs => "previous_block_txs",
+			VerFailure::CanEnqueueTxs => "can_enqueue_txs",
+			VerFailure::Prevalidation => "prevalidation",
+			VerFailure::EnqueueLimits => "enqueue_limits",
+			VerFailure::StoreSeed => "store_seed",
+			VerFailure::PopTxs => "pop_txs",
+			VerFailure::CreateEnqueueTxsInherent => "create_enqueue_txs_inherent",
+			VerFailure::ApplyEnqueueTxsInherent => "apply_enqueue_txs_inherent",
+		}
+	}
+}
+
+impl std::fmt::Display for VerFailure {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
 /// Substrate Client error
 #[derive(Debug, thiserror::Error)]
 #[allow(missing_docs)]
@@ -162,6 +205,9 @@ pub enum Error {
 	#[error("Failed to set the chain head to a block that's too old.")]
 	SetHeadTooOld,
 
+	#[error("VER block building failed at {0}: {1}")]
+	Ver(VerFailure, #[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+
 	#[error(transparent)]
 	Application(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
 
@@ -218,4 +264,12 @@ impl Error {
 	{
 		Error::StateDatabase(format!("{:?}", e))
 	}
+
+	/// Returns the failed step if this is a VER block building error.
+	pub fn ver_failure(&self) -> Option<VerFailure> {
+		match self {
+			Error::Ver(failure, _) => Some(*failure),
+			_ => None,
+		}
+	}
 }
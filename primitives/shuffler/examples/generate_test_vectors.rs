@@ -0,0 +1,6 @@
+//! Prints shuffling test vectors as JSON, see [`extrinsic_shuffler::test_vectors`].
+
+fn main() {
+	let vectors = extrinsic_shuffler::test_vectors::generate();
+	println!("{}", serde_json::to_string_pretty(&vectors).expect("vectors are serializable; qed"));
+}
@@ -8,6 +8,13 @@ use sp_std::{collections::btree_map::BTreeMap, convert::TryInto};
 use sp_core::H256;
 use sp_std::{collections::vec_deque::VecDeque, vec::Vec};
 
+#[cfg(feature = "std")]
+mod proof;
+#[cfg(feature = "std")]
+pub use proof::{verify_shuffle_proof, ShuffleProofError};
+#[cfg(feature = "std")]
+pub mod test_vectors;
+
 pub struct Xoshiro256PlusPlus {
 	s: [u64; 4],
 }
@@ -144,6 +151,87 @@ pub fn shuffle_using_seed<A: sp_std::cmp::Ord + Encode + Clone, E: Encode + Clon
 	slots
 }
 
+/// Algorithm that figures out execution order of enqueued txs based on the shuffling seed.
+///
+/// Implementations need to be deterministic (same txs & seed always produce same order) and have
+/// to preserve relative order of txs signed by the same account (as they are ordered by nonce).
+/// Strategy used by the runtime is selected with `frame_system::Config::ShuffleStrategy`.
+pub trait ShuffleStrategy {
+	/// Returns `extrinsics` (paired with their signers) in execution order.
+	fn shuffle<A: sp_std::cmp::Ord + Encode + Clone, E: Encode + Clone>(
+		extrinsics: Vec<(A, E)>,
+		seed: &H256,
+	) -> Vec<(A, E)>;
+}
+
+/// Default strategy, see [`RoundRobinShuffle`].
+impl ShuffleStrategy for () {
+	fn shuffle<A: sp_std::cmp::Ord + Encode + Clone, E: Encode + Clone>(
+		extrinsics: Vec<(A, E)>,
+		seed: &H256,
+	) -> Vec<(A, E)> {
+		RoundRobinShuffle::shuffle(extrinsics, seed)
+	}
+}
+
+/// Txs are executed in rounds, every round contains single tx from every account that still has
+/// txs to execute, order within the round is random ([`shuffle_using_seed`]). Accounts that sent
+/// many txs can not delay execution of txs sent by others.
+pub struct RoundRobinShuffle;
+
+impl ShuffleStrategy for RoundRobinShuffle {
+	fn shuffle<A: sp_std::cmp::Ord + Encode + Clone, E: Encode + Clone>(
+		extrinsics: Vec<(A, E)>,
+		seed: &H256,
+	) -> Vec<(A, E)> {
+		shuffle_using_seed(extrinsics, seed)
+	}
+}
+
+/// Every possible execution order (that preserves order of txs from the same account) is equally
+/// likely. Next tx is picked from a random account, with probability proportional to the number of
+/// txs that account still has to execute, so every tx gets the same chance of being executed
+/// early regardless of its sender.
+pub struct UniformInterleaveShuffle;
+
+impl ShuffleStrategy for UniformInterleaveShuffle {
+	fn shuffle<A: sp_std::cmp::Ord + Encode + Clone, E: Encode + Clone>(
+		extrinsics: Vec<(A, E)>,
+		seed: &H256,
+	) -> Vec<(A, E)> {
+		let mut rng = Xoshiro256PlusPlus::from_seed(seed.to_fixed_bytes());
+		let mut remaining = extrinsics.len();
+		let mut grouped_extrinsics: BTreeMap<_, VecDeque<_>> =
+			extrinsics.into_iter().fold(BTreeMap::new(), |mut groups, (who, tx)| {
+				groups.entry(who).or_insert_with(VecDeque::new).push_back(tx);
+				groups
+			});
+
+		let mut result = Vec::with_capacity(remaining);
+		while remaining > 0 {
+			let mut pick = (rng.next_u64() % remaining as u64) as usize;
+			let who = grouped_extrinsics
+				.iter()
+				.find_map(|(who, txs)| {
+					if pick < txs.len() {
+						Some(who.clone())
+					} else {
+						pick -= txs.len();
+						None
+					}
+				})
+				.expect("pick < remaining == sum of all groups lengths; qed");
+			let txs = grouped_extrinsics.get_mut(&who).expect("key has just been found; qed");
+			result.push((who.clone(), txs.pop_front().expect("groups are never empty; qed")));
+			if txs.is_empty() {
+				grouped_extrinsics.remove(&who);
+			}
+			remaining -= 1;
+		}
+		result
+	}
+}
+
 #[derive(derive_more::Display, Debug)]
 pub enum Error {
 	#[display(fmt = "Cannot apply inherents")]
@@ -276,6 +364,68 @@ mod tests {
 		assert_eq!(shuffled1, shuffled2);
 	}
 
+	#[test]
+	fn unit_strategy_is_round_robin() {
+		let input = vec![(Some("A"), 1), (Some("A"), 2), (Some("B"), 11), (Some("C"), 21)];
+		let seed =
+			H256::from_str("0x0876d51dc2c109b2e9bca322e8706879d68984a8031a537d76d0b21693a3dbd0")
+				.unwrap();
+
+		assert_eq!(
+			<() as ShuffleStrategy>::shuffle(input.clone(), &seed),
+			shuffle_using_seed(input, &seed)
+		);
+	}
+
+	#[test]
+	fn uniform_interleave_preserves_order_of_account_txs() {
+		let input = vec![
+			(Some("A"), 1),
+			(Some("A"), 2),
+			(Some("A"), 3),
+			(Some("A"), 4),
+			(Some("B"), 11),
+			(Some("B"), 12),
+			(Some("C"), 21),
+			(None, 31),
+		];
+		let seed =
+			H256::from_str("0xff8611a4d212fc161dae19dd57f0f1ba9309f45d6207da13f2d3eab4c6839e91")
+				.unwrap();
+
+		let shuffled = UniformInterleaveShuffle::shuffle(input.clone(), &seed);
+
+		assert_eq!(shuffled, UniformInterleaveShuffle::shuffle(input.clone(), &seed));
+		assert_eq!(
+			shuffled.iter().cloned().collect::<BTreeSet<_>>(),
+			input.iter().cloned().collect::<BTreeSet<_>>()
+		);
+		for who in [Some("A"), Some("B"), Some("C"), None] {
+			let account_txs = |txs: &Vec<(Option<&str>, u32)>| {
+				txs.iter().filter(|(w, _)| *w == who).map(|(_, tx)| *tx).collect::<Vec<_>>()
+			};
+			assert_eq!(account_txs(&shuffled), account_txs(&input));
+		}
+	}
+
+	#[test]
+	fn uniform_interleave_does_not_favour_accounts_with_fewer_txs() {
+		// with round robin `B` tx is always executed as first or second one
+		let input = vec![(Some("A"), 1), (Some("A"), 2), (Some("A"), 3), (Some("B"), 11)];
+		let mut positions = HashMap::new();
+		for i in 0..1000u64 {
+			let seed = BlakeTwo256::hash_of(&i);
+			let shuffled = UniformInterleaveShuffle::shuffle(input.clone(), &seed);
+			let position = shuffled.iter().position(|(who, _)| *who == Some("B")).unwrap();
+			*positions.entry(position).or_insert(0) += 1;
+		}
+
+		assert_eq!(positions.len(), input.len());
+		for (_, count) in positions {
+			assert!(count > 200);
+		}
+	}
+
 	#[test]
 	fn check_shuffling_works_for_two_elements() {
 		let mut fy = FisherYates::from_bytes([
@@ -0,0 +1,200 @@
+//! Verification of [`ShuffleProof`]s, lets light clients (or anyone that follows block headers)
+//! check that particular tx was executed at given position of the shuffled order, without
+//! executing blocks.
+
+use crate::ShuffleStrategy;
+use codec::Decode;
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+use sp_state_machine::{read_proof_check, StorageProof};
+use ver_api::ShuffleProof;
+
+#[derive(derive_more::Display, Debug, PartialEq)]
+pub enum ShuffleProofError {
+	#[display(fmt = "Invalid shuffling seed: {}", _0)]
+	InvalidSeed(sp_ver::SeedVerificationError),
+	#[display(fmt = "Invalid storage proof: {}", _0)]
+	InvalidStorageProof(String),
+	#[display(fmt = "Storage queue not found in storage proof")]
+	MissingStorageQueue,
+	#[display(fmt = "Cannot decode storage queue")]
+	InvalidStorageQueue,
+	#[display(fmt = "No txs enqueued in block #{}", _0)]
+	MissingQueueEntry(u32),
+	#[display(fmt = "Enqueued txs do not match hashes from the digest")]
+	HashesMismatch,
+	#[display(fmt = "Tx not found at position {}", _0)]
+	PositionMismatch(u32),
+}
+
+/// Verifies that tx with hash `tx_hash` has been placed at `proof.position` of the shuffled order
+/// of txs enqueued in block `proof.block_number`.
+///
+/// - `state_root` - state root from the header of the block that enqueued txs
+/// - `author` - public key of the author of the following block, the one that provided the seed
+/// - `S` - shuffling strategy used by the runtime (`frame_system::Config::ShuffleStrategy`)
+///
+/// Verification is done against the storage queue entry proven by `proof.queue_proof`, hashes of
+/// the entry txs need to match `proof.enqueued_hashes`. It is up to the caller to make sure that
+/// `proof.enqueued_hashes` and `proof.prev_seed` match the digest and the seed of the header of
+/// block `proof.block_number`, and that `proof.seed` matches the seed of the following header.
+pub fn verify_shuffle_proof<S, AccountId, BlockNumber>(
+	proof: &ShuffleProof<H256>,
+	state_root: &H256,
+	author: &[u8],
+	tx_hash: &H256,
+) -> Result<(), ShuffleProofError>
+where
+	S: ShuffleStrategy,
+	AccountId: Decode + Ord + codec::Encode + Clone,
+	BlockNumber: Decode + PartialEq + From<u32>,
+{
+	sp_ver::verify_shuffling_seed(author, &proof.prev_seed, &proof.seed)
+		.map_err(ShuffleProofError::InvalidSeed)?;
+
+	let queue = read_proof_check::<BlakeTwo256, _>(
+		*state_root,
+		StorageProof::new(proof.queue_proof.iter().cloned()),
+		[&proof.queue_key],
+	)
+	.map_err(|e| ShuffleProofError::InvalidStorageProof(e.to_string()))?
+	.remove(&proof.queue_key)
+	.flatten()
+	.ok_or(ShuffleProofError::MissingStorageQueue)?;
+
+	let queue = Vec::<(BlockNumber, Option<u32>, Vec<(Option<AccountId>, Vec<u8>)>)>::decode(
+		&mut &queue[..],
+	)
+	.map_err(|_| ShuffleProofError::InvalidStorageQueue)?;
+
+	let block_number = BlockNumber::from(proof.block_number);
+	let txs = queue
+		.into_iter()
+		.find_map(|(nr, _, txs)| (nr == block_number).then_some(txs))
+		.ok_or(ShuffleProofError::MissingQueueEntry(proof.block_number))?;
+
+	if !txs
+		.iter()
+		.map(|(_, tx)| BlakeTwo256::hash(tx))
+		.eq(proof.enqueued_hashes.iter().cloned())
+	{
+		return Err(ShuffleProofError::HashesMismatch)
+	}
+
+	S::shuffle(txs, &proof.seed.seed)
+		.get(proof.position as usize)
+		.filter(|(_, tx)| BlakeTwo256::hash(tx) == *tx_hash)
+		.map(|_| ())
+		.ok_or(ShuffleProofError::PositionMismatch(proof.position))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::RoundRobinShuffle;
+	use codec::Encode;
+	use sp_core::{ShufflingSeed, H512};
+	use sp_state_machine::{prove_read, InMemoryBackend};
+	use std::collections::BTreeMap;
+
+	const QUEUE_KEY: &[u8] = b"storage_queue";
+
+	type Queue = Vec<(u32, Option<u32>, Vec<(Option<u64>, Vec<u8>)>)>;
+
+	fn keypair() -> schnorrkel::Keypair {
+		schnorrkel::MiniSecretKey::from_bytes(&[7u8; 32])
+			.unwrap()
+			.expand_to_keypair(schnorrkel::ExpansionMode::Ed25519)
+	}
+
+	fn next_seed(keypair: &schnorrkel::Keypair, prev_seed: &H256) -> ShufflingSeed {
+		let mut transcript = merlin::Transcript::new(b"shuffling_seed");
+		transcript.append_message(b"prev_seed", prev_seed.as_bytes());
+		let (inout, proof, _) = keypair.vrf_sign(transcript);
+		ShufflingSeed {
+			seed: H256::from_slice(&inout.to_output().to_bytes()),
+			proof: H512::from_slice(&proof.to_bytes()),
+		}
+	}
+
+	fn txs() -> Vec<(Option<u64>, Vec<u8>)> {
+		vec![(Some(1), vec![1]), (Some(1), vec![2]), (Some(2), vec![3]), (None, vec![4])]
+	}
+
+	/// builds proof of tx at `position`, returns it along with the state root & tx hash
+	fn build_proof(position: u32) -> (ShuffleProof<H256>, H256, H256) {
+		let queue: Queue = vec![(1, Some(0), vec![(Some(3), vec![9])]), (2, None, txs())];
+		let backend = InMemoryBackend::<BlakeTwo256>::from((
+			BTreeMap::from([(QUEUE_KEY.to_vec(), queue.encode())]),
+			Default::default(),
+		));
+		let state_root = *backend.root();
+		let queue_proof = prove_read(backend, [QUEUE_KEY]).unwrap();
+
+		let prev_seed = H256::repeat_byte(1);
+		let seed = next_seed(&keypair(), &prev_seed);
+		let shuffled = RoundRobinShuffle::shuffle(txs(), &seed.seed);
+		let tx_hash = BlakeTwo256::hash(&shuffled[position as usize].1);
+
+		let proof = ShuffleProof {
+			block_number: 2,
+			position,
+			prev_seed,
+			seed,
+			enqueued_hashes: txs().iter().map(|(_, tx)| BlakeTwo256::hash(tx)).collect(),
+			queue_key: QUEUE_KEY.to_vec(),
+			queue_proof: queue_proof.into_iter_nodes().collect(),
+		};
+		(proof, state_root, tx_hash)
+	}
+
+	fn verify(proof: &ShuffleProof<H256>, root: &H256, tx: &H256) -> Result<(), ShuffleProofError> {
+		verify_shuffle_proof::<RoundRobinShuffle, u64, u32>(
+			proof,
+			root,
+			&keypair().public.to_bytes(),
+			tx,
+		)
+	}
+
+	#[test]
+	fn valid_proof_is_accepted() {
+		for position in 0..txs().len() as u32 {
+			let (proof, root, tx) = build_proof(position);
+			assert_eq!(verify(&proof, &root, &tx), Ok(()));
+		}
+	}
+
+	#[test]
+	fn proof_with_wrong_position_is_rejected() {
+		let (mut proof, root, tx) = build_proof(0);
+		proof.position = 1;
+		assert_eq!(verify(&proof, &root, &tx), Err(ShuffleProofError::PositionMismatch(1)));
+	}
+
+	#[test]
+	fn proof_with_seed_not_derived_from_prev_seed_is_rejected() {
+		let (mut proof, root, tx) = build_proof(0);
+		proof.prev_seed = H256::repeat_byte(2);
+		assert_eq!(
+			verify(&proof, &root, &tx),
+			Err(ShuffleProofError::InvalidSeed(sp_ver::SeedVerificationError::BadProof))
+		);
+	}
+
+	#[test]
+	fn proof_with_tampered_hashes_is_rejected() {
+		let (mut proof, root, tx) = build_proof(0);
+		proof.enqueued_hashes.swap(0, 1);
+		assert_eq!(verify(&proof, &root, &tx), Err(ShuffleProofError::HashesMismatch));
+	}
+
+	#[test]
+	fn proof_against_different_state_root_is_rejected() {
+		let (proof, _, tx) = build_proof(0);
+		assert!(matches!(
+			verify(&proof, &H256::repeat_byte(3), &tx),
+			Err(ShuffleProofError::InvalidStorageProof(_))
+		));
+	}
+}
@@ -0,0 +1,130 @@
+//! Test vectors for implementations of [`shuffle_using_seed`] in other languages.
+//!
+//! Vectors are published in `res/test_vectors.json` and checked against the implementation by
+//! `tests/test_vectors.rs`. Whenever cases below change, regenerate them with:
+//!
+//! ```text
+//! cargo run -p extrinsic-shuffler --example generate_test_vectors > \
+//! 	primitives/shuffler/res/test_vectors.json
+//! ```
+//!
+//! Signers are 32 bytes long and compared as raw bytes, exactly like `AccountId32`. Unsigned txs
+//! (`null` signer) are grouped together and ordered before all signed txs.
+
+use crate::shuffle_using_seed;
+use serde::{Deserialize, Serialize};
+use sp_core::{Bytes, H256};
+use std::str::FromStr;
+
+/// Single tx along with its signer.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Tx {
+	/// tx signer, `None` for unsigned txs
+	pub who: Option<H256>,
+	/// encoded tx
+	pub tx: Bytes,
+}
+
+/// Single shuffling test case.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TestVector {
+	/// description of the case
+	pub name: String,
+	/// shuffling seed
+	pub seed: H256,
+	/// txs in the order they were enqueued
+	pub input: Vec<Tx>,
+	/// txs in the order they are executed
+	pub output: Vec<Tx>,
+}
+
+impl TestVector {
+	/// Creates test vector with output calculated by [`shuffle`].
+	pub fn new(name: &str, seed: H256, input: Vec<Tx>) -> Self {
+		Self { name: name.into(), seed, output: shuffle(input.clone(), &seed), input }
+	}
+}
+
+/// Shuffles `txs` using [`shuffle_using_seed`].
+pub fn shuffle(txs: Vec<Tx>, seed: &H256) -> Vec<Tx> {
+	shuffle_using_seed(txs.into_iter().map(|Tx { who, tx }| (who, tx.0)).collect(), seed)
+		.into_iter()
+		.map(|(who, tx)| Tx { who, tx: tx.into() })
+		.collect()
+}
+
+fn account(byte: u8) -> Option<H256> {
+	Some(H256::repeat_byte(byte))
+}
+
+/// `count` txs signed by `who`, tx bytes are `[tag, index]`
+fn txs(who: Option<H256>, tag: u8, count: u8) -> Vec<Tx> {
+	(0..count).map(|index| Tx { who, tx: vec![tag, index].into() }).collect()
+}
+
+fn uneven_accounts() -> Vec<Tx> {
+	[(1, 5), (2, 1), (3, 3), (4, 2), (5, 4)]
+		.iter()
+		.flat_map(|&(byte, count)| txs(account(byte), byte, count))
+		.collect()
+}
+
+/// Generates all test vectors.
+pub fn generate() -> Vec<TestVector> {
+	let seeds = [
+		H256::zero(),
+		H256::repeat_byte(0xff),
+		H256::from_str("0x0876d51dc2c109b2e9bca322e8706879d68984a8031a537d76d0b21693a3dbd0")
+			.unwrap(),
+		H256::from_str("0xff8611a4d212fc161dae19dd57f0f1ba9309f45d6207da13f2d3eab4c6839e91")
+			.unwrap(),
+	];
+	let seed = seeds[2];
+
+	let mut vectors = vec![
+		TestVector::new("empty input", seed, vec![]),
+		TestVector::new("single tx", seed, txs(account(1), 1, 1)),
+		TestVector::new("single account", seed, txs(account(1), 1, 6)),
+		TestVector::new(
+			"two accounts with equal counts",
+			seed,
+			[txs(account(1), 1, 3), txs(account(2), 2, 3)].concat(),
+		),
+	];
+
+	vectors.extend(seeds.iter().enumerate().map(|(index, seed)| {
+		TestVector::new(
+			&format!("many accounts with uneven counts, seed #{}", index),
+			*seed,
+			uneven_accounts(),
+		)
+	}));
+
+	// txs from different accounts are enqueued interleaved, result is the same as for
+	// `uneven_accounts` as only the order of txs from the same account matters
+	let mut interleaved = uneven_accounts();
+	interleaved.sort_by_key(|tx| tx.tx[1]);
+	vectors.push(TestVector::new("many accounts with interleaved input", seed, interleaved));
+
+	vectors.extend([
+		TestVector::new("unsigned txs only", seeds[3], txs(None, 0, 4)),
+		TestVector::new(
+			"signed and unsigned txs",
+			seeds[3],
+			[txs(None, 0, 2), txs(account(3), 3, 3), txs(account(1), 1, 1), txs(None, 0xff, 2)]
+				.concat(),
+		),
+		TestVector::new(
+			"signers compared as raw bytes",
+			seed,
+			[
+				txs(Some(H256::from_low_u64_be(0xff)), 1, 2),
+				txs(Some(H256::from_low_u64_le(0x01)), 2, 2),
+				txs(Some(H256::from_low_u64_be(0x01)), 3, 2),
+			]
+			.concat(),
+		),
+	]);
+
+	vectors
+}
@@ -0,0 +1,26 @@
+//! Conformance tests checking published test vectors against the shuffling implementation.
+
+use extrinsic_shuffler::test_vectors::{generate, shuffle, TestVector};
+
+const TEST_VECTORS: &str = include_str!("../res/test_vectors.json");
+
+fn published_vectors() -> Vec<TestVector> {
+	serde_json::from_str(TEST_VECTORS).expect("test vectors are valid json")
+}
+
+#[test]
+fn published_vectors_match_implementation() {
+	for vector in published_vectors() {
+		assert_eq!(shuffle(vector.input.clone(), &vector.seed), vector.output, "{}", vector.name);
+	}
+}
+
+#[test]
+fn published_vectors_are_up_to_date() {
+	assert_eq!(
+		published_vectors(),
+		generate(),
+		"test vectors are outdated, regenerate them with \
+		`cargo run -p extrinsic-shuffler --example generate_test_vectors`"
+	);
+}
@@ -1,8 +1,12 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Codec, Decode, Encode};
-use sp_runtime::{traits::Block as BlockT, AccountId32};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::{ShufflingSeed, H256};
+use sp_runtime::{traits::Block as BlockT, AccountId32, RuntimeDebug};
 use sp_std::vec::Vec;
+use sp_weights::Weight;
 
 /// Information about extrinsic fetched from runtime API
 #[derive(Encode, Decode, PartialEq)]
@@ -11,9 +15,147 @@ pub struct ExtrinsicInfo {
 	pub who: AccountId32,
 }
 
+/// Single entry of the storage queue fetched from runtime API. Represents all txs enqueued
+/// in a particular block.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub struct StorageQueueEntry {
+	/// number of block that enqueued txs
+	pub block_number: u32,
+	/// index of the first tx that has not been executed yet, it is only set once txs have been
+	/// shuffled (shuffling seed of the following block is known)
+	pub shuffle_index: Option<u32>,
+	/// enqueued txs along with their signers, in shuffled order if `shuffle_index` is set
+	pub txs: Vec<(Option<AccountId32>, Vec<u8>)>,
+}
+
+/// Summary of the storage queue, used to report execution lag of VER blocks.
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, RuntimeDebug)]
+pub struct StorageQueueStatus {
+	/// number of blocks that have txs waiting in the storage queue
+	pub depth: u32,
+	/// number of txs that have not been executed yet
+	pub pending_txs: u32,
+	/// number of the oldest block that has txs waiting in the storage queue
+	pub oldest_block: Option<u32>,
+}
+
+impl StorageQueueStatus {
+	/// Summarizes entries of the storage queue fetched with [`VerApi::get_storage_queue`].
+	pub fn new(queue: &[StorageQueueEntry]) -> Self {
+		let pending_txs = queue.iter().fold(0u32, |count, entry| {
+			let executed = entry.shuffle_index.unwrap_or_default() as usize;
+			count.saturating_add(entry.txs.len().saturating_sub(executed) as u32)
+		});
+		Self {
+			depth: queue.len() as u32,
+			pending_txs,
+			oldest_block: queue.iter().map(|entry| entry.block_number).min(),
+		}
+	}
+
+	/// Number of blocks since the oldest block with pending txs, relative to `best_block`.
+	pub fn oldest_block_age(&self, best_block: u32) -> Option<u32> {
+		self.oldest_block.map(|number| best_block.saturating_sub(number))
+	}
+}
+
+/// Fetches [`StorageQueueStatus`] at block `at`, `None` if the runtime does not expose the
+/// storage queue or the runtime api call fails.
+#[cfg(feature = "std")]
+pub fn storage_queue_status<Block, C>(client: &C, at: Block::Hash) -> Option<StorageQueueStatus>
+where
+	Block: BlockT,
+	C: sp_api::ProvideRuntimeApi<Block>,
+	C::Api: VerApi<Block>,
+{
+	use sp_api::ApiExt;
+
+	let api = client.runtime_api();
+	match api.api_version::<dyn VerApi<Block>>(at) {
+		Ok(Some(version)) if version >= 2 => api
+			.get_storage_queue(at)
+			.map(|queue| StorageQueueStatus::new(&queue))
+			.map_err(|e| log::debug!(target: "ver", "cannot fetch storage queue: {}", e))
+			.ok(),
+		_ => None,
+	}
+}
+
+/// Source of [`StorageQueueStatus`] at a given block, used by node services that report the
+/// execution lag without depending on the runtime implementing [`VerApi`].
+#[cfg(feature = "std")]
+pub type StorageQueueStatusProvider<Hash> =
+	std::sync::Arc<dyn Fn(Hash) -> Option<StorageQueueStatus> + Send + Sync>;
+
+/// Creates [`StorageQueueStatusProvider`] that fetches the status with [`storage_queue_status`].
+#[cfg(feature = "std")]
+pub fn storage_queue_status_provider<Block, C>(
+	client: std::sync::Arc<C>,
+) -> StorageQueueStatusProvider<Block::Hash>
+where
+	Block: BlockT,
+	C: sp_api::ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	C::Api: VerApi<Block>,
+{
+	std::sync::Arc::new(move |at| storage_queue_status(&*client, at))
+}
+
+/// Limits that need to be respected by all txs stored in the storage queue by a single
+/// `enqueue_txs` inherent, as all of them are going to be executed in a single block.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub struct EnqueueLimits {
+	/// maximal cumulative dispatch weight of enqueued txs
+	pub max_weight: Weight,
+	/// maximal cumulative encoded length of enqueued txs
+	pub max_len: u32,
+}
+
+/// Data kept in runtime storage that is required to build [`ShuffleProof`] of txs enqueued in a
+/// particular block. Fetched from runtime API at the block that enqueued txs.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+pub struct EnqueuedTxsProofData<Hash> {
+	/// number of block that enqueued txs
+	pub block_number: u32,
+	/// shuffling seed of the block that enqueued txs
+	pub seed: H256,
+	/// hashes of enqueued txs in the order they were enqueued, as deposited in the digest by
+	/// `enqueue_txs` inherent
+	pub hashes: Vec<Hash>,
+	/// storage key of the storage queue
+	pub queue_key: Vec<u8>,
+}
+
+/// Proof that a tx enqueued in block `block_number` has been executed at `position` of the
+/// shuffled execution order. Txs are shuffled using the seed of the following block, so the proof
+/// can be checked by anyone that knows headers of both blocks, see
+/// `extrinsic_shuffler::verify_shuffle_proof`.
+#[derive(Encode, Decode, Clone, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ShuffleProof<Hash> {
+	/// number of block that enqueued txs
+	pub block_number: u32,
+	/// position of the tx in the shuffled order of txs enqueued in `block_number`
+	pub position: u32,
+	/// shuffling seed of the block that enqueued txs
+	pub prev_seed: H256,
+	/// shuffling seed (along with VRF proof) of the following block, used to shuffle txs
+	pub seed: ShufflingSeed,
+	/// hashes of enqueued txs, as deposited in the digest by `enqueue_txs` inherent
+	pub enqueued_hashes: Vec<Hash>,
+	/// storage key of the storage queue
+	pub queue_key: Vec<u8>,
+	/// storage proof of the storage queue at the block that enqueued txs
+	pub queue_proof: Vec<Vec<u8>>,
+}
+
 sp_api::decl_runtime_apis! {
 	/// The `VerApi` api trait for fetching information about extrinsic author and
 	/// nonce
+	///
+	/// Methods introduced after the initial version are annotated with `#[api_version(..)]`,
+	/// clients need to check the version implemented by the runtime (`ApiExt::api_version`)
+	/// before calling them.
 	pub trait VerApi {
 		/// Provides information about extrinsic signer and nonce
 		fn get_signer(tx: <Block as BlockT>::Extrinsic) -> Option<(AccountId32, u32)>;
@@ -41,6 +183,31 @@ sp_api::decl_runtime_apis! {
 
 		// creates inherent that injects new txs into storage queue
 		fn start_prevalidation();
+
+		/// fetches all entries of the storage queue as they are stored in runtime storage
+		#[api_version(2)]
+		fn get_storage_queue() -> Vec<StorageQueueEntry>;
+
+		/// fetches shuffling seed of the current block
+		#[api_version(2)]
+		fn get_block_seed() -> sp_core::H256;
+
+		/// fetches limits of txs that can be stored by a single enqueue inherent
+		#[api_version(2)]
+		fn get_enqueue_limits() -> EnqueueLimits;
+
+		/// calculates dispatch weight of given tx
+		#[api_version(2)]
+		fn get_dispatch_weight(tx: <Block as BlockT>::Extrinsic) -> Weight;
+
+		/// fetches data required to build shuffle proof of txs enqueued in the current block,
+		/// `None` if no txs were enqueued
+		#[api_version(3)]
+		fn get_enqueued_txs_proof_data() -> Option<EnqueuedTxsProofData<<Block as BlockT>::Hash>>;
+
+		/// fetches maximal number of txs that can be stored by a single enqueue inherent
+		#[api_version(4)]
+		fn get_max_enqueued_txs() -> u32;
 	}
 
 	pub trait VerNonceApi<Account> where
@@ -50,3 +217,28 @@ sp_api::decl_runtime_apis! {
 		fn enqueued_txs_count(account: Account) -> u64;
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn entry(block_number: u32, shuffle_index: Option<u32>, txs: usize) -> StorageQueueEntry {
+		StorageQueueEntry { block_number, shuffle_index, txs: vec![(None, vec![0u8]); txs] }
+	}
+
+	#[test]
+	fn storage_queue_status_of_empty_queue() {
+		assert_eq!(StorageQueueStatus::new(&[]), StorageQueueStatus::default());
+		assert_eq!(StorageQueueStatus::default().oldest_block_age(10), None);
+	}
+
+	#[test]
+	fn storage_queue_status_skips_executed_txs() {
+		let queue = vec![entry(7, Some(2), 5), entry(8, None, 3), entry(6, Some(4), 4)];
+		let status = StorageQueueStatus::new(&queue);
+
+		assert_eq!(status, StorageQueueStatus { depth: 3, pending_txs: 6, oldest_block: Some(6) });
+		assert_eq!(status.oldest_block_age(10), Some(4));
+		assert_eq!(status.oldest_block_age(5), Some(0));
+	}
+}
@@ -5,7 +5,7 @@ use codec::{Decode, Encode};
 use sp_core::crypto::key_types::AURA;
 #[cfg(feature = "helpers")]
 use sp_core::sr25519;
-use sp_core::ShufflingSeed;
+use sp_core::{ShufflingSeed, H256};
 use sp_inherents::{InherentData, InherentIdentifier};
 #[cfg(feature = "helpers")]
 use sp_keystore::vrf::{VRFTranscriptData, VRFTranscriptValue};
@@ -34,6 +34,56 @@ pub fn extract_inherent_data(data: &InherentData) -> Result<ShufflingSeed, Runti
 #[cfg(feature = "std")]
 pub struct RandomSeedInherentDataProvider(pub ShufflingSeed);
 
+/// Reason why shuffling seed verification failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeedVerificationError {
+	/// block author public key is not a valid sr25519 key
+	InvalidPublicKey,
+	/// seed is not a valid VRF output
+	InvalidSeed,
+	/// proof is not a valid VRF proof
+	InvalidProof,
+	/// seed was not generated from previous seed by the block author
+	BadProof,
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for SeedVerificationError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			SeedVerificationError::InvalidPublicKey => write!(f, "invalid block author public key"),
+			SeedVerificationError::InvalidSeed => write!(f, "cannot parse shuffling seed"),
+			SeedVerificationError::InvalidProof => write!(f, "cannot parse shuffling seed proof"),
+			SeedVerificationError::BadProof => write!(f, "shuffling seed verification failed"),
+		}
+	}
+}
+
+/// Verifies that `seed` has been generated by signing `prev_seed` with the
+/// block author's key, it is the same check as the one performed by the runtime
+/// during block execution. `prev_seed` is the seed stored in the parent block
+/// header.
+pub fn verify_shuffling_seed(
+	public_key: &[u8],
+	prev_seed: &H256,
+	seed: &ShufflingSeed,
+) -> Result<(), SeedVerificationError> {
+	let output = schnorrkel::vrf::VRFOutput::from_bytes(seed.seed.as_bytes())
+		.map_err(|_| SeedVerificationError::InvalidSeed)?;
+	let proof = schnorrkel::vrf::VRFProof::from_bytes(seed.proof.as_bytes())
+		.map_err(|_| SeedVerificationError::InvalidProof)?;
+	let public_key = schnorrkel::PublicKey::from_bytes(public_key)
+		.map_err(|_| SeedVerificationError::InvalidPublicKey)?;
+
+	let mut transcript = merlin::Transcript::new(b"shuffling_seed");
+	transcript.append_message(b"prev_seed", prev_seed.as_bytes());
+
+	public_key
+		.vrf_verify(transcript, &output, &proof)
+		.map(|_| ())
+		.map_err(|_| SeedVerificationError::BadProof)
+}
+
 #[cfg(feature = "helpers")]
 pub fn calculate_next_seed<T: sp_keystore::SyncCryptoStore + ?Sized>(
 	keystore: &T,
@@ -74,3 +124,50 @@ impl sp_inherents::InherentDataProvider for RandomSeedInherentDataProvider {
 		None
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn sign(keypair: &schnorrkel::Keypair, prev_seed: &H256) -> ShufflingSeed {
+		let mut transcript = merlin::Transcript::new(b"shuffling_seed");
+		transcript.append_message(b"prev_seed", prev_seed.as_bytes());
+		let (inout, proof, _) = keypair.vrf_sign(transcript);
+		ShufflingSeed { seed: inout.to_output().to_bytes().into(), proof: proof.to_bytes().into() }
+	}
+
+	fn keypair(byte: u8) -> schnorrkel::Keypair {
+		schnorrkel::MiniSecretKey::from_bytes(&[byte; 32])
+			.unwrap()
+			.expand_to_keypair(schnorrkel::ExpansionMode::Ed25519)
+	}
+
+	#[test]
+	fn verify_shuffling_seed_accepts_seed_signed_by_author() {
+		let author = keypair(1);
+		let prev_seed = H256::repeat_byte(7);
+		let seed = sign(&author, &prev_seed);
+
+		assert_eq!(verify_shuffling_seed(&author.public.to_bytes(), &prev_seed, &seed), Ok(()));
+	}
+
+	#[test]
+	fn verify_shuffling_seed_rejects_invalid_seeds() {
+		let author = keypair(1);
+		let prev_seed = H256::repeat_byte(7);
+		let seed = sign(&author, &prev_seed);
+
+		assert_eq!(
+			verify_shuffling_seed(&keypair(2).public.to_bytes(), &prev_seed, &seed),
+			Err(SeedVerificationError::BadProof)
+		);
+		assert_eq!(
+			verify_shuffling_seed(&author.public.to_bytes(), &H256::repeat_byte(8), &seed),
+			Err(SeedVerificationError::BadProof)
+		);
+		assert_eq!(
+			verify_shuffling_seed(&[0u8; 3], &prev_seed, &seed),
+			Err(SeedVerificationError::InvalidPublicKey)
+		);
+	}
+}
@@ -164,7 +164,7 @@ pub enum Extrinsic {
 	OffchainIndexSet(Vec<u8>, Vec<u8>),
 	OffchainIndexClear(Vec<u8>),
 	Store(Vec<u8>),
-	EnqueueTxs(u64),
+	EnqueueTxs(Vec<Vec<u8>>),
 }
 
 #[cfg(feature = "std")]
@@ -637,6 +637,9 @@ impl frame_system::pallet::Config for Runtime {
 	type SS58Prefix = ();
 	type OnSetCode = ();
 	type MaxConsumers = ConstU32<16>;
+	type ShuffleStrategy = ();
+	type StorageQueueLimits = ();
+	type EnqueuedTxsValidator = ();
 }
 
 impl system::Config for Runtime {}
@@ -727,6 +730,7 @@ cfg_if! {
 				}
 			}
 
+			#[api_version(4)]
 			impl ver_api::VerApi<Block> for Runtime {
 				fn get_signer(
 					_tx: <Block as BlockT>::Extrinsic,
@@ -747,12 +751,39 @@ cfg_if! {
 
 
 			  fn create_enqueue_txs_inherent(txs: Vec<<Block as BlockT>::Extrinsic>) -> <Block as BlockT>::Extrinsic{
-				  //just return some garbage
-					Extrinsic::EnqueueTxs(txs.len() as u64)
+					Extrinsic::EnqueueTxs(txs.iter().map(Encode::encode).collect())
 			  }
-				fn pop_txs(_count: u64) -> sp_application_crypto::Vec<sp_application_crypto::Vec<u8>> { Default::default() }
-				fn get_previous_block_txs() -> Vec<Vec<u8>>{Default::default()}
+				fn pop_txs(count: u64) -> sp_application_crypto::Vec<sp_application_crypto::Vec<u8>> {
+					system::pop_txs(count as usize)
+				}
+				fn get_previous_block_txs() -> Vec<Vec<u8>>{ system::enqueued_txs() }
 				fn start_prevalidation() {}
+				fn get_storage_queue() -> Vec<ver_api::StorageQueueEntry> {
+					// the test runtime keeps a single FIFO queue, it is reported as a single entry
+					// that has not been shuffled yet
+					let txs = system::enqueued_txs();
+					if txs.is_empty() {
+						return Default::default()
+					}
+					vec![ver_api::StorageQueueEntry {
+						block_number: Default::default(),
+						shuffle_index: None,
+						txs: txs.into_iter().map(|tx| (None, tx)).collect(),
+					}]
+				}
+				fn get_block_seed() -> sp_core::H256 { Default::default() }
+				fn get_enqueue_limits() -> ver_api::EnqueueLimits {
+					ver_api::EnqueueLimits { max_weight: Weight::MAX, max_len: u32::MAX }
+				}
+				fn get_dispatch_weight(_tx: <Block as BlockT>::Extrinsic) -> Weight {
+					Default::default()
+				}
+				fn get_enqueued_txs_proof_data() -> Option<ver_api::EnqueuedTxsProofData<<Block as BlockT>::Hash>> {
+					None
+				}
+				fn get_max_enqueued_txs() -> u32 {
+					u32::MAX
+				}
 			}
 
 			impl sp_api::Metadata<Block> for Runtime {
@@ -1030,6 +1061,12 @@ cfg_if! {
 					0
 				}
 			}
+
+			impl ver_api::VerNonceApi<Block, AccountId> for Runtime {
+				fn enqueued_txs_count(_account: AccountId) -> u64 {
+					0
+				}
+			}
 		}
 	} else {
 		impl_runtime_apis! {
@@ -1265,6 +1302,12 @@ cfg_if! {
 					0
 				}
 			}
+
+			impl ver_api::VerNonceApi<Block, AccountId> for Runtime {
+				fn enqueued_txs_count(_account: AccountId) -> u64 {
+					0
+				}
+			}
 		}
 	}
 }
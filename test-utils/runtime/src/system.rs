@@ -72,6 +72,10 @@ mod pallet {
 	#[pallet::storage]
 	pub type Authorities<T> = StorageValue<_, Vec<AuthorityId>, ValueQuery>;
 
+	/// Txs stored by `Extrinsic::EnqueueTxs`, waiting to be executed in the following blocks.
+	#[pallet::storage]
+	pub type StorageQueue<T> = StorageValue<_, Vec<Vec<u8>>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[cfg_attr(feature = "std", derive(Default))]
 	pub struct GenesisConfig {
@@ -116,6 +120,19 @@ pub fn authorities() -> Vec<AuthorityId> {
 	<Authorities<Runtime>>::get()
 }
 
+/// Returns txs waiting in the storage queue.
+pub fn enqueued_txs() -> Vec<Vec<u8>> {
+	<StorageQueue<Runtime>>::get()
+}
+
+/// Removes up to `count` txs from the front of the storage queue and returns them.
+pub fn pop_txs(count: usize) -> Vec<Vec<u8>> {
+	<StorageQueue<Runtime>>::mutate(|queue| {
+		let count = count.min(queue.len());
+		queue.drain(..count).collect()
+	})
+}
+
 pub fn get_block_number() -> Option<BlockNumber> {
 	<Number<Runtime>>::get()
 }
@@ -144,6 +161,9 @@ fn execute_block_with_state_root_handler(block: &mut Block, mode: Mode) -> Heade
 
 	initialize_block(header);
 
+	// txs executed from the storage queue are part of the block body
+	pop_txs(header.count as usize);
+
 	// execute transactions
 	block.extrinsics.iter().for_each(|e| {
 		let _ = execute_transaction(e.clone()).unwrap_or_else(|_| panic!("Invalid transaction"));
@@ -283,7 +303,10 @@ fn execute_transaction_backend(utx: &Extrinsic, extrinsic_index: u32) -> ApplyEx
 			Ok(Ok(()))
 		},
 		Extrinsic::Store(data) => execute_store(data.clone()),
-		Extrinsic::EnqueueTxs(_) => Ok(Ok(())),
+		Extrinsic::EnqueueTxs(txs) => {
+			<StorageQueue<Runtime>>::mutate(|queue| queue.extend(txs.iter().cloned()));
+			Ok(Ok(()))
+		},
 	}
 }
 
@@ -249,36 +249,76 @@ where
 		Self { client, params, inherent_data, _p: PhantomData }
 	}
 
+	/// Imports a block that enqueues as many extrinsics built with `ext_builder` as possible.
+	///
+	/// Returns the number of enqueued extrinsics.
 	pub fn prepare_benchmark(&mut self, ext_builder: &dyn ExtrinsicBuilder) -> Result<usize> {
-		let block = self.build_first_block(ext_builder)?;
-		let num_ext = block.block.extrinsics().len();
+		let (block, enqueued) = self.build_first_block(ext_builder)?;
 		self.import_block(block);
-		Ok(num_ext)
+		Ok(enqueued)
 	}
 
-	/// Benchmark a block that does not include any new extrinsics but needs to shuffle previous one
-	pub fn bench_block(&mut self, ext_builder: &dyn ExtrinsicBuilder) -> Result<Stats> {
-		let block = self.build_second_block(ext_builder, 0, false)?;
+	/// Benchmark a block with only inherents on top of genesis, the storage queue is empty.
+	pub fn bench_block(&self) -> Result<Stats> {
+		let block = self.build_empty_block(true)?;
 		let record = self.measure_block(&block.block)?;
 		Stats::new(&record)
 	}
 
+	/// Benchmark the storage queue bookkeeping of a single queued extrinsic.
+	///
+	/// Benchmarks a block that stores the seed and shuffles the `queued` extrinsics enqueued by
+	/// [`Self::prepare_benchmark`] but neither pops nor executes them, popping is measured along
+	/// with their execution by `bench_extrinsic`. Subtracts the time of an empty block,
+	/// analogous to `bench_block`, and divides the result by `queued`.
+	pub fn bench_queue(&self, queued: usize) -> Result<Stats> {
+		if queued == 0 {
+			return Err("Storage queue is empty".into())
+		}
+		let block = self.build_empty_block(true)?;
+		let base = self.measure_block(&block.block)?;
+		let base_time = Stats::new(&base)?.select(StatSelect::Average);
+
+		let block = self.build_empty_block(false)?;
+		let mut records = self.measure_block(&block.block)?;
+
+		for r in &mut records {
+			// Subtract the base time.
+			*r = r.saturating_sub(base_time);
+			// Divide by the number of extrinsics in the storage queue.
+			*r = ((*r as f64) / (queued as f64)).ceil() as u64;
+		}
+
+		Stats::new(&records)
+	}
+
 	/// Benchmark the time of an extrinsic in a full block.
 	///
-	/// First benchmarks an empty block, analogous to `bench_block` and use it as baseline.
-	/// Then benchmarks a full block built with the given `ext_builder` and subtracts the baseline
+	/// First benchmarks a block that only shuffles the `queued` extrinsics, analogous to
+	/// `bench_queue` and use it as baseline. Then benchmarks a block that executes them and
+	/// enqueues the same amount of extrinsics built with `ext_builder`, subtracts the baseline
 	/// from the result.
-	/// This is necessary to account for the time the inherents use.
+	/// This is necessary to account for the time the inherents and the storage queue use.
 	pub fn bench_extrinsic(
 		&mut self,
 		ext_builder: &dyn ExtrinsicBuilder,
-		count: usize,
+		queued: usize,
 	) -> Result<Stats> {
-		let block = self.build_second_block(ext_builder, count, true)?;
-		let num_ext = block.block.extrinsics().len();
-		let mut records = self.measure_block(&block.block.clone())?;
+		let block = self.build_empty_block(false)?;
+		let base_len = block.block.extrinsics().len();
+		let base = self.measure_block(&block.block)?;
+		let base_time = Stats::new(&base)?.select(StatSelect::Average);
+
+		let block = self.build_second_block(ext_builder, queued)?;
+		let num_ext = block.block.extrinsics().len().saturating_sub(base_len);
+		if num_ext == 0 {
+			return Err(Error::Input("Block was empty".into()))
+		}
+		let mut records = self.measure_block(&block.block)?;
 
 		for r in &mut records {
+			// Subtract the base time.
+			*r = r.saturating_sub(base_time);
 			// Divide by the number of extrinsics in the block.
 			*r = ((*r as f64) / (num_ext as f64)).ceil() as u64;
 		}
@@ -308,10 +348,12 @@ where
 	}
 
 	/// Builds a block that enqueues maximum possible amount of extrinsics
+	///
+	/// Returns the block and the number of enqueued extrinsics.
 	fn build_first_block(
 		&mut self,
 		ext_builder: &dyn ExtrinsicBuilder,
-	) -> Result<sc_block_builder_ver::BuiltBlock<Block, BA::State>> {
+	) -> Result<(sc_block_builder_ver::BuiltBlock<Block, BA::State>, usize)> {
 		let digest = self.create_digest(1_u64);
 		info!("creating remarks");
 		let remarks = (0..self.max_ext_per_block())
@@ -329,7 +371,7 @@ where
 		}
 
 		info!("applying previous block txs");
-		builder.apply_previous_block_extrinsics(seed.clone(), &mut 0, usize::MAX, || false);
+		builder.apply_previous_block_extrinsics(seed.clone(), &mut 0, usize::MAX, || false)?;
 
 		let mut txs_count = 0u64;
 		let txs_count_ref = &mut txs_count;
@@ -363,16 +405,16 @@ where
 		if txs_count >= self.max_ext_per_block() as u64 {
 			panic!("fully filled block should not consume more than half of pregenrated extrinsics .. consider increasing --max-ext-per-block paramter value");
 		}
-		Ok(block)
+		Ok((block, txs_count as usize))
 	}
 
+	/// Builds a block that executes the extrinsics enqueued by the imported block and enqueues
+	/// `txs_count` new ones.
 	fn build_second_block(
 		&mut self,
 		ext_builder: &dyn ExtrinsicBuilder,
 		txs_count: usize,
-		apply_previous_block_extrinsics: bool,
 	) -> Result<sc_block_builder_ver::BuiltBlock<Block, BA::State>> {
-		// Return early if we just want a block with inherents and no additional extrinsics.
 		let remarks = (txs_count..(txs_count * 2))
 			.map(|nonce| {
 				ext_builder.build(nonce as u32).expect("remark txs creation should not fail")
@@ -388,9 +430,7 @@ where
 			builder.push(inherent)?;
 		}
 
-		builder.apply_previous_block_extrinsics(seed.clone(), &mut 0, usize::MAX, || {
-			!apply_previous_block_extrinsics
-		});
+		builder.apply_previous_block_extrinsics(seed.clone(), &mut 0, usize::MAX, || false)?;
 
 		let block = builder.build_with_seed(seed, |_, _| {
 			remarks.into_iter().map(|remark| (None, remark)).collect::<Vec<_>>()
@@ -405,6 +445,34 @@ where
 		Ok(block)
 	}
 
+	/// Builds a block with only inherents that neither executes nor enqueues any extrinsics.
+	///
+	/// The block is built on top of genesis if `on_genesis` is set, the storage queue is empty
+	/// then. Otherwise it is built on top of the block imported by `prepare_benchmark` and
+	/// shuffles the extrinsics enqueued there.
+	fn build_empty_block(
+		&self,
+		on_genesis: bool,
+	) -> Result<sc_block_builder_ver::BuiltBlock<Block, BA::State>> {
+		let client = self.client.borrow();
+		let (parent, aura_slot, inherent_data) = if on_genesis {
+			(client.info().genesis_hash, 1_u64, self.inherent_data.0.clone())
+		} else {
+			(client.info().best_hash, 3_u64, self.inherent_data.1.clone())
+		};
+
+		let digest = self.create_digest(aura_slot);
+		let mut builder = client.new_block_at(&BlockId::Hash(parent), digest, false)?;
+		let (seed, inherents) = builder.create_inherents(inherent_data)?;
+		for inherent in inherents {
+			builder.push(inherent)?;
+		}
+
+		let block = builder.build_empty_with_seed(seed)?;
+		debug!("created block {:?}", block.block.clone());
+		Ok(block)
+	}
+
 	/// Measures the time that it take to execute a block or an extrinsic.
 	fn measure_block(&self, block: &Block) -> Result<BenchRecord> {
 		let mut record = BenchRecord::new();
@@ -92,6 +92,14 @@ pub struct OverheadParams {
 	/// This should only be used for performance analysis and not for final results.
 	#[arg(long)]
 	pub enable_trie_cache: bool,
+
+	/// Benchmark VER blocks.
+	///
+	/// Blocks are built with the VER block builder, so the measured overhead includes the seed
+	/// verification, the storage queue bookkeeping and the enqueue inherent. Additionally writes
+	/// the weight of the storage queue bookkeeping per queued extrinsic.
+	#[arg(long)]
+	pub ver: bool,
 }
 
 /// Type of a benchmark.
@@ -101,6 +109,8 @@ pub(crate) enum BenchmarkType {
 	Extrinsic,
 	/// Measure the per-block execution overhead.
 	Block,
+	/// Measure the storage queue bookkeeping overhead per queued extrinsic.
+	Queue,
 }
 
 impl OverheadCmd {
@@ -108,6 +118,8 @@ impl OverheadCmd {
 	///
 	/// Writes the results to console and into two instances of the
 	/// `weights.hbs` template, one for each benchmark.
+	///
+	/// Fails for runtimes that implement [`VerApi`], they are benchmarked by [`Self::run_ver`].
 	pub fn run<Block, BA, C>(
 		&self,
 		cfg: Configuration,
@@ -124,6 +136,17 @@ impl OverheadCmd {
 			+ sp_blockchain::HeaderBackend<Block>,
 		C::Api: ApiExt<Block, StateBackend = BA::State> + BlockBuilderApi<Block>,
 	{
+		if self.params.ver {
+			return Err("VER blocks are benchmarked by `OverheadCmd::run_ver`".into())
+		}
+		let genesis = client.info().genesis_hash;
+		if client
+			.runtime_api()
+			.has_api::<dyn VerApi<Block>>(genesis)
+			.map_err(|e| format!("Unable to check the runtime APIs: {:?}", e))?
+		{
+			return Err("The runtime implements `VerApi`, its blocks need to be benchmarked with `--ver`, see `OverheadCmd::run_ver`".into())
+		}
 		if ext_builder.pallet() != "system" || ext_builder.extrinsic() != "remark" {
 			return Err(format!("The extrinsic builder is required to build `System::Remark` extrinsics but builds `{}` extrinsics instead", ext_builder.name()).into());
 		}
@@ -146,6 +169,14 @@ impl OverheadCmd {
 
 		Ok(())
 	}
+
+	/// Measure the per-block, per-extrinsic and storage queue execution overhead of VER blocks.
+	///
+	/// Writes the results to console and into three instances of the
+	/// `weights.hbs` template, one for each benchmark.
+	///
+	/// `inherent_data` holds the inherent data of the first and the second block built on top of
+	/// genesis, both need to provide the shuffling seed of the respective block.
 	pub fn run_ver<Block, BA, C>(
 		&self,
 		cfg: Configuration,
@@ -169,19 +200,29 @@ impl OverheadCmd {
 		C::Api: BlockBuilderApiVer<Block>,
 		C::Api: VerApi<Block>,
 	{
+		if ext_builder.pallet() != "system" || ext_builder.extrinsic() != "remark" {
+			return Err(format!("The extrinsic builder is required to build `System::Remark` extrinsics but builds `{}` extrinsics instead", ext_builder.name()).into());
+		}
 		let mut bench = BenchmarkVer::new(client, self.params.bench.clone(), inherent_data);
 
-		let count = bench.prepare_benchmark(ext_builder)?;
 		// per-block execution overhead
 		{
-			let stats = bench.bench_block(ext_builder)?;
+			let stats = bench.bench_block()?;
 			info!("Per-block execution overhead [ns]:\n{:?}", stats);
 			let template = TemplateData::new(BenchmarkType::Block, &cfg, &self.params, &stats)?;
 			template.write(&self.params.weight.weight_path)?;
 		}
+		let queued = bench.prepare_benchmark(ext_builder)?;
+		// per-queued-extrinsic storage queue overhead
+		{
+			let stats = bench.bench_queue(queued)?;
+			info!("Per-queued-extrinsic storage queue overhead [ns]:\n{:?}", stats);
+			let template = TemplateData::new(BenchmarkType::Queue, &cfg, &self.params, &stats)?;
+			template.write(&self.params.weight.weight_path)?;
+		}
 		// per-extrinsic execution overhead
 		{
-			let stats = bench.bench_extrinsic(ext_builder, count)?;
+			let stats = bench.bench_extrinsic(ext_builder, queued)?;
 			info!("Per-extrinsic execution overhead [ns]:\n{:?}", stats);
 			let template = TemplateData::new(BenchmarkType::Extrinsic, &cfg, &self.params, &stats)?;
 			template.write(&self.params.weight.weight_path)?;
@@ -197,6 +238,7 @@ impl BenchmarkType {
 		match self {
 			Self::Extrinsic => "extrinsic",
 			Self::Block => "block",
+			Self::Queue => "queue",
 		}
 	}
 
@@ -205,6 +247,7 @@ impl BenchmarkType {
 		match self {
 			Self::Extrinsic => "ExtrinsicBase",
 			Self::Block => "BlockExecution",
+			Self::Queue => "StorageQueueTx",
 		}
 	}
 }
@@ -37,9 +37,9 @@ static TEMPLATE: &str = include_str!("./weights.hbs");
 /// Data consumed by Handlebar to fill out the `weights.hbs` template.
 #[derive(Serialize, Debug, Clone)]
 pub(crate) struct TemplateData {
-	/// Short name of the benchmark. Can be "block" or "extrinsic".
+	/// Short name of the benchmark. Can be "block", "extrinsic" or "queue".
 	long_name: String,
-	/// Long name of the benchmark. Can be "BlockExecution" or "ExtrinsicBase".
+	/// Long name of the benchmark. Can be "BlockExecution", "ExtrinsicBase" or "StorageQueueTx".
 	short_name: String,
 	/// Name of the runtime. Taken from the chain spec.
 	runtime_name: String,
@@ -77,6 +77,9 @@ use sp_storage::{StorageData, StorageKey};
 /// # 	type SS58Prefix = ();
 /// # 	type OnSetCode = ();
 /// # 	type MaxConsumers = ConstU32<16>;
+/// # 	type ShuffleStrategy = ();
+/// # 	type StorageQueueLimits = ();
+/// # 	type EnqueuedTxsValidator = ();
 /// # }
 /// #
 /// # impl pallet_test::Config for TestRuntime {}
@@ -32,9 +32,13 @@ use sp_api::ApiExt;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::HeaderBackend;
 use sp_core::{hexdisplay::HexDisplay, Bytes};
-use sp_runtime::{legacy, traits};
+use sp_runtime::{
+	legacy, traits,
+	traits::{Saturating, UniqueSaturatedInto},
+};
 
 pub use frame_system_rpc_runtime_api::AccountNonceApi;
+pub use ver_api::VerNonceApi;
 
 /// System RPC methods.
 #[rpc(client, server)]
@@ -44,6 +48,8 @@ pub trait SystemApi<BlockHash, AccountId, Index> {
 	/// This method takes into consideration all pending transactions
 	/// currently in the pool and if no transactions are found in the pool
 	/// it fallbacks to query the index from the runtime (aka. state nonce).
+	/// Transactions enqueued on chain but not executed yet are accounted for
+	/// as well, if the runtime exposes `VerNonceApi`.
 	#[method(name = "system_accountNextIndex", aliases = ["account_nextIndex"])]
 	async fn nonce(&self, account: AccountId) -> RpcResult<Index>;
 
@@ -92,6 +98,7 @@ where
 	C: HeaderBackend<Block>,
 	C: Send + Sync + 'static,
 	C::Api: AccountNonceApi<Block, AccountId, Index>,
+	C::Api: VerNonceApi<Block, AccountId>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 	Block: traits::Block,
@@ -109,6 +116,24 @@ where
 				Some(e.to_string()),
 			))
 		})?;
+
+		// enqueued txs already consumed their nonces, even though they are not executed yet
+		let has_ver_nonce_api =
+			api.has_api::<dyn VerNonceApi<Block, AccountId>>(best).unwrap_or_default();
+		let nonce = if has_ver_nonce_api {
+			let enqueued = api.enqueued_txs_count(best, account.clone()).map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to query enqueued txs count.",
+					Some(e.to_string()),
+				))
+			})?;
+			log::debug!(target: "rpc", "Enqueued txs count for {}: {}", account, enqueued);
+			nonce.saturating_add(enqueued.unique_saturated_into())
+		} else {
+			nonce
+		};
+
 		Ok(adjust_nonce(&*self.pool, account, nonce))
 	}
 
@@ -0,0 +1,377 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC methods exposing VER storage queue state.
+//!
+//! Txs collected by block `N` are persisted in `frame_system::StorageQueue` and executed
+//! (in shuffled order) starting from block `N+1`. These methods let external tooling find out
+//! when a particular tx is going to be executed without reimplementing the shuffling algorithm,
+//! and to obtain proofs of the shuffled order that can be checked using block headers only.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use extrinsic_shuffler::{RoundRobinShuffle, ShuffleStrategy};
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_client_api::ProofProvider;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{Block as BlockT, Hash as HashT, HashFor, Header as HeaderT},
+	AccountId32,
+};
+use ver_api::{EnqueuedTxsProofData, ShuffleProof, StorageQueueEntry};
+
+pub use ver_api::VerApi as VerRuntimeApi;
+
+/// VER RPC methods.
+#[rpc(client, server)]
+pub trait VerApi<BlockHash> {
+	/// Returns txs waiting for execution in the storage queue at the given block (or the best
+	/// block if not specified).
+	///
+	/// Txs that have already been shuffled are returned together with the exact position in
+	/// which they are going to be executed.
+	#[method(name = "ver_pendingExecutionOrder")]
+	fn pending_execution_order(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<PendingExecutionOrder<BlockHash>>;
+
+	/// Returns proof that tx `tx_hash`, enqueued by the parent of block `at`, has been shuffled
+	/// into particular position of the execution order using the shuffling seed of block `at`.
+	///
+	/// The proof can be checked with `extrinsic_shuffler::verify_shuffle_proof`.
+	#[method(name = "ver_shuffleProof")]
+	fn shuffle_proof(
+		&self,
+		tx_hash: BlockHash,
+		at: BlockHash,
+	) -> RpcResult<ShuffleProof<BlockHash>>;
+}
+
+/// Storage queue state at particular block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingExecutionOrder<Hash> {
+	/// shuffling seed stored at the requested block
+	pub seed: H256,
+	/// txs that have not been executed yet, ordered by enqueuing block
+	pub txs: Vec<EnqueuedTx<Hash>>,
+}
+
+/// Tx stored in the storage queue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnqueuedTx<Hash> {
+	/// hash of the enqueued extrinsic
+	pub hash: Hash,
+	/// extrinsic signer
+	pub who: Option<AccountId32>,
+	/// number of block that enqueued the extrinsic
+	pub block_number: u32,
+	/// position of the extrinsic among all txs enqueued in `block_number` after shuffling,
+	/// `None` if txs have not been shuffled yet
+	pub shuffle_index: Option<u32>,
+	/// position in which extrinsic is going to be executed in the following block(s), `None` if
+	/// it is not ready for execution yet
+	pub execution_index: Option<u32>,
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The runtime does not implement required version of the runtime api.
+	UnsupportedRuntime,
+	/// Requested proof cannot be generated.
+	ProofUnavailable,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::UnsupportedRuntime => 2,
+			Error::ProofUnavailable => 3,
+		}
+	}
+}
+
+/// Provides RPC methods to query VER storage queue.
+///
+/// `S` needs to match the shuffling strategy used by the runtime
+/// (`frame_system::Config::ShuffleStrategy`), it is used when building shuffle proofs.
+pub struct Ver<C, B, S = RoundRobinShuffle> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(B, S)>,
+}
+
+impl<C, B> Ver<C, B> {
+	/// Creates a new instance of the Ver Rpc helper.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, B, S: ShuffleStrategy> Ver<C, B, S> {
+	/// Creates a new instance of the Ver Rpc helper for runtimes using custom shuffling strategy.
+	pub fn with_shuffle_strategy(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, S> VerApiServer<<Block as BlockT>::Hash> for Ver<C, Block, S>
+where
+	Block: BlockT,
+	S: ShuffleStrategy + Send + Sync + 'static,
+	C: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ ProofProvider<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: VerRuntimeApi<Block>,
+{
+	fn pending_execution_order(
+		&self,
+		at: Option<Block::Hash>,
+	) -> RpcResult<PendingExecutionOrder<Block::Hash>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash);
+
+		ensure_api_version::<_, Block>(&*api, at, 2, "Runtime does not expose storage queue.")?;
+
+		let queue = api
+			.get_storage_queue(at)
+			.map_err(|e| map_err(e, "Unable to query storage queue."))?;
+		let ready_txs = api
+			.get_previous_block_txs(at)
+			.map_err(|e| map_err(e, "Unable to query txs ready for execution."))?;
+		let seed = api
+			.get_block_seed(at)
+			.map_err(|e| map_err(e, "Unable to query shuffling seed."))?;
+
+		Ok(PendingExecutionOrder {
+			seed,
+			txs: pending_txs(queue, ready_txs, |tx| HashFor::<Block>::hash(tx)),
+		})
+	}
+
+	fn shuffle_proof(
+		&self,
+		tx_hash: Block::Hash,
+		at: Block::Hash,
+	) -> RpcResult<ShuffleProof<Block::Hash>> {
+		let api = self.client.runtime_api();
+		let header = self
+			.client
+			.header(at)
+			.map_err(|e| map_err(e, "Unable to query block header."))?
+			.ok_or_else(|| proof_unavailable("Block not found.", at))?;
+		let parent = *header.parent_hash();
+
+		ensure_api_version::<_, Block>(&*api, parent, 3, "Runtime does not expose proof data.")?;
+
+		let EnqueuedTxsProofData { block_number, seed: prev_seed, hashes, queue_key } = api
+			.get_enqueued_txs_proof_data(parent)
+			.map_err(|e| map_err(e, "Unable to query proof data."))?
+			.ok_or_else(|| proof_unavailable("Parent block did not enqueue any txs.", parent))?;
+		let txs = api
+			.get_storage_queue(parent)
+			.map_err(|e| map_err(e, "Unable to query storage queue."))?
+			.into_iter()
+			.find(|entry| entry.block_number == block_number)
+			.map(|entry| entry.txs)
+			.ok_or_else(|| proof_unavailable("Enqueued txs not found.", parent))?;
+
+		let seed = header.seed().clone();
+		let position = S::shuffle(txs, &seed.seed)
+			.iter()
+			.position(|(_, tx)| HashFor::<Block>::hash(tx) == tx_hash)
+			.ok_or_else(|| proof_unavailable("Tx not found.", tx_hash))?;
+
+		let queue_proof = self
+			.client
+			.read_proof(parent, &mut std::iter::once(&queue_key[..]))
+			.map_err(|e| map_err(e, "Unable to generate storage proof."))?;
+
+		Ok(ShuffleProof {
+			block_number,
+			position: position as u32,
+			prev_seed,
+			seed,
+			enqueued_hashes: hashes,
+			queue_key,
+			queue_proof: queue_proof.into_iter_nodes().collect(),
+		})
+	}
+}
+
+fn map_err(error: impl ToString, desc: &'static str) -> CallError {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), desc, Some(error.to_string())))
+}
+
+fn proof_unavailable(desc: &'static str, hash: impl std::fmt::Debug) -> CallError {
+	CallError::Custom(ErrorObject::owned(
+		Error::ProofUnavailable.into(),
+		desc,
+		Some(format!("{:?}", hash)),
+	))
+}
+
+/// Fails if `VerApi` implemented by the runtime at `at` is older than `required`.
+fn ensure_api_version<Api, Block>(
+	api: &Api,
+	at: Block::Hash,
+	required: u32,
+	desc: &'static str,
+) -> Result<(), CallError>
+where
+	Block: BlockT,
+	Api: ApiExt<Block>,
+{
+	let version = api
+		.api_version::<dyn VerRuntimeApi<Block>>(at)
+		.map_err(|e| map_err(e, "Unable to query runtime api version."))?;
+	if version.unwrap_or_default() < required {
+		return Err(CallError::Custom(ErrorObject::owned(
+			Error::UnsupportedRuntime.into(),
+			desc,
+			Some(format!("VerApi version {:?}, required {}", version, required)),
+		)))
+	}
+	Ok(())
+}
+
+/// Lists all txs from the storage queue that have not been executed yet. Execution indexes are
+/// figured out based on `ready_txs` that are expected to be in exactly the same order as they are
+/// going to be executed.
+fn pending_txs<Hash, F>(
+	queue: Vec<StorageQueueEntry>,
+	ready_txs: Vec<Vec<u8>>,
+	hasher: F,
+) -> Vec<EnqueuedTx<Hash>>
+where
+	Hash: Ord + Clone,
+	F: Fn(&[u8]) -> Hash,
+{
+	let execution_indexes = ready_txs
+		.iter()
+		.enumerate()
+		.map(|(index, tx)| (hasher(tx), index as u32))
+		.collect::<BTreeMap<_, _>>();
+
+	queue
+		.into_iter()
+		.flat_map(|StorageQueueEntry { block_number, shuffle_index, txs }| {
+			let executed = shuffle_index.unwrap_or_default() as usize;
+			txs.into_iter().enumerate().skip(executed).map(move |(index, (who, tx))| {
+				(block_number, shuffle_index.map(|_| index as u32), who, tx)
+			})
+		})
+		.map(|(block_number, shuffle_index, who, tx)| {
+			let hash = hasher(&tx);
+			EnqueuedTx {
+				execution_index: shuffle_index.and_then(|_| execution_indexes.get(&hash)).cloned(),
+				hash,
+				who,
+				block_number,
+				shuffle_index,
+			}
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::traits::BlakeTwo256;
+
+	fn who(byte: u8) -> Option<AccountId32> {
+		Some(AccountId32::new([byte; 32]))
+	}
+
+	fn hash(tx: &[u8]) -> H256 {
+		BlakeTwo256::hash(tx)
+	}
+
+	#[test]
+	fn pending_txs_skips_executed_txs_and_tracks_execution_order() {
+		let queue = vec![
+			StorageQueueEntry {
+				block_number: 1,
+				shuffle_index: Some(1),
+				txs: vec![(who(1), vec![1]), (who(2), vec![2]), (who(1), vec![3])],
+			},
+			StorageQueueEntry {
+				block_number: 2,
+				shuffle_index: None,
+				txs: vec![(who(3), vec![4]), (None, vec![5])],
+			},
+		];
+		let ready_txs = vec![vec![2], vec![3]];
+
+		let txs = pending_txs(queue, ready_txs, hash);
+
+		assert_eq!(
+			txs,
+			vec![
+				EnqueuedTx {
+					hash: hash(&[2]),
+					who: who(2),
+					block_number: 1,
+					shuffle_index: Some(1),
+					execution_index: Some(0),
+				},
+				EnqueuedTx {
+					hash: hash(&[3]),
+					who: who(1),
+					block_number: 1,
+					shuffle_index: Some(2),
+					execution_index: Some(1),
+				},
+				EnqueuedTx {
+					hash: hash(&[4]),
+					who: who(3),
+					block_number: 2,
+					shuffle_index: None,
+					execution_index: None,
+				},
+				EnqueuedTx {
+					hash: hash(&[5]),
+					who: None,
+					block_number: 2,
+					shuffle_index: None,
+					execution_index: None,
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn pending_txs_for_empty_queue() {
+		assert!(pending_txs::<H256, _>(vec![], vec![], hash).is_empty());
+	}
+}
@@ -16,8 +16,8 @@
 // limitations under the License.
 
 use crate::{
-	build_executor, full_extensions, rpc_err_handler, state_machine_call_with_proof, LiveState,
-	SharedParams, State, LOG_TARGET,
+	build_executor, full_extensions, rpc_err_handler, state_machine_call_with_proof, ver,
+	LiveState, SharedParams, State, LOG_TARGET,
 };
 use parity_scale_codec::Encode;
 use sc_executor::sp_wasm_interface::HostFunctions;
@@ -31,8 +31,9 @@ use substrate_rpc_client::{ws_client, ChainApi};
 
 /// Configurations of the [`crate::Command::ExecuteBlock`].
 ///
-/// This will always call into `TryRuntime_execute_block`, which can optionally skip the state-root
-/// check (useful for trying a unreleased runtime), and can execute runtime sanity checks as well.
+/// This will always call into `TryRuntime_execute_block` (or `TryRuntime_execute_block_ver` if
+/// `--ver` is set), which can optionally skip the state-root check (useful for trying a unreleased
+/// runtime), and can execute runtime sanity checks as well.
 #[derive(Debug, Clone, clap::Parser)]
 pub struct ExecuteBlockCmd {
 	/// Which try-state targets to execute when running this command.
@@ -60,6 +61,13 @@ pub struct ExecuteBlockCmd {
 	)]
 	pub block_ws_uri: Option<String>,
 
+	/// Execute the block as a VER block.
+	///
+	/// The shuffling seed of the block is verified against the AURA author of the block, and txs
+	/// popped from the storage queue are executed, the same way collators do.
+	#[arg(long)]
+	pub ver: bool,
+
 	/// The state type to use.
 	#[command(subcommand)]
 	pub state: State,
@@ -99,7 +107,10 @@ where
 	HostFns: HostFunctions,
 {
 	let executor = build_executor::<HostFns>(&shared);
-	let ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+	let mut ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+	if command.ver {
+		ver::ensure_execute_block_ver::<Block, HostFns>(&executor, &mut ext)?;
+	}
 
 	// get the block number associated with this block.
 	let block_ws_uri = command.block_ws_uri::<Block>();
@@ -126,12 +137,21 @@ where
 	// for now, hardcoded for the sake of simplicity. We might customize them one day.
 	let state_root_check = false;
 	let signature_check = false;
-	let payload = (block.clone(), state_root_check, signature_check, command.try_state).encode();
+	let (method, payload) = if command.ver {
+		let author = ver::aura_block_author::<Block, HostFns>(&ext, &executor, block.header())?;
+		let payload =
+			(block.clone(), author, state_root_check, signature_check, command.try_state).encode();
+		(ver::EXECUTE_BLOCK_VER, payload)
+	} else {
+		let payload =
+			(block.clone(), state_root_check, signature_check, command.try_state).encode();
+		("TryRuntime_execute_block", payload)
+	};
 
 	let _ = state_machine_call_with_proof::<Block, HostFns>(
 		&ext,
 		&executor,
-		"TryRuntime_execute_block",
+		method,
 		&payload,
 		full_extensions(),
 		shared.export_proof,
@@ -16,8 +16,10 @@
 // limitations under the License.
 
 use crate::{
-	block_building_info::BlockBuildingInfoProvider, build_executor, full_extensions,
-	rpc_err_handler, state_machine_call, BlockT, LiveState, SharedParams, State,
+	block_building_info::BlockBuildingInfoProvider,
+	build_executor, full_extensions, rpc_err_handler, state_machine_call,
+	ver::{self, SeedSigner},
+	BlockT, LiveState, SharedParams, State,
 };
 use parity_scale_codec::{Decode, Encode};
 use sc_cli::Result;
@@ -27,9 +29,10 @@ use sp_core::H256;
 use sp_inherents::{InherentData, InherentDataProvider};
 use sp_io::TestExternalities;
 use sp_runtime::{
-	traits::{Header, NumberFor, One},
-	Digest,
+	traits::{Hash, HashFor, Header, NumberFor, One},
+	ApplyExtrinsicResult, Digest, StateVersion,
 };
+use sp_ver::RANDOM_SEED_INHERENT_IDENTIFIER;
 use std::{fmt::Debug, str::FromStr};
 use substrate_rpc_client::{ws_client, ChainApi};
 
@@ -62,6 +65,20 @@ pub struct FastForwardCmd {
 	///   round-robin fashion.
 	#[arg(long, default_value = "all")]
 	try_state: frame_try_runtime::TryStateSelect,
+
+	/// Produce VER blocks and execute them with `TryRuntime_execute_block_ver`.
+	///
+	/// Blocks are built the same way the VER block builder does: txs enqueued by the parent block
+	/// are popped from the storage queue and executed, while no new txs get enqueued, so the
+	/// storage queue of the initial state is drained.
+	#[arg(long)]
+	ver: bool,
+
+	/// Secret URI of the sr25519 key used to sign shuffling seeds of VER blocks.
+	///
+	/// Seeds are verified against that key instead of the AURA author of the slot.
+	#[arg(long, default_value = "//Alice")]
+	ver_key: String,
 }
 
 impl FastForwardCmd {
@@ -76,11 +93,8 @@ impl FastForwardCmd {
 	}
 }
 
-/// Read the block number corresponding to `hash` with an RPC call to `ws_uri`.
-async fn get_block_number<Block: BlockT>(
-	hash: Block::Hash,
-	ws_uri: &str,
-) -> Result<NumberFor<Block>>
+/// Read the header corresponding to `hash` with an RPC call to `ws_uri`.
+async fn get_header<Block: BlockT>(hash: Block::Hash, ws_uri: &str) -> Result<Block::Header>
 where
 	Block::Header: DeserializeOwned,
 {
@@ -88,7 +102,7 @@ where
 	Ok(ChainApi::<(), Block::Hash, Block::Header, ()>::header(&rpc, Some(hash))
 		.await
 		.map_err(rpc_err_handler)
-		.and_then(|maybe_header| maybe_header.ok_or("header_not_found").map(|h| *h.number()))?)
+		.and_then(|maybe_header| maybe_header.ok_or("header_not_found"))?)
 }
 
 /// Call `method` with `data` and return the result. `externalities` will not change.
@@ -137,20 +151,16 @@ async fn run<Block: BlockT, HostFns: HostFunctions>(
 	Ok(())
 }
 
-/// Produce next empty block.
-async fn next_empty_block<
+/// Create inherent data and pre-runtime digest of the block built on top of `parent_hash`.
+async fn block_building_info<
 	Block: BlockT,
-	HostFns: HostFunctions,
 	BBIP: BlockBuildingInfoProvider<Block, Option<(InherentData, Digest)>>,
 >(
-	externalities: &mut TestExternalities,
-	executor: &WasmExecutor<HostFns>,
-	parent_height: NumberFor<Block>,
 	parent_hash: Block::Hash,
 	block_building_info_provider: &Option<BBIP>,
 	previous_block_building_info: Option<(InherentData, Digest)>,
-) -> Result<(Block, Option<(InherentData, Digest)>)> {
-	let (maybe_inherent_data, pre_digest) = match &block_building_info_provider {
+) -> Result<(Option<InherentData>, Digest)> {
+	Ok(match &block_building_info_provider {
 		None => (None, Default::default()),
 		Some(bbip) => {
 			let (inherent_data_provider, pre_digest) = bbip
@@ -163,7 +173,28 @@ async fn next_empty_block<
 
 			(Some(inherent_data), Digest { logs: pre_digest })
 		},
-	};
+	})
+}
+
+/// Produce next empty block.
+async fn next_empty_block<
+	Block: BlockT,
+	HostFns: HostFunctions,
+	BBIP: BlockBuildingInfoProvider<Block, Option<(InherentData, Digest)>>,
+>(
+	externalities: &mut TestExternalities,
+	executor: &WasmExecutor<HostFns>,
+	parent_height: NumberFor<Block>,
+	parent_hash: Block::Hash,
+	block_building_info_provider: &Option<BBIP>,
+	previous_block_building_info: Option<(InherentData, Digest)>,
+) -> Result<(Block, Option<(InherentData, Digest)>)> {
+	let (maybe_inherent_data, pre_digest) = block_building_info::<Block, BBIP>(
+		parent_hash,
+		block_building_info_provider,
+		previous_block_building_info,
+	)
+	.await?;
 
 	let header = Block::Header::new(
 		parent_height + One::one(),
@@ -204,6 +235,142 @@ async fn next_empty_block<
 	Ok((Block::new(header, extrinsics), (maybe_inherent_data.map(|id| (id, pre_digest)))))
 }
 
+/// Produce next VER block.
+///
+/// Follows the VER block builder: inherents are applied first, then txs enqueued by the parent
+/// block are popped from the storage queue and executed, and finally the inherent that enqueues
+/// new txs is applied, with no txs to enqueue.
+async fn next_empty_block_ver<
+	Block: BlockT,
+	HostFns: HostFunctions,
+	BBIP: BlockBuildingInfoProvider<Block, Option<(InherentData, Digest)>>,
+>(
+	externalities: &mut TestExternalities,
+	executor: &WasmExecutor<HostFns>,
+	parent_header: &Block::Header,
+	seed_signer: &SeedSigner,
+	block_building_info_provider: &Option<BBIP>,
+	previous_block_building_info: Option<(InherentData, Digest)>,
+) -> Result<(Block, Option<(InherentData, Digest)>)> {
+	let (maybe_inherent_data, pre_digest) = block_building_info::<Block, BBIP>(
+		parent_header.hash(),
+		block_building_info_provider,
+		previous_block_building_info,
+	)
+	.await?;
+
+	let seed = seed_signer.next_seed(parent_header.seed())?;
+	let mut inherent_data = maybe_inherent_data.clone().unwrap_or_default();
+	inherent_data.replace_data(RANDOM_SEED_INHERENT_IDENTIFIER, &seed);
+
+	let header = Block::Header::new(
+		*parent_header.number() + One::one(),
+		Default::default(),
+		Default::default(),
+		parent_header.hash(),
+		pre_digest.clone(),
+	);
+
+	run::<Block, _>(externalities, executor, "Core_initialize_block", &header.encode()).await?;
+
+	let inherents = dry_run::<Vec<Block::Extrinsic>, Block, _>(
+		externalities,
+		executor,
+		"BlockBuilder_inherent_extrinsics",
+		&inherent_data.encode(),
+	)
+	.await?;
+
+	for xt in &inherents {
+		run::<Block, _>(externalities, executor, "BlockBuilder_apply_extrinsic", &xt.encode())
+			.await?;
+	}
+
+	run::<Block, _>(externalities, executor, "VerApi_store_seed", &seed.seed.encode()).await?;
+
+	let previous_block_txs = dry_run::<Vec<Vec<u8>>, Block, _>(
+		externalities,
+		executor,
+		"VerApi_get_previous_block_txs",
+		&[0u8; 0],
+	)
+	.await?;
+	let previous_block_txs_count = previous_block_txs.len();
+
+	let mut txs = <Vec<Block::Extrinsic>>::new();
+	for tx_bytes in previous_block_txs {
+		let xt = match Block::Extrinsic::decode(&mut tx_bytes.as_slice()) {
+			Ok(xt) => xt,
+			Err(_) => {
+				log::warn!("cannot decode enqueued tx");
+				continue
+			},
+		};
+
+		// failed txs are still included in the block, unless there is no room for them.
+		let result = dry_run::<ApplyExtrinsicResult, Block, _>(
+			externalities,
+			executor,
+			"BlockBuilder_apply_extrinsic",
+			&tx_bytes,
+		)
+		.await?;
+		match result {
+			Err(e) if e.exhausted_resources() => break,
+			Err(e) => log::warn!("enqueued tx execution failed: {:?}", e),
+			Ok(_) => {},
+		}
+
+		run::<Block, _>(externalities, executor, "BlockBuilder_apply_extrinsic", &tx_bytes).await?;
+		txs.push(xt);
+	}
+
+	run::<Block, _>(externalities, executor, "VerApi_pop_txs", &(txs.len() as u64).encode())
+		.await?;
+	log::info!("Executed {}/{} previous block transactions", txs.len(), previous_block_txs_count);
+
+	let enqueue_txs_inherent = dry_run::<Block::Extrinsic, Block, _>(
+		externalities,
+		executor,
+		"VerApi_create_enqueue_txs_inherent",
+		&<Vec<Block::Extrinsic>>::new().encode(),
+	)
+	.await?;
+	run::<Block, _>(
+		externalities,
+		executor,
+		"BlockBuilder_apply_extrinsic",
+		&enqueue_txs_inherent.encode(),
+	)
+	.await?;
+
+	let mut header = dry_run::<Block::Header, Block, _>(
+		externalities,
+		executor,
+		"BlockBuilder_finalize_block",
+		&[0u8; 0],
+	)
+	.await?;
+
+	run::<Block, _>(externalities, executor, "BlockBuilder_finalize_block", &[0u8; 0]).await?;
+
+	let popped_txs_count = txs.len() as u32;
+	let extrinsics: Vec<_> = inherents
+		.into_iter()
+		.chain(txs)
+		.chain(std::iter::once(enqueue_txs_inherent))
+		.collect();
+
+	header.set_extrinsics_root(HashFor::<Block>::ordered_trie_root(
+		extrinsics.iter().map(Encode::encode).collect(),
+		StateVersion::V0,
+	));
+	header.set_seed(seed);
+	header.set_count(popped_txs_count.into());
+
+	Ok((Block::new(header, extrinsics), (maybe_inherent_data.map(|id| (id, pre_digest)))))
+}
+
 pub(crate) async fn fast_forward<Block, HostFns, BBIP>(
 	shared: SharedParams,
 	command: FastForwardCmd,
@@ -220,11 +387,16 @@ where
 	BBIP: BlockBuildingInfoProvider<Block, Option<(InherentData, Digest)>>,
 {
 	let executor = build_executor::<HostFns>(&shared);
-	let ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+	let mut ext = command.state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+
+	let seed_signer = if command.ver {
+		ver::ensure_execute_block_ver::<Block, HostFns>(&executor, &mut ext)?;
+		Some(SeedSigner::new(&command.ver_key)?)
+	} else {
+		None
+	};
 
-	let mut last_block_hash = ext.block_hash;
-	let mut last_block_number =
-		get_block_number::<Block>(last_block_hash, command.block_ws_uri()).await?;
+	let mut last_header = get_header::<Block>(ext.block_hash, command.block_ws_uri()).await?;
 	let mut prev_block_building_info = None;
 
 	let mut ext = ext.inner_ext;
@@ -233,17 +405,30 @@ where
 		// We are saving state before we overwrite it while producing new block.
 		let backend = ext.as_backend();
 
-		log::info!("Producing new empty block at height {:?}", last_block_number + One::one());
-
-		let (next_block, new_block_building_info) = next_empty_block::<Block, HostFns, BBIP>(
-			&mut ext,
-			&executor,
-			last_block_number,
-			last_block_hash,
-			&block_building_info_provider,
-			prev_block_building_info,
-		)
-		.await?;
+		log::info!("Producing new empty block at height {:?}", *last_header.number() + One::one());
+
+		let (next_block, new_block_building_info) = match seed_signer {
+			Some(ref seed_signer) =>
+				next_empty_block_ver::<Block, HostFns, BBIP>(
+					&mut ext,
+					&executor,
+					&last_header,
+					seed_signer,
+					&block_building_info_provider,
+					prev_block_building_info,
+				)
+				.await?,
+			None =>
+				next_empty_block::<Block, HostFns, BBIP>(
+					&mut ext,
+					&executor,
+					*last_header.number(),
+					last_header.hash(),
+					&block_building_info_provider,
+					prev_block_building_info,
+				)
+				.await?,
+		};
 
 		log::info!("Produced a new block: {:?}", next_block.header());
 
@@ -252,16 +437,30 @@ where
 
 		let state_root_check = true;
 		let signature_check = true;
-		let payload =
-			(next_block.clone(), state_root_check, signature_check, command.try_state.clone())
-				.encode();
-		run::<Block, _>(&mut ext, &executor, "TryRuntime_execute_block", &payload).await?;
+		let (method, payload) = match seed_signer {
+			Some(ref seed_signer) => (
+				ver::EXECUTE_BLOCK_VER,
+				(
+					next_block.clone(),
+					seed_signer.public(),
+					state_root_check,
+					signature_check,
+					command.try_state.clone(),
+				)
+					.encode(),
+			),
+			None => (
+				"TryRuntime_execute_block",
+				(next_block.clone(), state_root_check, signature_check, command.try_state.clone())
+					.encode(),
+			),
+		};
+		run::<Block, _>(&mut ext, &executor, method, &payload).await?;
 
 		log::info!("Executed the new block");
 
 		prev_block_building_info = new_block_building_info;
-		last_block_hash = next_block.hash();
-		last_block_number += One::one();
+		last_header = next_block.header().clone();
 	}
 
 	Ok(())
@@ -16,7 +16,7 @@
 // limitations under the License.
 
 use crate::{
-	build_executor, full_extensions, parse, rpc_err_handler, state_machine_call_with_proof,
+	build_executor, full_extensions, parse, rpc_err_handler, state_machine_call_with_proof, ver,
 	LiveState, SharedParams, State, LOG_TARGET,
 };
 use parity_scale_codec::{Decode, Encode};
@@ -59,6 +59,13 @@ pub struct FollowChainCmd {
 	/// If present, a single connection to a node will be kept and reused for fetching blocks.
 	#[arg(long)]
 	pub keep_connection: bool,
+
+	/// Execute blocks as VER blocks.
+	///
+	/// Shuffling seeds are verified against the AURA authors of blocks, and txs popped from the
+	/// storage queue are executed, the same way collators do.
+	#[arg(long)]
+	pub ver: bool,
 }
 
 /// Start listening for with `SUB` at `url`.
@@ -137,18 +144,40 @@ where
 				pallet: vec![],
 				child_tree: true,
 			});
-			let ext = state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+			let mut ext = state.into_ext::<Block, HostFns>(&shared, &executor, None, true).await?;
+			if command.ver {
+				ver::ensure_execute_block_ver::<Block, HostFns>(&executor, &mut ext)?;
+			}
 			maybe_state_ext = Some(ext);
 		}
 
 		let state_ext =
 			maybe_state_ext.as_mut().expect("state_ext either existed or was just created");
 
+		let (method, payload) = if command.ver {
+			// spec version of the tested runtime is most likely different, see the crate docs.
+			let signature_check = false;
+			let author =
+				ver::aura_block_author::<Block, HostFns>(state_ext, &executor, block.header())?;
+			let payload = (
+				block,
+				author,
+				command.state_root_check,
+				signature_check,
+				command.try_state.clone(),
+			)
+				.encode();
+			(ver::EXECUTE_BLOCK_VER, payload)
+		} else {
+			let payload = (block, command.state_root_check, command.try_state.clone()).encode();
+			("TryRuntime_execute_block", payload)
+		};
+
 		let result = state_machine_call_with_proof::<Block, HostFns>(
 			state_ext,
 			&executor,
-			"TryRuntime_execute_block",
-			(block, command.state_root_check, command.try_state.clone()).encode().as_ref(),
+			method,
+			&payload,
 			full_extensions(),
 			shared
 				.export_proof
@@ -120,6 +120,11 @@
 //! 2. replaying all transactions will fail, because the spec-version is part of the transaction
 //!    signature.
 //!
+//! The same applies to VER blocks executed with `--ver`, which call into
+//! `TryRuntime_execute_block_ver` instead. Such blocks are replayed the same way collators execute
+//! them: the shuffling seed is verified against the AURA author of the block, and txs popped from
+//! the storage queue are executed before new txs get enqueued.
+//!
 //! ## Best Practices
 //!
 //! Try-runtime is all about battle-testing unreleased runtime. The following list of suggestions
@@ -395,6 +400,7 @@ use std::{fmt::Debug, path::PathBuf, str::FromStr};
 pub mod block_building_info;
 pub mod commands;
 pub(crate) mod parse;
+pub(crate) mod ver;
 pub(crate) const LOG_TARGET: &str = "try-runtime::cli";
 
 /// Possible commands of `try-runtime`.
@@ -416,6 +422,8 @@ pub enum Command {
 	/// as state-root and signature checks are always disabled, and additional checks like
 	/// `try-state` can be enabled.
 	///
+	/// VER blocks are executed with "TryRuntime_execute_block_ver" instead, see `--ver`.
+	///
 	/// See [`frame_try_runtime::TryRuntime`] and [`commands::execute_block::ExecuteBlockCmd`] for
 	/// more information.
 	ExecuteBlock(commands::execute_block::ExecuteBlockCmd),
@@ -455,6 +463,8 @@ pub enum Command {
 	///    execute every block immediately
 	///  - the only data that will be put into blocks are pre-runtime digest items and inherent
 	///    extrinsics; both things should be defined in your node CLI handling level
+	///  - with `--ver`, VER blocks also execute txs popped from the storage queue, so queued state
+	///    of the original chain gets drained
 	FastForward(commands::fast_forward::FastForwardCmd),
 
 	/// Create a new snapshot file.
@@ -845,6 +855,14 @@ fn ensure_try_runtime<Block: BlockT, HostFns: HostFunctions>(
 	executor: &WasmExecutor<HostFns>,
 	ext: &mut TestExternalities,
 ) -> bool {
+	try_runtime_api_version::<Block, HostFns>(executor, ext).is_some()
+}
+
+/// Version of the `TryRuntime` api implemented by the runtime of `ext`, if any.
+pub(crate) fn try_runtime_api_version<Block: BlockT, HostFns: HostFunctions>(
+	executor: &WasmExecutor<HostFns>,
+	ext: &mut TestExternalities,
+) -> Option<u32> {
 	use sp_api::RuntimeApiInfo;
 	let final_code = ext
 		.execute_with(|| sp_io::storage::get(well_known_keys::CODE))
@@ -853,9 +871,7 @@ fn ensure_try_runtime<Block: BlockT, HostFns: HostFunctions>(
 		&mut &*executor.read_runtime_version(&final_code, &mut ext.ext()).unwrap(),
 	)
 	.unwrap();
-	final_version
-		.api_version(&<dyn frame_try_runtime::TryRuntime<Block>>::ID)
-		.is_some()
+	final_version.api_version(&<dyn frame_try_runtime::TryRuntime<Block>>::ID)
 }
 
 /// Execute the given `method` and `data` on top of `ext`, returning the results (encoded) and the
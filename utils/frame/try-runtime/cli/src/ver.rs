@@ -0,0 +1,110 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers shared by the commands that execute VER blocks.
+
+use crate::{full_extensions, state_machine_call, try_runtime_api_version, BlockT};
+use parity_scale_codec::Decode;
+use sc_executor::{sp_wasm_interface::HostFunctions, WasmExecutor};
+use sp_consensus_aura::{sr25519::AuthorityId, Slot, AURA_ENGINE_ID};
+use sp_core::{
+	crypto::{key_types::AURA, ByteArray},
+	sr25519, ShufflingSeed,
+};
+use sp_io::TestExternalities;
+use sp_keystore::{testing::KeyStore, SyncCryptoStore};
+use sp_runtime::traits::Header as HeaderT;
+
+/// Runtime api used to execute VER blocks.
+pub(crate) const EXECUTE_BLOCK_VER: &str = "TryRuntime_execute_block_ver";
+
+/// First version of the `TryRuntime` api that provides [`EXECUTE_BLOCK_VER`].
+const EXECUTE_BLOCK_VER_API_VERSION: u32 = 2;
+
+/// Ensure that the runtime of `ext` is able to execute VER blocks.
+pub(crate) fn ensure_execute_block_ver<Block: BlockT, HostFns: HostFunctions>(
+	executor: &WasmExecutor<HostFns>,
+	ext: &mut TestExternalities,
+) -> sc_cli::Result<()> {
+	match try_runtime_api_version::<Block, HostFns>(executor, ext) {
+		Some(version) if version >= EXECUTE_BLOCK_VER_API_VERSION => Ok(()),
+		_ => Err(format!(
+			"given runtime does not provide '{}', TryRuntime api version {} is required",
+			EXECUTE_BLOCK_VER, EXECUTE_BLOCK_VER_API_VERSION
+		)
+		.into()),
+	}
+}
+
+/// Public key of the author of `header`, as expected by [`EXECUTE_BLOCK_VER`].
+///
+/// The author is the AURA authority of the slot found in the pre-runtime digest of `header`,
+/// authorities are read from `ext`, which needs to be the state of the parent block.
+pub(crate) fn aura_block_author<Block: BlockT, HostFns: HostFunctions>(
+	ext: &TestExternalities,
+	executor: &WasmExecutor<HostFns>,
+	header: &Block::Header,
+) -> sc_cli::Result<Vec<u8>> {
+	let slot = header
+		.digest()
+		.logs()
+		.iter()
+		.find_map(|log| log.pre_runtime_try_to::<Slot>(&AURA_ENGINE_ID))
+		.ok_or("block does not contain AURA pre-runtime digest")?;
+
+	let (_, encoded) = state_machine_call::<Block, HostFns>(
+		ext,
+		executor,
+		"AuraApi_authorities",
+		&[],
+		full_extensions(),
+	)?;
+	let authorities = <Vec<AuthorityId> as Decode>::decode(&mut &*encoded)?;
+	if authorities.is_empty() {
+		return Err("no AURA authorities found".into())
+	}
+
+	let index = *slot % authorities.len() as u64;
+	Ok(authorities[index as usize].to_raw_vec())
+}
+
+/// Signs shuffling seeds of blocks produced by try-runtime.
+pub(crate) struct SeedSigner {
+	keystore: KeyStore,
+	public: sr25519::Public,
+}
+
+impl SeedSigner {
+	/// Create a new signer using sr25519 key derived from `suri`.
+	pub(crate) fn new(suri: &str) -> sc_cli::Result<Self> {
+		let keystore = KeyStore::new();
+		let public = SyncCryptoStore::sr25519_generate_new(&keystore, AURA, Some(suri))
+			.map_err(|e| format!("invalid seed key '{}': {:?}", suri, e))?;
+		Ok(Self { keystore, public })
+	}
+
+	/// Public key of the signer, as expected by [`EXECUTE_BLOCK_VER`].
+	pub(crate) fn public(&self) -> Vec<u8> {
+		self.public.to_raw_vec()
+	}
+
+	/// Calculate the seed of the block built on top of a block with `prev_seed`.
+	pub(crate) fn next_seed(&self, prev_seed: &ShufflingSeed) -> sc_cli::Result<ShufflingSeed> {
+		sp_ver::calculate_next_seed(&self.keystore, &self.public, prev_seed)
+			.ok_or_else(|| "cannot sign shuffling seed".into())
+	}
+}